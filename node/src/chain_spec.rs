@@ -1,6 +1,7 @@
 use evercity_runtime::pallet_evercity::account::{
-    EvercityAccountStructT, AUDITOR_ROLE_MASK, CUSTODIAN_ROLE_MASK, IMPACT_REPORTER_ROLE_MASK,
-    INVESTOR_ROLE_MASK, ISSUER_ROLE_MASK, MANAGER_ROLE_MASK, MASTER_ROLE_MASK,
+    EvercityAccountStructT, RoleMask, AUDITOR_ROLE_MASK, CUSTODIAN_ROLE_MASK,
+    IMPACT_REPORTER_ROLE_MASK, INVESTOR_ROLE_MASK, ISSUER_ROLE_MASK, MANAGER_ROLE_MASK,
+    MASTER_ROLE_MASK,
 };
 use evercity_runtime::{
     AccountId, AuraConfig, BalancesConfig, EvercityConfig, GenesisConfig, GrandpaConfig, Signature,
@@ -144,7 +145,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 fn testnet_genesis(
     wasm_binary: &[u8],
     initial_authorities: Vec<(AuraId, GrandpaId)>,
-    endowed_accounts: Vec<(AccountId, u8)>,
+    endowed_accounts: Vec<(AccountId, RoleMask)>,
     _root_key: AccountId,
 ) -> GenesisConfig {
     GenesisConfig {
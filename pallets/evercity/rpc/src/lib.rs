@@ -0,0 +1,168 @@
+//! RPC interface for pallet-evercity.
+//! Serves BondApi runtime API under "evercity_*" namespace, allowing
+//! clients to read bonds, coupon yields, packages and balances
+//! without decoding raw storage keys.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_evercity::{
+    account::EvercityAccountStructT, BondId, BondStruct, BondUnitPackage, EverUSDBalance,
+    PeriodDataStruct, PeriodYield,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_evercity::runtime_api::BondApi as BondRuntimeApi;
+
+#[rpc]
+pub trait BondApi<BlockHash, AccountId, Moment, Hash> {
+    #[rpc(name = "evercity_getImpactReports")]
+    fn get_impact_reports(
+        &self,
+        bond: BondId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<PeriodDataStruct>>;
+
+    #[rpc(name = "evercity_getBond")]
+    fn get_bond(
+        &self,
+        bond: BondId,
+        at: Option<BlockHash>,
+    ) -> Result<BondStruct<AccountId, Moment, Hash>>;
+
+    #[rpc(name = "evercity_getBondYields")]
+    fn get_bond_yields(&self, bond: BondId, at: Option<BlockHash>) -> Result<Vec<PeriodYield>>;
+
+    #[rpc(name = "evercity_getBondholderPackages")]
+    fn get_bondholder_packages(
+        &self,
+        bond: BondId,
+        bondholder: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<BondUnitPackage>>;
+
+    #[rpc(name = "evercity_getEverUSDBalance")]
+    fn get_everusd_balance(&self, account: AccountId, at: Option<BlockHash>)
+        -> Result<EverUSDBalance>;
+
+    #[rpc(name = "evercity_getAccount")]
+    fn get_account(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<EvercityAccountStructT<Moment>>;
+}
+
+/// Implements BondApi RPC trait, delegating calls to the runtime
+pub struct Bond<C, Block> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Bond<C, Block> {
+    pub fn new(client: Arc<C>) -> Self {
+        Bond {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+/// Error code for failed runtime API calls
+const RUNTIME_ERROR: i64 = 1;
+
+fn runtime_error(message: &str, err: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(RUNTIME_ERROR),
+        message: message.into(),
+        data: Some(format!("{:?}", err).into()),
+    }
+}
+
+impl<C, Block, AccountId, Moment, Hash> BondApi<<Block as BlockT>::Hash, AccountId, Moment, Hash>
+    for Bond<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: BondRuntimeApi<Block, AccountId, Moment, Hash>,
+    AccountId: Codec,
+    Moment: Codec,
+    Hash: Codec,
+{
+    fn get_impact_reports(
+        &self,
+        bond: BondId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<PeriodDataStruct>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        self.client
+            .runtime_api()
+            .get_impact_reports(&at, bond)
+            .map_err(|e| runtime_error("Unable to query impact reports.", e))
+    }
+
+    fn get_bond(
+        &self,
+        bond: BondId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BondStruct<AccountId, Moment, Hash>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        self.client
+            .runtime_api()
+            .get_bond(&at, bond)
+            .map_err(|e| runtime_error("Unable to query bond.", e))
+    }
+
+    fn get_bond_yields(
+        &self,
+        bond: BondId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<PeriodYield>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        self.client
+            .runtime_api()
+            .get_bond_yields(&at, bond)
+            .map_err(|e| runtime_error("Unable to query bond yields.", e))
+    }
+
+    fn get_bondholder_packages(
+        &self,
+        bond: BondId,
+        bondholder: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<BondUnitPackage>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        self.client
+            .runtime_api()
+            .get_bondholder_packages(&at, bond, bondholder)
+            .map_err(|e| runtime_error("Unable to query bondholder packages.", e))
+    }
+
+    fn get_everusd_balance(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<EverUSDBalance> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        self.client
+            .runtime_api()
+            .get_everusd_balance(&at, account)
+            .map_err(|e| runtime_error("Unable to query EverUSD balance.", e))
+    }
+
+    fn get_account(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<EvercityAccountStructT<Moment>> {
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        self.client
+            .runtime_api()
+            .get_account(&at, account)
+            .map_err(|e| runtime_error("Unable to query account.", e))
+    }
+}
@@ -1,32 +1,47 @@
 use frame_support::{
     codec::{Decode, Encode},
+    dispatch::Vec,
     sp_runtime::RuntimeDebug,
 };
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use crate::bond::BondId;
 use crate::{EverUSDBalance, Expired};
 
-pub const MASTER_ROLE_MASK: u8 = 1u8;
-pub const CUSTODIAN_ROLE_MASK: u8 = 2u8;
-pub const ISSUER_ROLE_MASK: u8 = 4u8;
-pub const INVESTOR_ROLE_MASK: u8 = 8u8;
-pub const AUDITOR_ROLE_MASK: u8 = 16u8;
-pub const MANAGER_ROLE_MASK: u8 = 32u8;
-pub const IMPACT_REPORTER_ROLE_MASK: u8 = 64u8;
+/// Bit mask of account roles
+pub type RoleMask = u32;
 
-pub const ALL_ROLES_MASK: u8 = MASTER_ROLE_MASK
+pub const MASTER_ROLE_MASK: RoleMask = 1;
+pub const CUSTODIAN_ROLE_MASK: RoleMask = 2;
+pub const ISSUER_ROLE_MASK: RoleMask = 4;
+pub const INVESTOR_ROLE_MASK: RoleMask = 8;
+pub const AUDITOR_ROLE_MASK: RoleMask = 16;
+pub const MANAGER_ROLE_MASK: RoleMask = 32;
+pub const IMPACT_REPORTER_ROLE_MASK: RoleMask = 64;
+pub const REGISTRAR_ROLE_MASK: RoleMask = 128;
+pub const PAYING_AGENT_ROLE_MASK: RoleMask = 256;
+pub const RATING_AGENT_ROLE_MASK: RoleMask = 512;
+pub const COMPLIANCE_OFFICER_ROLE_MASK: RoleMask = 1024;
+pub const KYC_PROVIDER_ROLE_MASK: RoleMask = 2048;
+
+pub const ALL_ROLES_MASK: RoleMask = MASTER_ROLE_MASK
     | CUSTODIAN_ROLE_MASK
     | ISSUER_ROLE_MASK
     | INVESTOR_ROLE_MASK
     | AUDITOR_ROLE_MASK
     | MANAGER_ROLE_MASK
-    | IMPACT_REPORTER_ROLE_MASK;
+    | IMPACT_REPORTER_ROLE_MASK
+    | REGISTRAR_ROLE_MASK
+    | PAYING_AGENT_ROLE_MASK
+    | RATING_AGENT_ROLE_MASK
+    | COMPLIANCE_OFFICER_ROLE_MASK
+    | KYC_PROVIDER_ROLE_MASK;
 
 #[inline]
-pub const fn is_roles_correct(roles: u8) -> bool {
-    // max value of any roles combinations
-    roles <= ALL_ROLES_MASK && roles > 0
+pub const fn is_roles_correct(roles: RoleMask) -> bool {
+    // only known roles, at least one
+    roles & !ALL_ROLES_MASK == 0 && roles > 0
 }
 
 /// Main structure, containing account data: roles(bit mask), identity(external id), creation_time.
@@ -34,7 +49,7 @@ pub const fn is_roles_correct(roles: u8) -> bool {
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug)]
 pub struct EvercityAccountStructT<Moment> {
-    pub roles: u8,
+    pub roles: RoleMask,
     #[codec(compact)]
     pub identity: u64,
     #[codec(compact)]
@@ -44,6 +59,85 @@ pub struct EvercityAccountStructT<Moment> {
 pub type EvercityAccountStructOf<T> =
     EvercityAccountStructT<<T as pallet_timestamp::Config>::Moment>;
 
+/// Maximal amount of bonds in the scope of role grant
+pub const MAX_ROLE_GRANT_BONDS: usize = 32;
+
+/// Restriction of single account role. Without grant the role is permanent and global.
+/// With grant the role is valid before expiry moment and only in listed bonds
+/// (in all bonds, if the list is empty)
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct AccountRoleGrantStruct<Moment> {
+    pub expiry: Option<Moment>,
+    pub bonds: Vec<BondId>,
+}
+
+pub type AccountRoleGrantStructOf<T> =
+    AccountRoleGrantStruct<<T as pallet_timestamp::Config>::Moment>;
+
+impl<Moment: core::cmp::PartialOrd> Expired<Moment> for AccountRoleGrantStruct<Moment> {
+    fn is_expired(&self, now: Moment) -> bool {
+        self.expiry.as_ref().map_or(false, |expiry| *expiry <= now)
+    }
+}
+
+impl<Moment: core::cmp::PartialOrd> AccountRoleGrantStruct<Moment> {
+    /// Checks if the role is valid at the moment in the bond.
+    /// Scoped role is not valid outside of bond context
+    pub fn is_valid(&self, now: Moment, bond: Option<&BondId>) -> bool {
+        !self.is_expired(now)
+            && (self.bonds.is_empty() || bond.map_or(false, |bond| self.bonds.contains(bond)))
+    }
+}
+
+/// ISO 3166-1 alpha-2 country code of investor jurisdiction
+pub type KycJurisdiction = [u8; 2];
+/// Level of investor accreditation: 0 - retail investor, higher levels are defined
+/// by KYC providers (qualified, professional, institutional investor, etc.)
+pub type KycAccreditationLevel = u8;
+
+#[inline]
+pub fn is_jurisdiction_correct(jurisdiction: &KycJurisdiction) -> bool {
+    jurisdiction.iter().all(|c| c.is_ascii_uppercase())
+}
+
+/// KYC attestation of account, written by account with KYC_PROVIDER role.
+/// Attestation documents are kept off-chain by the provider and identified by hash
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub struct AccountKycStruct<AccountId, Moment, Hash> {
+    pub provider: AccountId,
+    pub attestation_hash: Hash,
+    pub jurisdiction: KycJurisdiction,
+    pub accreditation: KycAccreditationLevel,
+    /// Attestation is not valid since this moment
+    #[codec(compact)]
+    pub expiry: Moment,
+}
+
+pub type AccountKycStructOf<T> = AccountKycStruct<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+    <T as frame_system::Config>::Hash,
+>;
+
+impl<AccountId, Moment: core::cmp::PartialOrd, Hash> Expired<Moment>
+    for AccountKycStruct<AccountId, Moment, Hash>
+{
+    fn is_expired(&self, now: Moment) -> bool {
+        self.expiry <= now
+    }
+}
+
+/// Identifier of mint or burn request, unique among requests of the same kind
+pub type TokenRequestId = u64;
+
+/// Maximal length of mint or burn request reference (SWIFT UETR takes 36 bytes)
+pub const MAX_TOKEN_REQUEST_REFERENCE_LEN: usize = 64;
+
+/// Maximal amount of pending mint requests and burn requests of single account
+pub const MAX_ACCOUNT_TOKEN_REQUESTS: usize = 10;
+
 /// Structure, created by Issuer or Investor to receive EverUSD on her balance
 /// by paying USD to Custodian. Then Custodian confirms request, adding corresponding
 /// amount to mint request creator's balance
@@ -54,6 +148,8 @@ pub struct TokenMintRequestStruct<Moment> {
     pub amount: EverUSDBalance,
     #[codec(compact)]
     pub deadline: Moment,
+    /// Reference of the payment to Custodian, f.e. bank transfer reference or SWIFT UETR
+    pub reference: Vec<u8>,
 }
 
 impl<Moment: core::cmp::PartialOrd> Expired<Moment> for TokenMintRequestStruct<Moment> {
@@ -69,21 +165,36 @@ pub type TokenMintRequestStructOf<T> =
 /// and receive corresponding amount of USD from Custodian.
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug)]
-pub struct TokenBurnRequestStruct<Moment> {
+pub struct TokenBurnRequestStruct<AccountId, Moment> {
     #[codec(compact)]
     pub amount: EverUSDBalance,
     #[codec(compact)]
     pub deadline: Moment,
+    /// Reference of the payout, f.e. bank account or payment instructions
+    pub reference: Vec<u8>,
+    /// Custodian, holding reserves for the payout
+    pub custodian: AccountId,
 }
 
-impl<Moment: core::cmp::PartialOrd> Expired<Moment> for TokenBurnRequestStruct<Moment> {
+impl<AccountId, Moment: core::cmp::PartialOrd> Expired<Moment>
+    for TokenBurnRequestStruct<AccountId, Moment>
+{
     fn is_expired(&self, now: Moment) -> bool {
         self.deadline <= now
     }
 }
 
-pub type TokenBurnRequestStructOf<T> =
-    TokenBurnRequestStruct<<T as pallet_timestamp::Config>::Moment>;
+impl<AccountId: PartialEq, Moment> TokenBurnRequestStruct<AccountId, Moment> {
+    /// Checks if the request can be processed by the custodian
+    pub fn is_routed_to(&self, custodian: &AccountId) -> bool {
+        self.custodian == *custodian
+    }
+}
+
+pub type TokenBurnRequestStructOf<T> = TokenBurnRequestStruct<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+>;
 
 #[impl_trait_for_tuples::impl_for_tuples(30)]
 pub trait OnAddAccount<AccountId, Moment> {
@@ -0,0 +1,1000 @@
+//! Benchmarks of pallet-evercity extrinsics.
+//! Bond setups create the worst case for each call: many bondholders in
+//! BondUnitPackageRegistry, many payment periods to accrue and many sale lots.
+//! Run them with the node "benchmark" subcommand to generate weights (see README)
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::account::{
+    AccountKycStruct, AccountRoleGrantStruct, EvercityAccountStructT, MAX_ACCOUNT_TOKEN_REQUESTS,
+    MAX_ROLE_GRANT_BONDS, MAX_TOKEN_REQUEST_REFERENCE_LEN,
+};
+use crate::bond::{
+    BondCallScheduleStruct, BondInnerStruct, BondPutScheduleStruct, BondState, BondUnitSaleLotId,
+    BondUnitSaleLotStruct, MAX_BOND_AUDITORS, MAX_RATING_LEN,
+};
+use crate::custodian::CustodianStruct;
+use crate::document::{BondDocumentStruct, BondDocumentType, MAX_BOND_DOCUMENTS, MAX_DOCUMENT_URI_LEN};
+use crate::governance::{BondProposalAction, BondProposalId, BondProposalStruct};
+use crate::order::{BondOrderSide, BondOrderStruct, MAX_BOND_ORDERS};
+use crate::restructuring::{BondRestructuringStruct, BondRestructuringTermsStruct};
+use crate::swap::BondSwapStruct;
+use crate::Module as Evercity;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::sp_runtime::{traits::Hash, SaturatedConversion};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+/// EverUSD balance, enough to pay for any bond in benchmarks
+const BALANCE: EverUSDBalance = 1_000_000_000_000_000_000;
+
+fn create_account<T: Config>(name: &'static str, index: u32, roles: RoleMask) -> T::AccountId {
+    let who: T::AccountId = account(name, index, SEED);
+    AccountRegistry::<T>::insert(
+        &who,
+        EvercityAccountStructT {
+            roles,
+            identity: 0,
+            create_time: Default::default(),
+        },
+    );
+    who
+}
+
+/// Writes KYC attestation of the account, valid for a long time
+fn set_kyc<T: Config>(who: &T::AccountId) {
+    AccountKyc::<T>::insert(
+        who,
+        AccountKycStruct {
+            provider: account("kyc", 0, SEED),
+            attestation_hash: T::Hashing::hash_of(who),
+            jurisdiction: *b"DE",
+            accreditation: 0,
+            expiry: u64::MAX.saturated_into(),
+        },
+    );
+}
+
+/// Registers the custodian, backing given amount of EverUSD, without minting limits
+fn create_custodian<T: Config>(index: u32, supply: EverUSDBalance) -> T::AccountId {
+    let custodian = create_account::<T>("custodian", index, CUSTODIAN_ROLE_MASK);
+    CustodianRegistry::<T>::insert(
+        &custodian,
+        CustodianStruct {
+            supply,
+            mint_cap: BALANCE,
+            ..Default::default()
+        },
+    );
+    custodian
+}
+
+/// Reference of mint or burn request of maximal length
+fn get_reference() -> Vec<u8> {
+    vec![b'0'; MAX_TOKEN_REQUEST_REFERENCE_LEN]
+}
+
+fn set_timestamp<T: Config>(moment: u64) {
+    pallet_timestamp::Module::<T>::set_timestamp(moment.saturated_into());
+}
+
+fn get_bond_body<T: Config>(periods: BondPeriodNumber) -> BondInnerStructOf<T> {
+    let day = T::TimeStep::get();
+    BondInnerStruct {
+        docs_pack_root_hash_main: Default::default(),
+        docs_pack_root_hash_legal: Default::default(),
+        docs_pack_root_hash_finance: Default::default(),
+        docs_pack_root_hash_tech: Default::default(),
+
+        impact_data_type: Default::default(),
+        impact_data_baseline: vec![Some(20000_u64); periods as usize],
+        impact_data_max_deviation_cap: Some(30000_u64),
+        impact_data_max_deviation_floor: Some(14000_u64),
+        impact_data_kpis: Vec::new(),
+        interest_rate_penalty_for_missed_report: Some(400),
+
+        interest_rate_base_value: 2000,
+        interest_rate_margin_cap: Some(4000),
+        interest_rate_margin_floor: Some(1000),
+        interest_rate_start_period_value: Some(1900),
+        day_count_convention: Default::default(),
+        start_period: Some(120 * day),
+        payment_period: 30 * day,
+        interest_pay_period: Some(7 * day),
+        mincap_deadline: (20 * day as u64 * 1000).saturated_into(),
+        impact_data_send_period: 10 * day,
+        bond_duration: periods,
+        bond_finishing_period: 14 * day,
+        call_schedule: None,
+        put_schedule: None,
+        amortization_schedule: Vec::new(),
+        eligibility: Default::default(),
+        lockup_period: 0,
+
+        bond_units_mincap_amount: 1,
+        bond_units_maxcap_amount: 1_000_000,
+        bond_units_base_price: 4_000_000_000_000,
+    }
+}
+
+/// Assigns "auditors" - 1 additional auditors to the bond, requiring approval of all of them
+fn set_auditors<T: Config>(bond: BondId, item: &mut BondStructOf<T>, auditors: u32) {
+    item.auditors = (1..auditors)
+        .map(|index| create_account::<T>("auditor", index, AUDITOR_ROLE_MASK))
+        .collect();
+    item.auditor_quorum = auditors;
+    BondRegistry::<T>::insert(&bond, &*item);
+}
+
+/// Registers "documents" documents of the bond with the longest URI
+fn add_documents<T: Config>(bond: BondId, uploader: &T::AccountId, documents: u32) {
+    for id in 0..documents {
+        BondDocument::<T>::insert(
+            &bond,
+            id,
+            BondDocumentStruct {
+                doc_type: BondDocumentType::LEGAL,
+                content_hash: T::Hashing::hash_of(&id),
+                uri: vec![b'x'; MAX_DOCUMENT_URI_LEN],
+                uploader: uploader.clone(),
+                version: 1,
+                ..Default::default()
+            },
+        );
+    }
+    BondDocumentCount::insert(&bond, documents);
+}
+
+/// Creates bond in given state, having "bondholders" Investors with single Bond Unit each
+fn create_bond<T: Config>(
+    bond: BondId,
+    state: BondState,
+    periods: BondPeriodNumber,
+    bondholders: u32,
+) -> BondStructOf<T> {
+    let issuer = create_account::<T>("issuer", 0, ISSUER_ROLE_MASK);
+    let auditor = create_account::<T>("auditor", 0, AUDITOR_ROLE_MASK);
+    BalanceEverUSD::<T>::insert(&issuer, BALANCE);
+
+    let mut item = BondStruct {
+        inner: get_bond_body::<T>(periods),
+        issuer,
+        auditor,
+        issued_amount: bondholders,
+        state,
+        ..Default::default()
+    };
+    for index in 0..bondholders {
+        let bondholder = create_account::<T>("bondholder", index, INVESTOR_ROLE_MASK);
+        BondUnitPackageRegistry::<T>::insert(
+            &bond,
+            &bondholder,
+            vec![BondUnitPackage {
+                bond_units: 1,
+                acquisition: 0,
+                coupon_yield: 0,
+            }],
+        );
+    }
+    BondHolderCount::insert(&bond, bondholders);
+    match item.state {
+        BondState::BOOKING => item.increase(item.par_value(bondholders)),
+        BondState::ACTIVE | BondState::BANKRUPT => {
+            BondImpactReport::insert(&bond, vec![BondImpactReportStruct::default(); periods as usize]);
+        }
+        _ => (),
+    }
+    BondRegistry::<T>::insert(&bond, &item);
+    item
+}
+
+/// Moves time to the beginning of payment period number "periods" of active bond
+/// plus "offset" seconds, so "periods" payment periods can be accrued
+fn pass_periods<T: Config>(item: &BondStructOf<T>, periods: BondPeriodNumber, offset: BondPeriod) {
+    let moment = item
+        .coupon_accrual_moment(periods - 1)
+        .expect("bond has enough periods");
+    let offset: u64 = offset as u64 * 1000 + 1;
+    pallet_timestamp::Module::<T>::set_timestamp(moment + offset.saturated_into());
+}
+
+fn get_lot<T: Config>(
+    id: BondUnitSaleLotId,
+    bond_units: BondUnitAmount,
+    amount: EverUSDBalance,
+) -> BondUnitSaleLotStructOf<T> {
+    let deadline: u64 = 1000 * 1000;
+    BondUnitSaleLotStruct {
+        id,
+        deadline: Timestamp::<T>::get() + deadline.saturated_into(),
+        new_bondholder: Default::default(),
+        bond_units,
+        amount,
+    }
+}
+
+/// Returns restructuring terms, extending bond duration and reducing par value
+fn get_restructuring_terms<T: Config>(item: &BondStructOf<T>) -> BondRestructuringTermsStruct {
+    BondRestructuringTermsStruct {
+        duration_extension: 1,
+        interest_rate_base_value: item.inner.interest_rate_base_value,
+        interest_rate_margin_cap: item.inner.interest_rate_margin_cap,
+        interest_rate_margin_floor: item.inner.interest_rate_margin_floor,
+        haircut_bps: 1000,
+    }
+}
+
+benchmarks! {
+    account_disable {
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        let who = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+    }: _(RawOrigin::Signed(master), who.clone())
+    verify {
+        assert!(!Evercity::<T>::account_is_registered(&who));
+    }
+
+    account_add_with_role_and_data {
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        let who: T::AccountId = account("investor", 0, SEED);
+    }: _(RawOrigin::Signed(master), who.clone(), INVESTOR_ROLE_MASK, 0)
+    verify {
+        assert!(Evercity::<T>::account_is_investor(&who));
+    }
+
+    account_set_with_role_and_data {
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        let who = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+    }: _(RawOrigin::Signed(master), who.clone(), ISSUER_ROLE_MASK, 0)
+    verify {
+        assert!(Evercity::<T>::account_is_issuer(&who));
+    }
+
+    account_set_role_grant {
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        let who = create_account::<T>("auditor", 0, AUDITOR_ROLE_MASK);
+        let grant = AccountRoleGrantStruct {
+            expiry: Some(u64::MAX.saturated_into()),
+            bonds: (0..MAX_ROLE_GRANT_BONDS).map(|_| BondId::default()).collect(),
+        };
+    }: _(RawOrigin::Signed(master), who.clone(), AUDITOR_ROLE_MASK, Some(grant))
+    verify {
+        assert!(AccountRoleGrant::<T>::contains_key(&who, AUDITOR_ROLE_MASK));
+    }
+
+    account_kyc_set {
+        let provider = create_account::<T>("kyc", 0, KYC_PROVIDER_ROLE_MASK);
+        let who = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        let hash = T::Hashing::hash_of(&who);
+    }: _(RawOrigin::Signed(provider), who.clone(), hash, *b"DE", 1, u64::MAX.saturated_into())
+    verify {
+        assert!(AccountKyc::<T>::contains_key(&who));
+    }
+
+    account_kyc_revoke {
+        let provider = create_account::<T>("kyc", 0, KYC_PROVIDER_ROLE_MASK);
+        let who = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        set_kyc::<T>(&who);
+    }: _(RawOrigin::Signed(provider), who.clone())
+    verify {
+        assert!(!AccountKyc::<T>::contains_key(&who));
+    }
+
+    set_transfer_kyc_required {
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+    }: _(RawOrigin::Signed(master), true)
+    verify {
+        assert!(TransferKycRequired::get());
+    }
+
+    token_mint_request_create_everusd {
+        let caller = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        // caller has maximal amount of pending requests
+        for _ in 1..MAX_ACCOUNT_TOKEN_REQUESTS {
+            Evercity::<T>::token_mint_request_create_everusd(RawOrigin::Signed(caller.clone()).into(), 1000, get_reference())?;
+        }
+        let id = MintRequestCount::get();
+    }: _(RawOrigin::Signed(caller.clone()), 1000, get_reference())
+    verify {
+        assert!(MintRequestEverUSD::<T>::contains_key(&caller, id));
+    }
+
+    token_mint_request_revoke_everusd {
+        let caller = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        Evercity::<T>::token_mint_request_create_everusd(RawOrigin::Signed(caller.clone()).into(), 1000, get_reference())?;
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert!(!MintRequestEverUSD::<T>::contains_key(&caller, 0));
+    }
+
+    token_mint_request_confirm_everusd {
+        let custodian = create_custodian::<T>(0, 0);
+        let who = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        Evercity::<T>::token_mint_request_create_everusd(RawOrigin::Signed(who.clone()).into(), 1000, get_reference())?;
+    }: _(RawOrigin::Signed(custodian), who.clone(), 0, 1000)
+    verify {
+        assert_eq!(Evercity::<T>::balance_everusd(&who), 1000);
+    }
+
+    token_mint_request_decline_everusd {
+        let custodian = create_account::<T>("custodian", 0, CUSTODIAN_ROLE_MASK);
+        let who = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        Evercity::<T>::token_mint_request_create_everusd(RawOrigin::Signed(who.clone()).into(), 1000, get_reference())?;
+    }: _(RawOrigin::Signed(custodian), who.clone(), 0)
+    verify {
+        assert!(!MintRequestEverUSD::<T>::contains_key(&who, 0));
+    }
+
+    token_burn_request_create_everusd {
+        let caller = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        let custodian = create_custodian::<T>(0, BALANCE);
+        BalanceEverUSD::<T>::insert(&caller, 1000 * MAX_ACCOUNT_TOKEN_REQUESTS as EverUSDBalance);
+        // caller has maximal amount of pending requests
+        for _ in 1..MAX_ACCOUNT_TOKEN_REQUESTS {
+            Evercity::<T>::token_burn_request_create_everusd(RawOrigin::Signed(caller.clone()).into(), 1000, get_reference(), custodian.clone())?;
+        }
+        let id = BurnRequestCount::get();
+    }: _(RawOrigin::Signed(caller.clone()), 1000, get_reference(), custodian)
+    verify {
+        assert!(BurnRequestEverUSD::<T>::contains_key(&caller, id));
+    }
+
+    token_burn_request_revoke_everusd {
+        let caller = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        let custodian = create_custodian::<T>(0, 1000);
+        BalanceEverUSD::<T>::insert(&caller, 1000);
+        Evercity::<T>::token_burn_request_create_everusd(RawOrigin::Signed(caller.clone()).into(), 1000, get_reference(), custodian)?;
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert!(!BurnRequestEverUSD::<T>::contains_key(&caller, 0));
+    }
+
+    token_burn_request_confirm_everusd {
+        let custodian = create_custodian::<T>(0, 1000);
+        let who = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        BalanceEverUSD::<T>::insert(&who, 1000);
+        TotalSupplyEverUSD::put(1000);
+        Evercity::<T>::token_burn_request_create_everusd(RawOrigin::Signed(who.clone()).into(), 1000, get_reference(), custodian.clone())?;
+    }: _(RawOrigin::Signed(custodian), who.clone(), 0, 1000)
+    verify {
+        assert_eq!(Evercity::<T>::balance_everusd(&who), 0);
+    }
+
+    token_burn_request_decline_everusd {
+        let custodian = create_custodian::<T>(0, 1000);
+        let who = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        BalanceEverUSD::<T>::insert(&who, 1000);
+        Evercity::<T>::token_burn_request_create_everusd(RawOrigin::Signed(who.clone()).into(), 1000, get_reference(), custodian.clone())?;
+    }: _(RawOrigin::Signed(custodian), who.clone(), 0)
+    verify {
+        assert!(!BurnRequestEverUSD::<T>::contains_key(&who, 0));
+    }
+
+    custodian_set {
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        let custodian = create_account::<T>("custodian", 0, CUSTODIAN_ROLE_MASK);
+    }: _(RawOrigin::Signed(master), custodian.clone(), 1000, 1000_u32.into())
+    verify {
+        assert_eq!(CustodianRegistry::<T>::get(&custodian).unwrap().mint_cap, 1000);
+    }
+
+    custodian_supply_assign {
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        // other custodians, backing the part of supply
+        for index in 1..20 {
+            create_custodian::<T>(index, 1000);
+        }
+        let custodian = create_custodian::<T>(0, 0);
+        TotalSupplyEverUSD::put(20 * 1000);
+    }: _(RawOrigin::Signed(master), custodian.clone(), 1000)
+    verify {
+        assert_eq!(CustodianRegistry::<T>::get(&custodian).unwrap().supply, 1000);
+    }
+
+    custodian_reserves_attest {
+        let auditor = create_account::<T>("auditor", 0, AUDITOR_ROLE_MASK);
+        let custodian = create_custodian::<T>(0, 1000);
+    }: _(RawOrigin::Signed(auditor), custodian.clone(), 1000, T::Hashing::hash_of(&custodian))
+    verify {
+        assert!(CustodianRegistry::<T>::get(&custodian).unwrap().is_backed());
+    }
+
+    // keep-alive transfer is the most expensive one: it also checks burn requests of sender
+    transfer_everusd {
+        let from = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        let to = create_account::<T>("investor", 1, INVESTOR_ROLE_MASK);
+        let custodian = create_custodian::<T>(0, BALANCE);
+        BalanceEverUSD::<T>::insert(&from, 2000);
+        for _ in 0..MAX_ACCOUNT_TOKEN_REQUESTS {
+            Evercity::<T>::token_burn_request_create_everusd(RawOrigin::Signed(from.clone()).into(), 50, get_reference(), custodian.clone())?;
+        }
+        TransferKycRequired::put(true);
+        set_kyc::<T>(&from);
+        set_kyc::<T>(&to);
+    }: transfer_everusd_keep_alive(RawOrigin::Signed(from), to.clone(), 1000)
+    verify {
+        assert_eq!(Evercity::<T>::balance_everusd(&to), 1000);
+    }
+
+    bond_add_new {
+        let d in 1 .. MAX_BOND_DURATION;
+        let issuer = create_account::<T>("issuer", 0, ISSUER_ROLE_MASK);
+        let bond: BondId = "BOND".into();
+        let body = get_bond_body::<T>(d);
+    }: _(RawOrigin::Signed(issuer), bond, body)
+    verify {
+        assert!(BondRegistry::<T>::contains_key(&bond));
+    }
+
+    // all bond_set_* calls have the same weight
+    bond_set {
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        let auditor = create_account::<T>("auditor", 1, AUDITOR_ROLE_MASK);
+        let bond: BondId = "BOND".into();
+        create_bond::<T>(bond, BondState::PREPARE, 1, 0);
+    }: bond_set_auditor(RawOrigin::Signed(master), bond, auditor.clone())
+    verify {
+        assert_eq!(Evercity::<T>::get_bond(&bond).auditor, auditor);
+    }
+
+    bond_set_auditors {
+        let a in 0 .. MAX_BOND_AUDITORS as u32;
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        let auditors: Vec<T::AccountId> = (0..a)
+            .map(|index| create_account::<T>("auditor", index + 1, AUDITOR_ROLE_MASK))
+            .collect();
+        let bond: BondId = "BOND".into();
+        create_bond::<T>(bond, BondState::PREPARE, 1, 0);
+    }: _(RawOrigin::Signed(master), bond, auditors.clone(), a + 1)
+    verify {
+        assert_eq!(Evercity::<T>::get_bond(&bond).auditors, auditors);
+    }
+
+    bond_update {
+        let d in 1 .. MAX_BOND_DURATION;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::PREPARE, 1, 0);
+        let body = get_bond_body::<T>(d);
+    }: _(RawOrigin::Signed(item.issuer), bond, 0, body)
+    verify {
+        assert_eq!(Evercity::<T>::get_bond(&bond).inner.bond_duration, d);
+    }
+
+    bond_document_add {
+        let n in 0 .. MAX_BOND_DOCUMENTS - 1;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::PREPARE, 1, 0);
+        add_documents::<T>(bond, &item.issuer, n);
+        let content_hash = T::Hashing::hash_of(&n);
+    }: _(RawOrigin::Signed(item.issuer), bond, BondDocumentType::LEGAL, content_hash, vec![b'x'; MAX_DOCUMENT_URI_LEN])
+    verify {
+        assert_eq!(BondDocumentCount::get(&bond), n + 1);
+    }
+
+    bond_document_amend {
+        let n in 1 .. MAX_BOND_DOCUMENTS - 1;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, 1, 0);
+        add_documents::<T>(bond, &item.issuer, n);
+        let content_hash = T::Hashing::hash_of(&n);
+    }: _(RawOrigin::Signed(item.issuer), bond, n - 1, content_hash, vec![b'x'; MAX_DOCUMENT_URI_LEN])
+    verify {
+        assert_eq!(Evercity::<T>::bond_document(&bond, n).and_then(|doc| doc.amends), Some(n - 1));
+    }
+
+    bond_document_acknowledge {
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, 1, 0);
+        add_documents::<T>(bond, &item.issuer, 1);
+    }: _(RawOrigin::Signed(item.auditor.clone()), bond, 0)
+    verify {
+        assert_eq!(Evercity::<T>::bond_document(&bond, 0).map(|doc| doc.acknowledged_by), Some(vec![item.auditor]));
+    }
+
+    bond_credit_rating_set {
+        let agent = create_account::<T>("agent", 0, RATING_AGENT_ROLE_MASK);
+        let bond: BondId = "BOND".into();
+        create_bond::<T>(bond, BondState::ACTIVE, 1, 0);
+        let rating = vec![b'A'; MAX_RATING_LEN];
+    }: _(RawOrigin::Signed(agent), bond, rating, T::Hashing::hash_of(&bond))
+    verify {
+        assert!(BondCreditRating::<T>::contains_key(&bond));
+    }
+
+    bond_allow_list_set {
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, 1, 0);
+        let investor = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+    }: _(RawOrigin::Signed(item.issuer), bond, investor.clone(), true)
+    verify {
+        assert!(BondAllowList::<T>::get(&bond, &investor));
+    }
+
+    bond_trading_halt_set {
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        let bond: BondId = "BOND".into();
+        create_bond::<T>(bond, BondState::ACTIVE, 1, 0);
+    }: _(RawOrigin::Signed(master), bond, true)
+    verify {
+        assert!(BondTradingHalt::get(&bond));
+    }
+
+    bond_unit_lockup_set {
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, 1, 1);
+        let bondholder: T::AccountId = account("bondholder", 0, SEED);
+    }: _(RawOrigin::Signed(item.issuer), bond, bondholder.clone(), Some(u64::MAX.saturated_into()))
+    verify {
+        assert!(BondUnitLockup::<T>::contains_key(&bond, &bondholder));
+    }
+
+    bond_release {
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        let bond: BondId = "BOND".into();
+        create_bond::<T>(bond, BondState::PREPARE, 1, 0);
+        set_timestamp::<T>(1000);
+    }: _(RawOrigin::Signed(master), bond, 0)
+    verify {
+        assert!(Evercity::<T>::get_bond(&bond).state == BondState::BOOKING);
+    }
+
+    // buying in ACTIVE state accrues coupon yield of all passed periods
+    bond_unit_package_buy {
+        // new bondholder is added to the bond
+        let h in 1 .. WEIGHT_BONDHOLDERS - 1;
+        let p in 1 .. WEIGHT_PERIODS;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, p, h);
+        pass_periods::<T>(&item, p, 0);
+        let caller = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        BalanceEverUSD::<T>::insert(&caller, BALANCE);
+    }: _(RawOrigin::Signed(caller.clone()), bond, 0, 1)
+    verify {
+        assert_eq!(Evercity::<T>::bond_holder_packages(&bond, &caller).len(), 1);
+        assert_eq!(Evercity::<T>::get_coupon_yields(&bond).len(), p as usize);
+    }
+
+    bond_unit_package_return {
+        let bond: BondId = "BOND".into();
+        create_bond::<T>(bond, BondState::BOOKING, 1, 1);
+        let caller: T::AccountId = account("bondholder", 0, SEED);
+    }: _(RawOrigin::Signed(caller.clone()), bond, 1)
+    verify {
+        assert!(Evercity::<T>::bond_holder_packages(&bond, &caller).is_empty());
+    }
+
+    bond_withdraw {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::BOOKING, 1, h);
+        BondRegistry::<T>::mutate(&bond, |item| item.inner.bond_units_mincap_amount = h + 1);
+        pallet_timestamp::Module::<T>::set_timestamp(item.inner.mincap_deadline);
+    }: _(RawOrigin::Signed(item.issuer), bond)
+    verify {
+        assert!(Evercity::<T>::get_bond(&bond).state == BondState::PREPARE);
+    }
+
+    bond_activate {
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        let bond: BondId = "BOND".into();
+        create_bond::<T>(bond, BondState::BOOKING, WEIGHT_PERIODS, 1);
+        set_timestamp::<T>(1000);
+    }: _(RawOrigin::Signed(master), bond, 0)
+    verify {
+        assert!(Evercity::<T>::get_bond(&bond).state == BondState::ACTIVE);
+    }
+
+    bond_impact_report_send {
+        let a in 1 .. MAX_BOND_AUDITORS as u32 + 1;
+        let bond: BondId = "BOND".into();
+        let mut item = create_bond::<T>(bond, BondState::ACTIVE, 1, 1);
+        set_auditors::<T>(bond, &mut item, a);
+        // resent report discards approvals of all auditors
+        for auditor in item.auditors.iter().chain(Some(&item.auditor)) {
+            BondImpactReportApproval::<T>::insert((bond, 0 as BondPeriodNumber), auditor, vec![10000_u64]);
+        }
+        // impact_data_send_period of start period
+        let moment = item.inner.start_period.unwrap_or(0) - item.inner.impact_data_send_period / 2;
+        set_timestamp::<T>(moment as u64 * 1000);
+    }: _(RawOrigin::Signed(item.issuer), bond, 0, vec![20000])
+    verify {
+        assert_eq!(Evercity::<T>::bond_impact_data(&bond)[0].impact_data, vec![20000]);
+    }
+
+    bond_impact_report_approve {
+        let a in 1 .. MAX_BOND_AUDITORS as u32 + 1;
+        let bond: BondId = "BOND".into();
+        let mut item = create_bond::<T>(bond, BondState::ACTIVE, 1, 1);
+        set_auditors::<T>(bond, &mut item, a);
+        let moment = item.inner.start_period.unwrap_or(0) - item.inner.impact_data_send_period / 2;
+        set_timestamp::<T>(moment as u64 * 1000);
+        Evercity::<T>::bond_impact_report_send(RawOrigin::Signed(item.issuer.clone()).into(), bond, 0, vec![20000])?;
+        // bond auditor completes the quorum
+        for auditor in item.auditors.iter() {
+            Evercity::<T>::bond_impact_report_approve(RawOrigin::Signed(auditor.clone()).into(), bond, 0, vec![20000])?;
+        }
+    }: _(RawOrigin::Signed(item.auditor), bond, 0, vec![20000])
+    verify {
+        assert!(Evercity::<T>::bond_impact_data(&bond)[0].signed);
+    }
+
+    bond_redeem {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let p in 1 .. WEIGHT_PERIODS;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, p, h);
+        // maturity date
+        pass_periods::<T>(&item, p + 1, 0);
+    }: _(RawOrigin::Signed(item.issuer), bond)
+    verify {
+        assert!(Evercity::<T>::get_bond(&bond).state == BondState::FINISHED);
+    }
+
+    bond_call {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let p in 1 .. WEIGHT_PERIODS;
+        let bond: BondId = "BOND".into();
+        let mut item = create_bond::<T>(bond, BondState::ACTIVE, p, h);
+        item.inner.call_schedule = Some(BondCallScheduleStruct {
+            call_periods: vec![p],
+            premium_bps: 100,
+        });
+        BondRegistry::<T>::insert(&bond, &item);
+        pass_periods::<T>(&item, p, 0);
+    }: _(RawOrigin::Signed(item.issuer), bond)
+    verify {
+        assert!(Evercity::<T>::get_bond(&bond).state == BondState::FINISHED);
+    }
+
+    bond_declare_bankrupt {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let p in 1 .. WEIGHT_PERIODS;
+        let master = create_account::<T>("master", 0, MASTER_ROLE_MASK);
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, p + 1, h);
+        BondRegistry::<T>::mutate(&bond, |item| item.bond_credit = 1);
+        // interest_pay_period is over
+        pass_periods::<T>(&item, p, item.inner.interest_pay_period.unwrap_or(0) + T::TimeStep::get());
+    }: _(RawOrigin::Signed(master), bond)
+    verify {
+        assert!(Evercity::<T>::get_bond(&bond).state == BondState::BANKRUPT);
+    }
+
+    bond_restructuring_propose {
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::BANKRUPT, 1, 1);
+        let terms = get_restructuring_terms::<T>(&item);
+    }: _(RawOrigin::Signed(item.issuer), bond, terms, u64::MAX.saturated_into())
+    verify {
+        assert!(BondRestructuring::<T>::contains_key(&bond));
+    }
+
+    bond_restructuring_vote {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let p in 1 .. WEIGHT_PERIODS;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::BANKRUPT, p, h);
+        // the vote of caller approves the proposal
+        BondRestructuring::<T>::insert(&bond, BondRestructuringStruct {
+            proposer: item.issuer.clone(),
+            terms: get_restructuring_terms::<T>(&item),
+            deadline: u64::MAX.saturated_into(),
+            votes_for: h / 2,
+            votes_against: 0,
+        });
+        pass_periods::<T>(&item, p, 0);
+        let caller: T::AccountId = account("bondholder", 0, SEED);
+    }: _(RawOrigin::Signed(caller), bond, true)
+    verify {
+        assert!(Evercity::<T>::get_bond(&bond).state == BondState::ACTIVE);
+    }
+
+    bond_restructuring_cancel {
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::BANKRUPT, 1, 1);
+        BondRestructuring::<T>::insert(&bond, BondRestructuringStruct {
+            proposer: item.issuer.clone(),
+            terms: get_restructuring_terms::<T>(&item),
+            deadline: u64::MAX.saturated_into(),
+            votes_for: 0,
+            votes_against: 0,
+        });
+    }: _(RawOrigin::Signed(item.issuer), bond)
+    verify {
+        assert!(!BondRestructuring::<T>::contains_key(&bond));
+    }
+
+    bond_proposal_create {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, 1, h);
+        let auditor = create_account::<T>("auditor", 1, AUDITOR_ROLE_MASK);
+        let action = BondProposalAction::SetAuditor(auditor);
+    }: _(RawOrigin::Signed(item.issuer), bond, action, u64::MAX.saturated_into())
+    verify {
+        assert!(BondProposal::<T>::contains_key(&bond, 0));
+    }
+
+    bond_proposal_vote {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, 1, h);
+        let auditor = create_account::<T>("auditor", 1, AUDITOR_ROLE_MASK);
+        let id: BondProposalId = 0;
+        // the vote of caller passes the proposal
+        BondProposal::<T>::insert(&bond, id, BondProposalStruct {
+            proposer: item.issuer.clone(),
+            action: BondProposalAction::SetAuditor(auditor.clone()),
+            deadline: u64::MAX.saturated_into(),
+            snapshot_units: h,
+            votes_for: h / 2,
+            votes_against: 0,
+        });
+        for index in 0..h {
+            let bondholder: T::AccountId = account("bondholder", index, SEED);
+            BondProposalSnapshot::<T>::insert((bond, id), &bondholder, 1);
+        }
+        let caller: T::AccountId = account("bondholder", 0, SEED);
+    }: _(RawOrigin::Signed(caller), bond, id, true)
+    verify {
+        assert!(Evercity::<T>::get_bond(&bond).auditor == auditor);
+    }
+
+    bond_proposal_cancel {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, 1, h);
+        let id: BondProposalId = 0;
+        BondProposal::<T>::insert(&bond, id, BondProposalStruct {
+            proposer: item.issuer.clone(),
+            action: BondProposalAction::Resolution(T::Hashing::hash_of(&id)),
+            deadline: u64::MAX.saturated_into(),
+            snapshot_units: h,
+            votes_for: 0,
+            votes_against: 0,
+        });
+        for index in 0..h {
+            let bondholder: T::AccountId = account("bondholder", index, SEED);
+            BondProposalSnapshot::<T>::insert((bond, id), &bondholder, 1);
+        }
+    }: _(RawOrigin::Signed(item.issuer), bond, id)
+    verify {
+        assert!(!BondProposal::<T>::contains_key(&bond, id));
+    }
+
+    bond_accrue_coupon_yield {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let p in 1 .. WEIGHT_PERIODS;
+        let caller = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, p, h);
+        pass_periods::<T>(&item, p, 0);
+    }: _(RawOrigin::Signed(caller), bond)
+    verify {
+        assert_eq!(Evercity::<T>::get_coupon_yields(&bond).len(), p as usize);
+    }
+
+    bond_revoke {
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::PREPARE, 1, 0);
+    }: _(RawOrigin::Signed(item.issuer), bond)
+    verify {
+        assert!(!BondRegistry::<T>::contains_key(&bond));
+    }
+
+    bond_withdraw_everusd {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let p in 1 .. WEIGHT_PERIODS;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, p, h);
+        BondRegistry::<T>::mutate(&bond, |item| item.bond_debit = BALANCE);
+        pass_periods::<T>(&item, p, 0);
+        let caller: T::AccountId = account("bondholder", 0, SEED);
+    }: _(RawOrigin::Signed(caller.clone()), bond)
+    verify {
+        assert!(Evercity::<T>::balance_everusd(&caller) > 0);
+    }
+
+    bond_unit_put {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let p in 1 .. WEIGHT_PERIODS;
+        let bond: BondId = "BOND".into();
+        let mut item = create_bond::<T>(bond, BondState::ACTIVE, p, h);
+        item.inner.put_schedule = Some(BondPutScheduleStruct {
+            put_periods: vec![p],
+        });
+        item.bond_debit = BALANCE;
+        BondRegistry::<T>::insert(&bond, &item);
+        pass_periods::<T>(&item, p, 0);
+        let caller: T::AccountId = account("bondholder", 0, SEED);
+    }: _(RawOrigin::Signed(caller.clone()), bond, 1)
+    verify {
+        assert!(Evercity::<T>::bond_holder_packages(&bond, &caller).is_empty());
+    }
+
+    bond_deposit_everusd {
+        let h in 1 .. WEIGHT_BONDHOLDERS;
+        let p in 1 .. WEIGHT_PERIODS;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, p, h);
+        pass_periods::<T>(&item, p, 0);
+    }: _(RawOrigin::Signed(item.issuer), bond, 1000)
+    verify {
+        assert_eq!(Evercity::<T>::get_coupon_yields(&bond).len(), p as usize);
+    }
+
+    bond_unit_lot_bid {
+        // new lot is added to the lots of caller
+        let l in 1 .. WEIGHT_LOTS - 1;
+        let bond: BondId = "BOND".into();
+        let caller = create_account::<T>("bondholder", 0, INVESTOR_ROLE_MASK);
+        set_timestamp::<T>(1000);
+        BondUnitPackageRegistry::<T>::insert(&bond, &caller, vec![BondUnitPackage {
+            bond_units: l + 1,
+            acquisition: 0,
+            coupon_yield: 0,
+        }]);
+        let lots: Vec<_> = (0..l).map(|index| get_lot::<T>(index as u64, 1, index as EverUSDBalance)).collect();
+        BondUnitPackageLot::<T>::insert(&bond, &caller, lots);
+        let lot = get_lot::<T>(0, 1, BALANCE);
+    }: _(RawOrigin::Signed(caller.clone()), bond, lot)
+    verify {
+        assert_eq!(BondUnitPackageLot::<T>::get(&bond, &caller).len(), l as usize + 1);
+    }
+
+    bond_unit_lot_settle {
+        // new bondholder is added to the bond
+        let h in 1 .. WEIGHT_BONDHOLDERS - 1;
+        let p in 1 .. WEIGHT_PERIODS;
+        let l in 1 .. WEIGHT_LOTS;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, p, h);
+        BondRegistry::<T>::mutate(&bond, |item| item.bond_debit = BALANCE);
+        pass_periods::<T>(&item, p, 0);
+        let bondholder: T::AccountId = account("bondholder", 0, SEED);
+        // lot to settle is the last one
+        let lots: Vec<_> = (0..l).map(|index| get_lot::<T>(index as u64, 1, index as EverUSDBalance + 1)).collect();
+        let lot = lots[lots.len() - 1].clone();
+        BondUnitPackageLot::<T>::insert(&bond, &bondholder, lots);
+        let caller = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        BalanceEverUSD::<T>::insert(&caller, BALANCE);
+    }: _(RawOrigin::Signed(caller.clone()), bond, bondholder, lot.id, lot.bond_units)
+    verify {
+        assert_eq!(Evercity::<T>::bond_holder_packages(&bond, &caller).len(), 1);
+    }
+
+    bond_unit_transfer {
+        // new bondholder is added to the bond
+        let h in 1 .. WEIGHT_BONDHOLDERS - 1;
+        let p in 1 .. WEIGHT_PERIODS;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, p, h);
+        BondRegistry::<T>::mutate(&bond, |item| item.bond_debit = BALANCE);
+        pass_periods::<T>(&item, p, 0);
+        let bondholder: T::AccountId = account("bondholder", 0, SEED);
+        let to = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+    }: _(RawOrigin::Signed(bondholder), bond, to.clone(), 1)
+    verify {
+        assert_eq!(Evercity::<T>::bond_holder_packages(&bond, &to).len(), 1);
+    }
+
+    bond_order_place {
+        // new bondholder is added to the bond
+        let h in 1 .. WEIGHT_BONDHOLDERS - 1;
+        let p in 1 .. WEIGHT_PERIODS;
+        let o in 1 .. MAX_BOND_ORDERS as u32;
+        let bond: BondId = "BOND".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, p, h);
+        BondRegistry::<T>::mutate(&bond, |item| item.bond_debit = BALANCE);
+        pass_periods::<T>(&item, p, 0);
+        // buy order matches "o" sell orders of different sellers
+        let asks: Vec<_> = (0..o).map(|index| {
+            let seller = create_account::<T>("seller", index, INVESTOR_ROLE_MASK);
+            BondUnitPackageRegistry::<T>::insert(&bond, &seller, vec![BondUnitPackage {
+                bond_units: 1,
+                acquisition: 0,
+                coupon_yield: 0,
+            }]);
+            BondOrderStruct {
+                id: index as u64,
+                owner: seller,
+                price: 1000,
+                bond_units: 1,
+                deadline: u64::MAX.saturated_into(),
+            }
+        }).collect();
+        BondOrderBook::<T>::insert(&bond, BondOrderSide::SELL, asks);
+        BondOrderCount::insert(&bond, o as u64);
+        let caller = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        BalanceEverUSD::<T>::insert(&caller, BALANCE);
+    }: _(RawOrigin::Signed(caller.clone()), bond, BondOrderSide::BUY, 1000, o, u64::MAX.saturated_into())
+    verify {
+        assert!(BondOrderBook::<T>::get(&bond, BondOrderSide::SELL).is_empty());
+    }
+
+    bond_order_cancel {
+        let bond: BondId = "BOND".into();
+        create_bond::<T>(bond, BondState::ACTIVE, 1, 0);
+        let caller = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        BondOrderBook::<T>::insert(&bond, BondOrderSide::BUY, vec![BondOrderStruct {
+            id: 0,
+            owner: caller.clone(),
+            price: 1000,
+            bond_units: 1,
+            deadline: u64::MAX.saturated_into(),
+        }]);
+        BondOrderCount::insert(&bond, 1);
+    }: _(RawOrigin::Signed(caller.clone()), bond, 0)
+    verify {
+        assert_eq!(Evercity::<T>::balance_everusd(&caller), 1000);
+    }
+
+    bond_swap_offer {
+        let bond: BondId = "BOND".into();
+        let bond2: BondId = "BOND2".into();
+        create_bond::<T>(bond, BondState::ACTIVE, 1, 1);
+        create_bond::<T>(bond2, BondState::ACTIVE, 1, 0);
+        let caller: T::AccountId = account("bondholder", 0, SEED);
+        BalanceEverUSD::<T>::insert(&caller, BALANCE);
+    }: _(RawOrigin::Signed(caller.clone()), bond, 1, 1000, bond2, 1, Default::default(), u64::MAX.saturated_into())
+    verify {
+        assert!(BondSwap::<T>::get(0).is_some());
+    }
+
+    bond_swap_accept {
+        let h in 2 .. WEIGHT_BONDHOLDERS;
+        let p in 1 .. WEIGHT_PERIODS;
+        let bond: BondId = "BOND".into();
+        let bond2: BondId = "BOND2".into();
+        let item = create_bond::<T>(bond, BondState::ACTIVE, p, h);
+        create_bond::<T>(bond2, BondState::ACTIVE, p, h);
+        for id in &[bond, bond2] {
+            BondRegistry::<T>::mutate(id, |item| item.bond_debit = BALANCE);
+        }
+        pass_periods::<T>(&item, p, 0);
+        let maker: T::AccountId = account("bondholder", 0, SEED);
+        let caller: T::AccountId = account("bondholder", 1, SEED);
+        BondSwap::<T>::insert(0, BondSwapStruct {
+            id: 0,
+            maker: maker.clone(),
+            counterparty: Default::default(),
+            give_bond: bond,
+            give_units: 1,
+            amount: 1000,
+            take_bond: bond2,
+            take_units: 1,
+            deadline: u64::MAX.saturated_into(),
+        });
+        BondSwapCount::put(1);
+    }: _(RawOrigin::Signed(caller), 0)
+    verify {
+        assert_eq!(Evercity::<T>::bond_holder_packages(&bond2, &maker).len(), 2);
+    }
+
+    bond_swap_cancel {
+        let bond: BondId = "BOND".into();
+        let bond2: BondId = "BOND2".into();
+        let caller = create_account::<T>("investor", 0, INVESTOR_ROLE_MASK);
+        BondSwap::<T>::insert(0, BondSwapStruct {
+            id: 0,
+            maker: caller.clone(),
+            counterparty: Default::default(),
+            give_bond: bond,
+            give_units: 1,
+            amount: 1000,
+            take_bond: bond2,
+            take_units: 1,
+            deadline: u64::MAX.saturated_into(),
+        });
+        BondSwapCount::put(1);
+    }: _(RawOrigin::Signed(caller.clone()), 0)
+    verify {
+        assert_eq!(Evercity::<T>::balance_everusd(&caller), 1000);
+    }
+}
+
+impl_benchmark_test_suite!(
+    Evercity,
+    crate::mock::new_test_ext(),
+    crate::mock::TestRuntime,
+);
@@ -1,5 +1,8 @@
+use crate::account::{
+    is_jurisdiction_correct, AccountKycStruct, KycAccreditationLevel, KycJurisdiction,
+};
 use crate::period::{PeriodDescr, PeriodIterator};
-use crate::{EverUSDBalance, Expired, MIN_BOND_DURATION};
+use crate::{EverUSDBalance, Expired, MAX_BOND_DURATION, MIN_BOND_DURATION};
 use frame_support::{
     codec::{Decode, Encode, EncodeLike},
     dispatch::{DispatchResult, Vec},
@@ -7,7 +10,7 @@ use frame_support::{
         traits::{AtLeast32Bit, SaturatedConversion, UniqueSaturatedInto},
         RuntimeDebug,
     },
-    sp_std::cmp::{min, Eq, PartialEq},
+    sp_std::cmp::{max, min, Eq, PartialEq},
     sp_std::fmt,
     sp_std::ops::Deref,
     sp_std::str::from_utf8_unchecked,
@@ -22,6 +25,21 @@ pub const DEFAULT_DAY_DURATION: u32 = 86400;
 
 pub const MIN_PAYMENT_PERIOD: BondPeriod = 1;
 
+/// Maximal amount of additional KPIs of bond
+pub const MAX_IMPACT_KPIS: usize = 8;
+/// Sum of weights of all bond KPIs, ppm
+pub const IMPACT_WEIGHT_TOTAL: u32 = 1_000_000;
+/// Maximal amount of additional auditors of bond
+pub const MAX_BOND_AUDITORS: usize = 16;
+/// Maximal amount of jurisdictions, permitted in the bond
+pub const MAX_BOND_JURISDICTIONS: usize = 32;
+/// Maximal amount of bondholders of single bond. Bounds calls, iterating over bondholders
+pub const MAX_BOND_HOLDERS: u32 = 1000;
+/// Basis points in 100%
+pub const BASIS_POINTS: u32 = 10_000;
+/// Interest rate, equal to 100% per year
+pub const INTEREST_RATE_SCALE: u128 = 100_000;
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Clone, Copy, Default, Encode, Eq, Decode, RuntimeDebug)]
 pub struct BondId([u8; 16]);
@@ -70,6 +88,7 @@ impl From<&str> for BondId {
 pub enum BondImpactType {
     POWER_GENERATED,
     CO2_EMISSIONS_REDUCTION,
+    WATER_SAVED,
 }
 
 impl Default for BondImpactType {
@@ -78,6 +97,83 @@ impl Default for BondImpactType {
     }
 }
 
+/// Day count convention, defining the part of annual coupon interest rate,
+/// accrued for given number of days of payment period
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum BondDayCountConvention {
+    /// actual days / 365
+    ACT_365,
+    /// actual days / 360
+    ACT_360,
+    /// every payment period is counted as whole number of 30-day months / 360
+    THIRTY_360,
+    /// actual days / (actual days of payment period * number of payment periods per year)
+    ACT_ACT,
+}
+
+impl Default for BondDayCountConvention {
+    fn default() -> Self {
+        BondDayCountConvention::ACT_365
+    }
+}
+
+impl BondDayCountConvention {
+    /// Returns the part of the year for "days" days of payment period,
+    /// lasting "period_days" days, as (numerator, denominator)
+    pub fn year_fraction(self, days: u32, period_days: u32) -> (u128, u128) {
+        let days = min(days, period_days) as u128;
+        let period_days = period_days as u128;
+        match self {
+            BondDayCountConvention::ACT_365 => (days, 365),
+            BondDayCountConvention::ACT_360 => (days, 360),
+            BondDayCountConvention::THIRTY_360 => {
+                // payment period length, rounded to whole months
+                let months = max((period_days + 15) / 30, 1);
+                (days * months * 30, period_days * 360)
+            }
+            BondDayCountConvention::ACT_ACT => {
+                let frequency = max((365 + period_days / 2) / max(period_days, 1), 1);
+                (days, period_days * frequency)
+            }
+        }
+    }
+}
+
+/// Applies haircut of "bps" basis points to the principal value
+pub fn apply_haircut(principal: EverUSDBalance, bps: u32) -> EverUSDBalance {
+    principal - (principal as u128 * bps as u128 / BASIS_POINTS as u128) as EverUSDBalance
+}
+
+/// <pre>
+/// Method: calc_coupon_yield(principal, bond_units, interest_rate, days, period_days, convention)
+/// Arguments: principal: EverUSDBalance - outstanding principal of single Bond Unit
+///            bond_units: BondUnitAmount - amount of Bond Units
+///            interest_rate: BondInterest - annual coupon interest rate
+///            days: u32 - number of days, Bond Units were held during the payment period
+///            period_days: u32 - length of the payment period, days
+///            convention: BondDayCountConvention - day count convention of the bond
+///
+/// Returns coupon yield of Bond Units for the payment period.
+/// All intermediate values are calculated in u128, result is rounded down once
+/// </pre>
+pub fn calc_coupon_yield(
+    principal: EverUSDBalance,
+    bond_units: BondUnitAmount,
+    interest_rate: BondInterest,
+    days: u32,
+    period_days: u32,
+    convention: BondDayCountConvention,
+) -> EverUSDBalance {
+    let (numerator, denominator) = convention.year_fraction(days, period_days);
+    if denominator == 0 {
+        return 0;
+    }
+    (principal as u128 * bond_units as u128 * interest_rate as u128 * numerator
+        / (INTEREST_RATE_SCALE * denominator)) as EverUSDBalance
+}
+
 /// Bond state
 #[allow(clippy::upper_case_acronyms)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -96,6 +192,155 @@ impl Default for BondState {
     }
 }
 
+/// Additional key performance indicator (KPI) of bond. Like the main KPI of bond
+/// (impact_data_type, impact_data_baseline, impact_data_max_deviation_cap/floor),
+/// it moves interest rate between interest_rate_margin_floor and interest_rate_margin_cap,
+/// but only by "weight" share. Main KPI has the rest of IMPACT_WEIGHT_TOTAL
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq)]
+pub struct BondImpactKpiStruct {
+    /// Type of data, sent to bond each payment_period
+    pub impact_data_type: BondImpactType,
+    /// Base values of KPI for each payment_period
+    pub impact_data_baseline: Vec<u64>,
+    /// Cap of KPI value. Values more then cap are considered equal to cap
+    #[codec(compact)]
+    pub impact_data_max_deviation_cap: u64,
+    /// Floor of KPI value. Values less then floor are considered equal to floor
+    #[codec(compact)]
+    pub impact_data_max_deviation_floor: u64,
+    /// Share of KPI in the effective interest rate, ppm
+    #[codec(compact)]
+    pub weight: u32,
+}
+
+impl BondImpactKpiStruct {
+    fn is_valid(&self, bond_duration: BondPeriodNumber) -> bool {
+        self.weight > 0
+            && self.impact_data_max_deviation_floor < self.impact_data_max_deviation_cap
+            && self.impact_data_baseline.len() == bond_duration as usize
+            && self.impact_data_baseline.iter().all(|&bl| {
+                bl <= self.impact_data_max_deviation_cap
+                    && bl >= self.impact_data_max_deviation_floor
+            })
+    }
+}
+
+/// Call option of bond: issuer can redeem the bond before maturity date,
+/// paying principal value with call premium and coupon yield accrued up to the call date
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq)]
+pub struct BondCallScheduleStruct {
+    /// Call dates: numbers of passed payment periods (in ascending order).
+    /// Bond can be called during the period, following the call date
+    pub call_periods: Vec<BondPeriodNumber>,
+    /// Call premium over principal value, basis points
+    #[codec(compact)]
+    pub premium_bps: u32,
+}
+
+/// Checks that option dates are in ascending order, between start of the bond and maturity date
+fn are_option_periods_valid(periods: &[BondPeriodNumber], bond_periods: BondPeriodNumber) -> bool {
+    !periods.is_empty()
+        && periods[0] > 0
+        && periods.windows(2).all(|pair| pair[0] < pair[1])
+        && periods[periods.len() - 1] < bond_periods
+}
+
+impl BondCallScheduleStruct {
+    fn is_valid(&self, bond_periods: BondPeriodNumber) -> bool {
+        self.premium_bps <= BASIS_POINTS && are_option_periods_valid(&self.call_periods, bond_periods)
+    }
+
+    /// Checks if the bond can be called when "period" payment periods are passed
+    pub fn is_callable(&self, period: BondPeriodNumber) -> bool {
+        self.call_periods.binary_search(&period).is_ok()
+    }
+}
+
+/// Put option of bond: bondholders can return Bond Units to the bond before maturity date,
+/// receiving principal value and accrued coupon yield from the bond fund
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq)]
+pub struct BondPutScheduleStruct {
+    /// Put dates: numbers of passed payment periods (in ascending order).
+    /// Bond Units can be put during the period, following the put date
+    pub put_periods: Vec<BondPeriodNumber>,
+}
+
+impl BondPutScheduleStruct {
+    fn is_valid(&self, bond_periods: BondPeriodNumber) -> bool {
+        are_option_periods_valid(&self.put_periods, bond_periods)
+    }
+
+    /// Checks if Bond Units can be put when "period" payment periods are passed
+    pub fn is_puttable(&self, period: BondPeriodNumber) -> bool {
+        self.put_periods.binary_search(&period).is_ok()
+    }
+}
+
+/// Investor eligibility rules of bond, checked when Bond Units are bought
+/// from the bond or from other bondholders
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq)]
+pub struct BondEligibilityStruct {
+    /// Investor must have valid KYC attestation
+    pub kyc_required: bool,
+    /// Minimal accreditation level of investor
+    pub min_accreditation: KycAccreditationLevel,
+    /// Permitted jurisdictions of investor. Empty list permits any jurisdiction
+    pub jurisdictions: Vec<KycJurisdiction>,
+    /// Only investors from allow-list of the bond (private placement)
+    pub allow_list_only: bool,
+    /// Minimal amount of Bond Units, held by investor. Seller can sell
+    /// the whole holding or keep at least this amount
+    #[codec(compact)]
+    pub min_holding: BondUnitAmount,
+    /// Maximal amount of Bond Units, held by investor
+    pub max_holding: Option<BondUnitAmount>,
+    /// Maximal number of bondholders
+    pub max_holders: Option<u32>,
+}
+
+impl BondEligibilityStruct {
+    /// Accreditation and jurisdiction rules are checked with KYC attestation only
+    fn is_valid(&self) -> bool {
+        self.jurisdictions.len() <= MAX_BOND_JURISDICTIONS
+            && self.jurisdictions.iter().all(is_jurisdiction_correct)
+            && (self.kyc_required || (self.min_accreditation == 0 && self.jurisdictions.is_empty()))
+            && self
+                .max_holding
+                .map_or(true, |max| max > 0 && max >= self.min_holding)
+            && self.max_holders.map_or(true, |max| max > 0)
+    }
+
+    /// Checks if investor can hold "holding" Bond Units
+    pub fn is_holding_eligible(&self, holding: BondUnitAmount) -> bool {
+        holding >= self.min_holding && self.max_holding.map_or(true, |max| holding <= max)
+    }
+
+    /// Checks if investor can keep "holding" Bond Units after sale
+    pub fn is_remainder_eligible(&self, holding: BondUnitAmount) -> bool {
+        holding == 0 || holding >= self.min_holding
+    }
+
+    /// Checks if KYC attestation of investor matches the rules at the moment
+    pub fn is_kyc_eligible<AccountId, Moment: PartialOrd, Hash>(
+        &self,
+        kyc: Option<&AccountKycStruct<AccountId, Moment, Hash>>,
+        now: Moment,
+    ) -> bool {
+        if !self.kyc_required {
+            return true;
+        }
+        kyc.map_or(false, |kyc| {
+            !kyc.is_expired(now)
+                && kyc.accreditation >= self.min_accreditation
+                && (self.jurisdictions.is_empty() || self.jurisdictions.contains(&kyc.jurisdiction))
+        })
+    }
+}
+
 /// Bond period parametes type, seconds
 pub type BondPeriod = u32;
 /// The number of Bond units,
@@ -104,6 +349,8 @@ pub type BondUnitAmount = u32;
 pub type BondInterest = u32;
 /// Bond period numerator
 pub type BondPeriodNumber = u32;
+/// Sale lot identifier
+pub type BondUnitSaleLotId = u64;
 
 /// Inner part of BondStruct, containing parameters, related to
 /// calculation of coupon interest rate using impact data, sent to bond.
@@ -141,6 +388,9 @@ pub struct BondInnerStruct<Moment, Hash> {
     /// are considered equal to impact_data_max_deviation_floor
     /// when calculating coupon interest_rate depending on impact_data
     pub impact_data_max_deviation_floor: Option<u64>,
+    /// Additional KPIs of bond. Impact report contains value of main KPI
+    /// followed by values of these KPIs
+    pub impact_data_kpis: Vec<BondImpactKpiStruct>,
     /// Amount of seconds before end of a payment_period
     /// when Issuer should release regular impact report (confirmed by Auditor)
     #[codec(compact)]
@@ -161,6 +411,8 @@ pub struct BondInnerStruct<Moment, Hash> {
     /// Interest rate during the start_periodm when interest rate is constant
     /// (from activation to first payment period), ppm
     pub interest_rate_start_period_value: Option<BondInterest>,
+    /// Day count convention, used to calculate coupon yield of payment period
+    pub day_count_convention: BondDayCountConvention,
     /// Period when Issuer should pay off coupon interests, sec
     pub interest_pay_period: Option<BondPeriod>,
 
@@ -188,6 +440,20 @@ pub struct BondInnerStruct<Moment, Hash> {
     #[codec(compact)]
     pub bond_finishing_period: BondPeriod,
 
+    /// Optional call option of bond
+    pub call_schedule: Option<BondCallScheduleStruct>,
+    /// Optional put option of bond
+    pub put_schedule: Option<BondPutScheduleStruct>,
+    /// Principal repayment schedule: share of par value, repaid at the end of each
+    /// payment period (including start period), basis points. The rest of par value
+    /// is repaid at maturity date. Empty schedule means no amortization
+    pub amortization_schedule: Vec<u32>,
+    /// Rules, investors must comply with to buy Bond Units
+    pub eligibility: BondEligibilityStruct,
+    /// Lock-up period after bond activation, when Bond Units cannot be sold
+    #[codec(compact)]
+    pub lockup_period: BondPeriod,
+
     /// Minimal amount(mincap_amount) of bond units should be raised up to this date,
     /// otherwise bond can be withdrawn by issuer back to BondState::PREPARE
     #[codec(compact)]
@@ -219,6 +485,7 @@ impl<Moment, Hash> BondInnerStruct<Moment, Hash> {
             && self.interest_rate_base_value == other.interest_rate_base_value
             && self.interest_rate_margin_cap == other.interest_rate_margin_cap
             && self.interest_rate_margin_floor == other.interest_rate_margin_floor
+            && self.day_count_convention == other.day_count_convention
             && self.impact_data_max_deviation_cap == other.impact_data_max_deviation_cap
             && self.impact_data_max_deviation_floor == other.impact_data_max_deviation_floor
             && self.bond_duration == other.bond_duration
@@ -226,10 +493,14 @@ impl<Moment, Hash> BondInnerStruct<Moment, Hash> {
             && self.bond_units_maxcap_amount == other.bond_units_maxcap_amount
             && self.impact_data_type == other.impact_data_type
             && self.impact_data_baseline == other.impact_data_baseline
+            && self.impact_data_kpis == other.impact_data_kpis
             && self.interest_pay_period == other.interest_pay_period
             && self.impact_data_send_period == other.impact_data_send_period
             && self.payment_period == other.payment_period
             && self.bond_finishing_period == other.bond_finishing_period
+            && self.call_schedule == other.call_schedule
+            && self.put_schedule == other.put_schedule
+            && self.amortization_schedule == other.amortization_schedule
     }
 
     /// Checks if the bond is stable based on the impact data send period - 
@@ -238,11 +509,46 @@ impl<Moment, Hash> BondInnerStruct<Moment, Hash> {
         self.impact_data_send_period == 0
     }
 
+    /// Returns the amount of values in impact report: main KPI and additional KPIs
+    pub fn impact_data_len(&self) -> usize {
+        self.impact_data_kpis.len() + 1
+    }
+
+    /// Returns share of main KPI in the effective interest rate, ppm
+    pub fn impact_data_weight(&self) -> u32 {
+        IMPACT_WEIGHT_TOTAL.saturating_sub(self.impact_data_kpis.iter().map(|kpi| kpi.weight).sum())
+    }
+
     /// Checks common bounds for all bond parameters
     fn are_common_values_valid(&self, time_step: BondPeriod) -> bool {
         self.payment_period >= MIN_PAYMENT_PERIOD * time_step &&
         self.bond_duration >= MIN_BOND_DURATION &&
-        self.bond_units_base_price > 0
+        self.bond_duration <= MAX_BOND_DURATION &&
+        self.bond_units_base_price > 0 &&
+        self.call_schedule.as_ref().map_or(true, |schedule| schedule.is_valid(self.get_periods())) &&
+        self.put_schedule.as_ref().map_or(true, |schedule| schedule.is_valid(self.get_periods())) &&
+        self.is_amortization_schedule_valid() &&
+        self.eligibility.is_valid() &&
+        is_period_muliple_of_time_step(self.lockup_period, time_step)
+    }
+
+    /// Checks that amortization schedule covers every payment period and doesn't exceed par value.
+    /// Nothing can be repaid in the first period of the bond without start period: it has zero length
+    fn is_amortization_schedule_valid(&self) -> bool {
+        self.amortization_schedule.is_empty()
+            || (self.amortization_schedule.len() == self.get_periods() as usize
+                && (self.start_period.unwrap_or(0) > 0 || self.amortization_schedule[0] == 0)
+                && self.amortization_schedule.iter().map(|&bps| bps as u64).sum::<u64>()
+                    <= BASIS_POINTS as u64)
+    }
+
+    /// Returns the number of bond payment periods, including start period
+    pub fn get_periods(&self) -> BondPeriodNumber {
+        if self.start_period.unwrap_or(0) == 0 {
+            self.bond_duration
+        } else {
+            self.bond_duration + 1
+        }
     }
 
     /// Checks if bond data is valid. For non-stable bonds: Checking mincap-maxcap, periods durations
@@ -263,6 +569,7 @@ impl<Moment, Hash> BondInnerStruct<Moment, Hash> {
                 && self.interest_rate_margin_floor.is_none()
                 && self.interest_rate_start_period_value.is_none()
                 && self.interest_pay_period.is_none()
+                && self.impact_data_kpis.is_empty()
         } else {
             // ensure that for a non-stable bond the below
             // parameters have values
@@ -292,6 +599,10 @@ impl<Moment, Hash> BondInnerStruct<Moment, Hash> {
                     bl <= self.impact_data_max_deviation_cap
                         && bl >= self.impact_data_max_deviation_floor
                 })
+                && self.impact_data_kpis.len() <= MAX_IMPACT_KPIS
+                && self.impact_data_kpis.iter().all(|kpi| kpi.is_valid(self.bond_duration))
+                && self.impact_data_kpis.iter().map(|kpi| kpi.weight as u64).sum::<u64>()
+                    <= IMPACT_WEIGHT_TOTAL as u64
         }
     }
 }
@@ -315,6 +626,11 @@ pub struct BondStruct<AccountId, Moment, Hash> {
     pub manager: AccountId,
     /// bond auditor
     pub auditor: AccountId,
+    /// additional bond auditors, confirming impact reports together with bond auditor
+    pub auditors: Vec<AccountId>,
+    /// amount of auditor approvals, required to sign impact report (0 or 1 means single approval)
+    #[codec(compact)]
+    pub auditor_quorum: u32,
     /// bond impact data reporter
     pub impact_reporter: AccountId,
     /// total amount of issued bond units
@@ -333,6 +649,13 @@ pub struct BondStruct<AccountId, Moment, Hash> {
     pub active_start_date: Moment,
     /// Bond current state (PREPARE, BOOKING, ACTIVE, BANKRUPT, FINISHED)
     pub state: BondState,
+    /// Call premium, paid to bondholders over principal value, if the bond was called
+    /// by issuer before maturity date, basis points
+    #[codec(compact)]
+    pub call_premium_bps: u32,
+    /// Haircuts of Bond Unit par value, approved in restructurings: the first payment period
+    /// with reduced principal and the haircut, basis points. Earlier periods keep original par value
+    pub principal_haircuts: Vec<(BondPeriodNumber, u32)>,
 
     //#Bond ledger
     /// Bond fund, keeping EverUSD sent to bond
@@ -365,6 +688,49 @@ impl<AccountId, Moment, Hash> BondStruct<AccountId, Moment, Hash> {
     pub fn par_value(&self, unit_amount: BondUnitAmount) -> EverUSDBalance {
         unit_amount as EverUSDBalance * self.inner.bond_units_base_price as EverUSDBalance
     }
+    /// Applies haircuts of restructurings, selected by their first period, to the principal value
+    fn apply_haircuts<F>(&self, principal: EverUSDBalance, filter: F) -> EverUSDBalance
+    where
+        F: Fn(BondPeriodNumber) -> bool,
+    {
+        self.principal_haircuts
+            .iter()
+            .filter(|(from, _)| filter(*from))
+            .fold(principal, |value, &(_, bps)| apply_haircut(value, bps))
+    }
+    /// Returns principal repayment of single Bond Unit at the end of payment period "period"
+    pub fn unit_principal_installment(&self, period: BondPeriodNumber) -> EverUSDBalance {
+        let installment = self
+            .inner
+            .amortization_schedule
+            .get(period as usize)
+            .map_or(0, |&bps| {
+                (self.inner.bond_units_base_price as u128 * bps as u128 / BASIS_POINTS as u128)
+                    as EverUSDBalance
+            });
+        self.apply_haircuts(installment, |from| from <= period)
+    }
+    /// Returns outstanding principal of single Bond Unit, when "periods" payment periods are passed
+    pub fn unit_principal(&self, periods: BondPeriodNumber) -> EverUSDBalance {
+        let mut principal = self.inner.bond_units_base_price;
+        for period in 0..=periods {
+            principal = self.apply_haircuts(principal, |from| from == period);
+            if period < periods {
+                principal = principal.saturating_sub(self.unit_principal_installment(period));
+            }
+        }
+        principal
+    }
+    /// Returns outstanding principal of unit_amount Bond units, when "periods" payment periods are passed
+    pub fn outstanding_value(&self, unit_amount: BondUnitAmount, periods: BondPeriodNumber) -> EverUSDBalance {
+        unit_amount as EverUSDBalance * self.unit_principal(periods)
+    }
+    /// Returns redemption value of unit_amount Bond units, when "periods" payment periods are passed:
+    /// outstanding principal with call premium
+    pub fn redemption_value(&self, unit_amount: BondUnitAmount, periods: BondPeriodNumber) -> EverUSDBalance {
+        let value = self.outstanding_value(unit_amount, periods);
+        value + (value as u128 * self.call_premium_bps as u128 / BASIS_POINTS as u128) as EverUSDBalance
+    }
     /// Returns true if bond has unpaid debt
     #[inline]
     pub fn is_shortage(&self) -> bool {
@@ -400,11 +766,7 @@ impl<AccountId, Moment, Hash> BondStruct<AccountId, Moment, Hash> {
 
     #[inline]
     pub fn get_periods(&self) -> BondPeriodNumber {
-        if self.inner.start_period.unwrap_or(0) == 0 {
-            self.inner.bond_duration
-        } else {
-            self.inner.bond_duration + 1
-        }
+        self.inner.get_periods()
     }
 
     #[allow(dead_code)]
@@ -417,19 +779,58 @@ impl<AccountId, Moment, Hash> BondStruct<AccountId, Moment, Hash> {
         PeriodIterator::starts_with(&self, period).next()
     }
 
-    /// Calculate coupon effective interest rate using impact_data.
+    /// Calculate coupon effective interest rate using impact_data of the period.
     /// This method moves interest_rate up and down when good or bad impact_data
-    /// is sent to bond and approved by Auditor
-    pub fn calc_effective_interest_rate(
+    /// is sent to bond and approved by Auditor.
+    /// impact_data contains value of main KPI followed by values of additional KPIs.
+    /// Interest rate of every KPI is calculated separately, then rates are combined
+    /// according to KPI weights. Missing KPI values are considered equal to baseline
+    pub fn calc_effective_interest_rate(&self, period: usize, impact_data: &[u64]) -> BondInterest {
+        let inner = &self.inner;
+        let baseline = inner.impact_data_baseline.get(period).copied().flatten().unwrap_or(0);
+        let main_rate = self.calc_kpi_interest_rate(
+            baseline,
+            inner.impact_data_max_deviation_cap.unwrap_or(0),
+            inner.impact_data_max_deviation_floor.unwrap_or(0),
+            impact_data.get(0).copied().unwrap_or(baseline),
+        );
+        if inner.impact_data_kpis.is_empty() {
+            return main_rate;
+        }
+
+        let weighted_rate: u128 = inner
+            .impact_data_kpis
+            .iter()
+            .enumerate()
+            .map(|(index, kpi)| {
+                let baseline = kpi.impact_data_baseline.get(period).copied().unwrap_or(0);
+                let rate = self.calc_kpi_interest_rate(
+                    baseline,
+                    kpi.impact_data_max_deviation_cap,
+                    kpi.impact_data_max_deviation_floor,
+                    impact_data.get(index + 1).copied().unwrap_or(baseline),
+                );
+                kpi.weight as u128 * rate as u128
+            })
+            .sum::<u128>()
+            + inner.impact_data_weight() as u128 * main_rate as u128;
+
+        (weighted_rate / IMPACT_WEIGHT_TOTAL as u128) as BondInterest
+    }
+
+    /// Calculate interest rate, using single KPI value and its baseline, cap and floor
+    pub fn calc_kpi_interest_rate(
         &self,
         impact_data_baseline: u64,
+        impact_data_max_deviation_cap: u64,
+        impact_data_max_deviation_floor: u64,
         impact_data: u64,
     ) -> BondInterest {
         let inner = &self.inner;
 
-        if impact_data >= inner.impact_data_max_deviation_cap.unwrap_or(0) {
+        if impact_data >= impact_data_max_deviation_cap {
             inner.interest_rate_margin_floor.unwrap_or(0)
-        } else if impact_data <= inner.impact_data_max_deviation_floor.unwrap_or(0) {
+        } else if impact_data <= impact_data_max_deviation_floor {
             inner.interest_rate_margin_cap.unwrap_or(0)
         } else if impact_data == impact_data_baseline {
             inner.interest_rate_base_value
@@ -437,18 +838,30 @@ impl<AccountId, Moment, Hash> BondStruct<AccountId, Moment, Hash> {
             inner.interest_rate_base_value
                 - ((impact_data - impact_data_baseline) as u128
                     * (inner.interest_rate_base_value - inner.interest_rate_margin_floor.unwrap_or(0)) as u128
-                    / (inner.impact_data_max_deviation_cap.unwrap_or(0) - impact_data_baseline) as u128)
+                    / (impact_data_max_deviation_cap - impact_data_baseline) as u128)
                     as BondInterest
         } else {
             inner.interest_rate_base_value
                 + ((impact_data_baseline - impact_data) as u128
                     * (inner.interest_rate_margin_cap.unwrap_or(0) - inner.interest_rate_base_value) as u128
-                    / (impact_data_baseline - inner.impact_data_max_deviation_floor.unwrap_or(0)) as u128)
+                    / (impact_data_baseline - impact_data_max_deviation_floor) as u128)
                     as BondInterest
         }
     }
 }
 
+impl<AccountId: PartialEq, Moment, Hash> BondStruct<AccountId, Moment, Hash> {
+    /// Checks if account is the bond auditor or one of additional bond auditors
+    pub fn is_auditor(&self, acc: &AccountId) -> bool {
+        self.auditor == *acc || self.auditors.contains(acc)
+    }
+
+    /// Returns amount of auditor approvals, required to sign impact report
+    pub fn approval_quorum(&self) -> u32 {
+        max(self.auditor_quorum, 1)
+    }
+}
+
 impl<AccountId, Moment: UniqueSaturatedInto<u64> + AtLeast32Bit + Copy, Hash>
     BondStruct<AccountId, Moment, Hash>
 {
@@ -479,9 +892,30 @@ impl<AccountId, Moment: UniqueSaturatedInto<u64> + AtLeast32Bit + Copy, Hash>
             }
         }
     }
+
+    /// Checks if lock-up period after activation of the bond is passed
+    pub fn is_lockup_passed(&self, now: Moment) -> bool {
+        self.inner.lockup_period == 0
+            || self
+                .time_passed_after_activation(now)
+                .map_or(false, |(moment, _)| moment >= self.inner.lockup_period)
+    }
+
+    /// Returns the moment when coupon yield of period number `accrued` can be accrued
+    /// (the end of the period), or None if all bond periods are already accrued
+    pub fn coupon_accrual_moment(&self, accrued: BondPeriodNumber) -> Option<Moment> {
+        if accrued > self.inner.bond_duration {
+            return None;
+        }
+        self.period_desc(accrued).map(|desc| {
+            self.active_start_date
+                + (desc.payment_period as u64 * 1000_u64).saturated_into::<Moment>()
+        })
+    }
 }
 
 /// Struct, accumulating per-account coupon_yield for each period num
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
 pub struct AccountYield {
     #[codec(compact)]
@@ -493,6 +927,7 @@ pub struct AccountYield {
 /// Pack of bond units, bought at given time, belonging to given Bearer.
 /// Created when performed a deal to aquire bond uints (booking, buy from bond, buy from market).
 /// Contains data about amount of bondholder's acquired bond units, aquisition period and coupon_yield
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
 pub struct BondUnitPackage {
     /// amount of bond units
@@ -501,11 +936,33 @@ pub struct BondUnitPackage {
     /// acquisition moment (seconds after bond start date)
     #[codec(compact)]
     pub acquisition: BondPeriod,
-    /// paid coupon yield
+    /// paid coupon yield (including paid principal installments)
     #[codec(compact)]
     pub coupon_yield: EverUSDBalance,
 }
 
+/// Maximal length of credit rating symbol
+pub const MAX_RATING_LEN: usize = 8;
+
+/// Credit rating of the bond, published by Rating Agent
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct BondCreditRatingStruct<AccountId, Moment, Hash> {
+    pub agent: AccountId,
+    /// Rating symbol in the scale of the agent, f.e. "AA+" or "Baa1"
+    pub rating: Vec<u8>,
+    /// Hash of the rating report
+    pub report_hash: Hash,
+    #[codec(compact)]
+    pub date: Moment,
+}
+
+pub type BondCreditRatingStructOf<T> = BondCreditRatingStruct<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+    <T as frame_system::Config>::Hash,
+>;
+
 /// Struct with impact_data sent to bond. In the future can become
 /// more complicated for other types of impact_data and processing logic.
 /// Field "signed" is set to true by Auditor, when impact_data is verified.
@@ -514,8 +971,8 @@ pub struct BondUnitPackage {
 pub struct BondImpactReportStruct {
     #[codec(compact)]
     pub create_period: BondPeriod,
-    #[codec(compact)]
-    pub impact_data: u64,
+    /// Values of main KPI and additional KPIs of bond
+    pub impact_data: Vec<u64>,
     pub signed: bool,
 }
 
@@ -523,7 +980,7 @@ impl Default for BondImpactReportStruct {
     fn default() -> Self {
         BondImpactReportStruct {
             create_period: 0,
-            impact_data: 0,
+            impact_data: Vec::new(),
             signed: false,
         }
     }
@@ -534,6 +991,9 @@ impl Default for BondImpactReportStruct {
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug)]
 pub struct BondUnitSaleLotStruct<AccountId, Moment> {
+    /// Lot identifier, unique within the bond. Assigned by bond_unit_lot_bid()
+    #[codec(compact)]
+    pub id: BondUnitSaleLotId,
     /// Sale lot is available for buy only before this deadline
     #[codec(compact)]
     pub deadline: Moment,
@@ -548,6 +1008,17 @@ pub struct BondUnitSaleLotStruct<AccountId, Moment> {
     pub amount: EverUSDBalance,
 }
 
+impl<AccountId, Moment> BondUnitSaleLotStruct<AccountId, Moment> {
+    /// Pro-rata price of "bond_units" Bond Units of the lot, rounded down.
+    /// Price of all remaining Bond Units is the remaining amount of the lot
+    pub fn part_amount(&self, bond_units: BondUnitAmount) -> EverUSDBalance {
+        if bond_units >= self.bond_units {
+            return self.amount;
+        }
+        (self.amount as u128 * bond_units as u128 / self.bond_units as u128) as EverUSDBalance
+    }
+}
+
 impl<AccountId, Moment: core::cmp::PartialOrd> Expired<Moment>
     for BondUnitSaleLotStruct<AccountId, Moment>
 {
@@ -608,6 +1079,40 @@ pub(crate) fn transfer_bond_units<T: crate::Config>(
     Ok(())
 }
 
+/// <pre>
+/// Method: surrender_bond_units(packages, bond_units)
+/// Arguments: packages: &mut Vec<BondUnitPackage> - pack of BU packages of bondholder
+///            bond_units: BondUnitAmount -  amount of BUs to remove
+///
+/// Internal function, called when bondholder returns BUs to the bond. Removes BUs from
+/// packages, beginning from the smallest package. Paid coupon yield of partially
+/// removed package is reduced pro-rata
+/// </pre>
+pub(crate) fn surrender_bond_units<T: crate::Config>(
+    packages: &mut Vec<BondUnitPackage>,
+    mut bond_units: BondUnitAmount,
+) -> DispatchResult {
+    packages.sort_by_key(|package| core::cmp::Reverse(package.bond_units));
+
+    while bond_units > 0 {
+        let mut last = packages
+            .pop()
+            .ok_or(crate::Error::<T>::BondParamIncorrect)?;
+        if last.bond_units > bond_units {
+            let rest = last.bond_units - bond_units;
+            last.coupon_yield =
+                (last.coupon_yield as u128 * rest as u128 / last.bond_units as u128) as EverUSDBalance;
+            last.bond_units = rest;
+            bond_units = 0;
+            packages.push(last);
+        } else {
+            bond_units -= last.bond_units;
+        }
+    }
+    packages.shrink_to_fit();
+    Ok(())
+}
+
 #[impl_trait_for_tuples::impl_for_tuples(30)]
 pub trait OnAddBond<AccountId, Moment, Hash> {
     fn on_add_bond(bondid: &BondId, bond: &mut BondStruct<AccountId, Moment, Hash>);
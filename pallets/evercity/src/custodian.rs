@@ -0,0 +1,81 @@
+use crate::EverUSDBalance;
+use frame_support::{
+    codec::{Decode, Encode},
+    sp_runtime::RuntimeDebug,
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Attestation of custodian reserves, signed by auditor
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct CustodianReservesStruct<AccountId, Moment, Hash> {
+    pub auditor: AccountId,
+    /// Reserves of the custodian, expressed in EverUSD
+    #[codec(compact)]
+    pub amount: EverUSDBalance,
+    /// Hash of the attestation report
+    pub attestation_hash: Hash,
+    #[codec(compact)]
+    pub attested_at: Moment,
+}
+
+/// Custodian, which backs a part of EverUSD supply with its reserves
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct CustodianStruct<AccountId, Moment, Hash> {
+    /// EverUSD, minted by the custodian and not yet burned
+    #[codec(compact)]
+    pub supply: EverUSDBalance,
+    /// Maximal amount of EverUSD, the custodian can mint within the mint window
+    #[codec(compact)]
+    pub mint_cap: EverUSDBalance,
+    /// Duration of the mint window. Zero window limits every single mint by mint_cap
+    #[codec(compact)]
+    pub mint_window: Moment,
+    #[codec(compact)]
+    pub window_start: Moment,
+    /// EverUSD, minted within the current mint window
+    #[codec(compact)]
+    pub window_minted: EverUSDBalance,
+    /// Last attestation of the custodian reserves
+    pub reserves: Option<CustodianReservesStruct<AccountId, Moment, Hash>>,
+}
+
+pub type CustodianStructOf<T> = CustodianStruct<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+    <T as frame_system::Config>::Hash,
+>;
+
+impl<AccountId, Moment, Hash> CustodianStruct<AccountId, Moment, Hash>
+where
+    Moment: Copy + PartialOrd + core::ops::Add<Output = Moment>,
+{
+    /// Accounts `amount` of minted EverUSD within the rolling mint window.
+    /// Returns false if the mint cap is exceeded
+    pub fn mint(&mut self, amount: EverUSDBalance, now: Moment) -> bool {
+        if now >= self.window_start + self.mint_window {
+            self.window_start = now;
+            self.window_minted = 0;
+        }
+        match self.window_minted.checked_add(amount) {
+            Some(minted) if minted <= self.mint_cap => match self.supply.checked_add(amount) {
+                Some(supply) => {
+                    self.window_minted = minted;
+                    self.supply = supply;
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Checks if the last reserves attestation covers the supply of the custodian
+    pub fn is_backed(&self) -> bool {
+        self.reserves
+            .as_ref()
+            .map_or(false, |reserves| reserves.amount >= self.supply)
+    }
+}
@@ -1,3 +1,9 @@
+//! Default weights of pallet-evercity calls.
+//! Functions with arguments depend on the number of bondholders (h), accrued payment
+//! periods (p), sale lots of bondholder (l), bond duration (d), bond auditors (a),
+//! bond documents (n) and matched orders (o), see benchmarking.rs.
+//! Runtime can use weights, generated by the node "benchmark" subcommand instead
+
 use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
 
 pub trait WeightInfo {
@@ -5,6 +11,10 @@ pub trait WeightInfo {
     fn account_disable() -> Weight;
     fn account_add_with_role_and_data() -> Weight;
     fn account_set_with_role_and_data() -> Weight;
+    fn account_set_role_grant() -> Weight;
+    fn account_kyc_set() -> Weight;
+    fn account_kyc_revoke() -> Weight;
+    fn set_transfer_kyc_required() -> Weight;
     fn token_mint_request_create_everusd() -> Weight;
     fn token_mint_request_revoke_everusd() -> Weight;
     fn token_mint_request_confirm_everusd() -> Weight;
@@ -13,24 +23,50 @@ pub trait WeightInfo {
     fn token_burn_request_revoke_everusd() -> Weight;
     fn token_burn_request_confirm_everusd() -> Weight;
     fn token_burn_request_decline_everusd() -> Weight;
-    fn bond_add_new() -> Weight;
+    fn custodian_set() -> Weight;
+    fn custodian_supply_assign() -> Weight;
+    fn custodian_reserves_attest() -> Weight;
+    fn transfer_everusd() -> Weight;
+    fn bond_add_new(d: u32) -> Weight;
     fn bond_set() -> Weight;
-    fn bond_update() -> Weight;
+    fn bond_set_auditors(a: u32) -> Weight;
+    fn bond_update(d: u32) -> Weight;
+    fn bond_document_add(n: u32) -> Weight;
+    fn bond_document_amend(n: u32) -> Weight;
+    fn bond_document_acknowledge() -> Weight;
+    fn bond_credit_rating_set() -> Weight;
+    fn bond_allow_list_set() -> Weight;
+    fn bond_trading_halt_set() -> Weight;
+    fn bond_unit_lockup_set() -> Weight;
     fn bond_release() -> Weight;
-    fn bond_unit_package_buy() -> Weight;
+    fn bond_unit_package_buy(h: u32, p: u32) -> Weight;
     fn bond_unit_package_return() -> Weight;
-    fn bond_withdraw() -> Weight;
+    fn bond_withdraw(h: u32) -> Weight;
     fn bond_activate() -> Weight;
-    fn bond_impact_report_send() -> Weight;
-    fn bond_impact_report_approve() -> Weight;
-    fn bond_redeem() -> Weight;
-    fn bond_declare_bankrupt() -> Weight;
-    fn bond_accrue_coupon_yield() -> Weight;
+    fn bond_impact_report_send(a: u32) -> Weight;
+    fn bond_impact_report_approve(a: u32) -> Weight;
+    fn bond_redeem(h: u32, p: u32) -> Weight;
+    fn bond_call(h: u32, p: u32) -> Weight;
+    fn bond_declare_bankrupt(h: u32, p: u32) -> Weight;
+    fn bond_accrue_coupon_yield(h: u32, p: u32) -> Weight;
     fn bond_revoke() -> Weight;
-    fn bond_withdraw_everusd() -> Weight;
-    fn bond_deposit_everusd() -> Weight;
-    fn bond_unit_lot_bid() -> Weight;
-    fn bond_unit_lot_settle() -> Weight;
+    fn bond_withdraw_everusd(h: u32, p: u32) -> Weight;
+    fn bond_unit_put(h: u32, p: u32) -> Weight;
+    fn bond_deposit_everusd(h: u32, p: u32) -> Weight;
+    fn bond_unit_lot_bid(l: u32) -> Weight;
+    fn bond_unit_lot_settle(h: u32, p: u32, l: u32) -> Weight;
+    fn bond_unit_transfer(h: u32, p: u32) -> Weight;
+    fn bond_order_place(h: u32, p: u32, o: u32) -> Weight;
+    fn bond_order_cancel() -> Weight;
+    fn bond_swap_offer() -> Weight;
+    fn bond_swap_accept(h: u32, p: u32) -> Weight;
+    fn bond_swap_cancel() -> Weight;
+    fn bond_restructuring_propose() -> Weight;
+    fn bond_restructuring_vote(h: u32, p: u32) -> Weight;
+    fn bond_restructuring_cancel() -> Weight;
+    fn bond_proposal_create(h: u32) -> Weight;
+    fn bond_proposal_vote(h: u32) -> Weight;
+    fn bond_proposal_cancel(h: u32) -> Weight;
 }
 
 #[allow(clippy::unnecessary_cast)]
@@ -42,7 +78,7 @@ impl WeightInfo for () {
     fn account_disable() -> Weight {
         (10000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(4_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(8_u64 as Weight))
     }
     fn account_add_with_role_and_data() -> Weight {
         (10000_u64 as Weight)
@@ -54,11 +90,31 @@ impl WeightInfo for () {
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
     }
-    fn token_mint_request_create_everusd() -> Weight {
-        (20000_u64 as Weight)
+    fn account_set_role_grant() -> Weight {
+        (15000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
     }
+    fn account_kyc_set() -> Weight {
+        (15000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn account_kyc_revoke() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(5_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn set_transfer_kyc_required() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(2_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn token_mint_request_create_everusd() -> Weight {
+        (30000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(14_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(12_u64 as Weight))
+    }
     fn token_mint_request_revoke_everusd() -> Weight {
         (10000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(2_u64 as Weight))
@@ -66,8 +122,8 @@ impl WeightInfo for () {
     }
     fn token_mint_request_confirm_everusd() -> Weight {
         (10000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(5_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().reads(6_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
     }
     fn token_mint_request_decline_everusd() -> Weight {
         (10000_u64 as Weight)
@@ -75,9 +131,9 @@ impl WeightInfo for () {
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
     }
     fn token_burn_request_create_everusd() -> Weight {
-        (10000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+        (20000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(16_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(12_u64 as Weight))
     }
     fn token_burn_request_revoke_everusd() -> Weight {
         (10000_u64 as Weight)
@@ -86,26 +142,94 @@ impl WeightInfo for () {
     }
     fn token_burn_request_confirm_everusd() -> Weight {
         (10000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(2_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
     }
     fn token_burn_request_decline_everusd() -> Weight {
         (10000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
     }
-    fn bond_add_new() -> Weight {
+    fn custodian_set() -> Weight {
         (10000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
     }
+    fn custodian_supply_assign() -> Weight {
+        (20000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(20_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn custodian_reserves_attest() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn transfer_everusd() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(18_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_add_new(d: u32) -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add((100_u64 as Weight).saturating_mul(d as Weight))
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
     fn bond_set() -> Weight {
         (10000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
     }
-    fn bond_update() -> Weight {
+    fn bond_set_auditors(a: u32) -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add((1000_u64 as Weight).saturating_mul(a as Weight))
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn bond_update(d: u32) -> Weight {
         (50000_u64 as Weight)
+            .saturating_add((100_u64 as Weight).saturating_mul(d as Weight))
+            .saturating_add(DbWeight::get().reads(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn bond_document_add(n: u32) -> Weight {
+        (20000_u64 as Weight)
+            .saturating_add((2000_u64 as Weight).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes(3_u64 as Weight))
+    }
+    fn bond_document_amend(n: u32) -> Weight {
+        (20000_u64 as Weight)
+            .saturating_add((2000_u64 as Weight).saturating_mul(n as Weight))
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(DbWeight::get().writes(4_u64 as Weight))
+    }
+    fn bond_document_acknowledge() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn bond_credit_rating_set() -> Weight {
+        (15000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn bond_allow_list_set() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn bond_trading_halt_set() -> Weight {
+        (10000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+    }
+    fn bond_unit_lockup_set() -> Weight {
+        (10000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(1_u64 as Weight))
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
     }
@@ -114,74 +238,202 @@ impl WeightInfo for () {
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
     }
-    fn bond_unit_package_buy() -> Weight {
+    fn bond_unit_package_buy(h: u32, p: u32) -> Weight {
         (1000000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(3_u64 as Weight))
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((100000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(6_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(4_u64 as Weight))
     }
     fn bond_unit_package_return() -> Weight {
         (1000000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(4_u64 as Weight))
             .saturating_add(DbWeight::get().writes(3_u64 as Weight))
     }
-    fn bond_withdraw() -> Weight {
+    fn bond_withdraw(h: u32) -> Weight {
         (1000000_u64 as Weight)
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
             .saturating_add(DbWeight::get().reads(4_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(10_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((2_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+            .saturating_add(DbWeight::get().writes((2_u64 as Weight).saturating_mul(h as Weight)))
     }
     fn bond_activate() -> Weight {
         (100000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(3_u64 as Weight))
     }
-    fn bond_impact_report_send() -> Weight {
+    fn bond_impact_report_send(a: u32) -> Weight {
         (10000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes((1_u64 as Weight).saturating_mul(a as Weight)))
     }
-    fn bond_impact_report_approve() -> Weight {
+    fn bond_impact_report_approve(a: u32) -> Weight {
         (10000_u64 as Weight)
+            .saturating_add((1000_u64 as Weight).saturating_mul(a as Weight))
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
     }
-    fn bond_redeem() -> Weight {
+    fn bond_redeem(h: u32, p: u32) -> Weight {
         (1000000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((100000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(5_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(3_u64 as Weight))
     }
-    fn bond_declare_bankrupt() -> Weight {
+    fn bond_call(h: u32, p: u32) -> Weight {
         (1000000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((100000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(5_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(3_u64 as Weight))
     }
-    fn bond_accrue_coupon_yield() -> Weight {
+    fn bond_declare_bankrupt(h: u32, p: u32) -> Weight {
         (1000000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(14_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(13_u64 as Weight))
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((100000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(5_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_accrue_coupon_yield(h: u32, p: u32) -> Weight {
+        (1000000_u64 as Weight)
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((100000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(4_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
     }
     fn bond_revoke() -> Weight {
         (10000_u64 as Weight)
             .saturating_add(DbWeight::get().reads(3_u64 as Weight))
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
     }
-    fn bond_withdraw_everusd() -> Weight {
+    fn bond_withdraw_everusd(h: u32, p: u32) -> Weight {
         (1000000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((150000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(7_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(5_u64 as Weight))
     }
-    fn bond_deposit_everusd() -> Weight {
+    fn bond_unit_put(h: u32, p: u32) -> Weight {
         (1000000_u64 as Weight)
-            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((150000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(9_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(5_u64 as Weight))
+    }
+    fn bond_deposit_everusd(h: u32, p: u32) -> Weight {
+        (1000000_u64 as Weight)
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((100000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(5_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(3_u64 as Weight))
     }
-    fn bond_unit_lot_bid() -> Weight {
+    fn bond_unit_lot_bid(l: u32) -> Weight {
         (20000_u64 as Weight)
+            .saturating_add((1000_u64 as Weight).saturating_mul(l as Weight))
             .saturating_add(DbWeight::get().reads(4_u64 as Weight))
             .saturating_add(DbWeight::get().writes(1_u64 as Weight))
     }
-    fn bond_unit_lot_settle() -> Weight {
-        (20000_u64 as Weight)
+    fn bond_unit_lot_settle(h: u32, p: u32, l: u32) -> Weight {
+        (50000_u64 as Weight)
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((200000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add((1000_u64 as Weight).saturating_mul(l as Weight))
+            .saturating_add(DbWeight::get().reads(11_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(7_u64 as Weight))
+    }
+    fn bond_unit_transfer(h: u32, p: u32) -> Weight {
+        (50000_u64 as Weight)
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((200000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(13_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(6_u64 as Weight))
+    }
+    fn bond_order_place(h: u32, p: u32, o: u32) -> Weight {
+        (100000_u64 as Weight)
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((200000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add((60000_u64 as Weight).saturating_mul(o as Weight))
             .saturating_add(DbWeight::get().reads(9_u64 as Weight))
-            .saturating_add(DbWeight::get().writes(5_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().reads((5_u64 as Weight).saturating_mul(o as Weight)))
+            .saturating_add(DbWeight::get().writes(6_u64 as Weight))
+            .saturating_add(DbWeight::get().writes((5_u64 as Weight).saturating_mul(o as Weight)))
+    }
+    fn bond_order_cancel() -> Weight {
+        (30000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_swap_offer() -> Weight {
+        (40000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(12_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(3_u64 as Weight))
+    }
+    fn bond_swap_accept(h: u32, p: u32) -> Weight {
+        (100000_u64 as Weight)
+            .saturating_add((40000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((400000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(27_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((2_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(11_u64 as Weight))
+    }
+    fn bond_swap_cancel() -> Weight {
+        (30000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_restructuring_propose() -> Weight {
+        (40000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(2_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_restructuring_vote(h: u32, p: u32) -> Weight {
+        (200000_u64 as Weight)
+            .saturating_add((20000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add((150000_u64 as Weight).saturating_mul(p as Weight))
+            .saturating_add(DbWeight::get().reads(8_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(7_u64 as Weight))
+            .saturating_add(DbWeight::get().writes((2_u64 as Weight).saturating_mul(h as Weight)))
+    }
+    fn bond_restructuring_cancel() -> Weight {
+        (30000_u64 as Weight)
+            .saturating_add(DbWeight::get().reads(2_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+    }
+    fn bond_proposal_create(h: u32) -> Weight {
+        (60000_u64 as Weight)
+            .saturating_add((10000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add(DbWeight::get().reads(5_u64 as Weight))
+            .saturating_add(DbWeight::get().reads((1_u64 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(DbWeight::get().writes(2_u64 as Weight))
+            .saturating_add(DbWeight::get().writes((1_u64 as Weight).saturating_mul(h as Weight)))
+    }
+    fn bond_proposal_vote(h: u32) -> Weight {
+        (60000_u64 as Weight)
+            .saturating_add((5000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add(DbWeight::get().reads(5_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(3_u64 as Weight))
+            .saturating_add(DbWeight::get().writes((2_u64 as Weight).saturating_mul(h as Weight)))
+    }
+    fn bond_proposal_cancel(h: u32) -> Weight {
+        (30000_u64 as Weight)
+            .saturating_add((5000_u64 as Weight).saturating_mul(h as Weight))
+            .saturating_add(DbWeight::get().reads(2_u64 as Weight))
+            .saturating_add(DbWeight::get().writes(1_u64 as Weight))
+            .saturating_add(DbWeight::get().writes((2_u64 as Weight).saturating_mul(h as Weight)))
     }
 }
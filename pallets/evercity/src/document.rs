@@ -0,0 +1,120 @@
+use crate::bond::BondInnerStruct;
+use frame_support::{
+    codec::{Decode, Encode},
+    dispatch::Vec,
+    sp_runtime::{traits::Hash as HashT, RuntimeDebug},
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Maximal amount of documents (including amendments) of single bond
+pub const MAX_BOND_DOCUMENTS: u32 = 64;
+/// Maximal length of document URI or IPFS CID
+pub const MAX_DOCUMENT_URI_LEN: usize = 256;
+
+pub type BondDocumentId = u32;
+
+/// Type of bond document. Each type has its own documentation pack
+/// with root hash, stored in BondInnerStruct
+#[allow(clippy::upper_case_acronyms)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq)]
+pub enum BondDocumentType {
+    MAIN,
+    LEGAL,
+    FINANCE,
+    TECH,
+}
+
+impl Default for BondDocumentType {
+    fn default() -> Self {
+        BondDocumentType::MAIN
+    }
+}
+
+/// Document, registered in bond documentation pack.
+/// Registered documents are never replaced: new version of the document
+/// is added as amendment, linked to the previous version
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct BondDocumentStruct<AccountId, Moment, Hash> {
+    pub doc_type: BondDocumentType,
+    /// Hash of document content
+    pub content_hash: Hash,
+    /// URI or IPFS CID of the document
+    pub uri: Vec<u8>,
+    /// Issuer, Manager or Registrar, who registered the document
+    pub uploader: AccountId,
+    #[codec(compact)]
+    pub create_date: Moment,
+    /// Version of the document, starting from 1
+    #[codec(compact)]
+    pub version: u32,
+    /// Previous version of the document, amended by this one
+    pub amends: Option<BondDocumentId>,
+    /// Next version of the document, amending this one
+    pub amended_by: Option<BondDocumentId>,
+    /// Auditors, acknowledged the document
+    pub acknowledged_by: Vec<AccountId>,
+}
+
+pub type BondDocumentStructOf<T> = BondDocumentStruct<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+    <T as frame_system::Config>::Hash,
+>;
+
+impl<AccountId, Moment, Hash> BondDocumentStruct<AccountId, Moment, Hash> {
+    /// Checks if the document is the latest version
+    pub fn is_actual(&self) -> bool {
+        self.amended_by.is_none()
+    }
+}
+
+/// Calculates root of binary Merkle tree over given leaves.
+/// Every node is a hash of concatenated child nodes, last node of odd level
+/// is moved to the next level unchanged. Returns None if there are no leaves
+pub fn merkle_root<H: HashT>(mut leaves: Vec<H::Output>) -> Option<H::Output> {
+    while leaves.len() > 1 {
+        leaves = leaves
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => H::hash_of(&(left, right)),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    leaves.pop()
+}
+
+/// Replaces documentation pack root hashes of the bond with Merkle roots over
+/// actual versions of registered documents (ordered by document id).
+/// Root hash of the pack without registered documents is left unchanged
+pub fn apply_document_roots<H: HashT, AccountId, Moment>(
+    inner: &mut BondInnerStruct<Moment, H::Output>,
+    documents: &[(BondDocumentId, BondDocumentStruct<AccountId, Moment, H::Output>)],
+) {
+    let pack_root = |doc_type: BondDocumentType| {
+        merkle_root::<H>(
+            documents
+                .iter()
+                .filter(|(_, doc)| doc.doc_type == doc_type && doc.is_actual())
+                .map(|(_, doc)| doc.content_hash)
+                .collect(),
+        )
+    };
+
+    if let Some(root) = pack_root(BondDocumentType::MAIN) {
+        inner.docs_pack_root_hash_main = root;
+    }
+    if let Some(root) = pack_root(BondDocumentType::LEGAL) {
+        inner.docs_pack_root_hash_legal = root;
+    }
+    if let Some(root) = pack_root(BondDocumentType::FINANCE) {
+        inner.docs_pack_root_hash_finance = root;
+    }
+    if let Some(root) = pack_root(BondDocumentType::TECH) {
+        inner.docs_pack_root_hash_tech = root;
+    }
+}
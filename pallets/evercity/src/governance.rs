@@ -0,0 +1,77 @@
+use crate::bond::BondUnitAmount;
+use crate::Expired;
+use frame_support::{
+    codec::{Decode, Encode},
+    sp_runtime::RuntimeDebug,
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Maximal amount of proposals, opened in the bond at the same time
+pub const MAX_BOND_PROPOSALS: usize = 10;
+/// Minimal share of issued Bond Units, held by bondholder to open a proposal, basis points
+pub const MIN_PROPOSAL_STAKE_BPS: u32 = 100;
+
+pub type BondProposalId = u32;
+
+/// Action, performed when bondholders pass the proposal
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub enum BondProposalAction<AccountId, Hash> {
+    /// Resolution without on-chain effect (waiver, consent to amendment, etc.),
+    /// identified by hash of its text
+    Resolution(Hash),
+    /// Assigns auditor to the bond. Account must have AUDITOR role
+    SetAuditor(AccountId),
+    /// Assigns impact reporter to the bond. Account must have IMPACT_REPORTER role
+    SetImpactReporter(AccountId),
+}
+
+/// Bondholder meeting proposal of ACTIVE bond. Voting power of bondholder is
+/// the amount of Bond Units, owned when the proposal was opened.
+/// Proposal passes when more than half of snapshot Bond Units vote for it,
+/// and is rejected when at least half of snapshot Bond Units vote against it
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub struct BondProposalStruct<AccountId, Moment, Hash> {
+    pub proposer: AccountId,
+    pub action: BondProposalAction<AccountId, Hash>,
+    /// Voting is closed after this deadline
+    #[codec(compact)]
+    pub deadline: Moment,
+    /// Bond Units of all bondholders at the moment of opening
+    #[codec(compact)]
+    pub snapshot_units: BondUnitAmount,
+    /// Bond Units, voted for the proposal
+    #[codec(compact)]
+    pub votes_for: BondUnitAmount,
+    /// Bond Units, voted against the proposal
+    #[codec(compact)]
+    pub votes_against: BondUnitAmount,
+}
+
+pub type BondProposalStructOf<T> = BondProposalStruct<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+    <T as frame_system::Config>::Hash,
+>;
+
+impl<AccountId, Moment: core::cmp::PartialOrd, Hash> Expired<Moment>
+    for BondProposalStruct<AccountId, Moment, Hash>
+{
+    fn is_expired(&self, now: Moment) -> bool {
+        self.deadline < now
+    }
+}
+
+impl<AccountId, Moment, Hash> BondProposalStruct<AccountId, Moment, Hash> {
+    /// Checks if more than half of snapshot Bond Units voted for the proposal
+    pub fn is_passed(&self) -> bool {
+        self.votes_for as u64 * 2 > self.snapshot_units as u64
+    }
+
+    /// Checks if the proposal cannot be passed anymore
+    pub fn is_rejected(&self) -> bool {
+        self.votes_against as u64 * 2 >= self.snapshot_units as u64
+    }
+}
@@ -7,10 +7,14 @@ pub struct EvercityBalance {
     pub account: EverUSDBalance,
     /// bond fund balance
     pub bond_fund: EverUSDBalance,
+    /// balance, locked by buy orders
+    pub order_fund: EverUSDBalance,
+    /// balance, locked by swaps
+    pub swap_fund: EverUSDBalance,
 }
 
 impl EvercityBalance {
     pub fn is_ok(&self) -> bool {
-        self.supply == self.account + self.bond_fund
+        self.supply == self.account + self.bond_fund + self.order_fund + self.swap_fund
     }
 }
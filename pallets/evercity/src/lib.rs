@@ -120,32 +120,61 @@
 #![recursion_limit = "256"]
 
 use account::{
-    is_roles_correct, EvercityAccountStructOf, EvercityAccountStructT, OnAddAccount,
-    TokenBurnRequestStruct, TokenBurnRequestStructOf, TokenMintRequestStruct,
-    TokenMintRequestStructOf, AUDITOR_ROLE_MASK, CUSTODIAN_ROLE_MASK, IMPACT_REPORTER_ROLE_MASK,
-    INVESTOR_ROLE_MASK, ISSUER_ROLE_MASK, MANAGER_ROLE_MASK, MASTER_ROLE_MASK,
+    is_jurisdiction_correct, is_roles_correct, AccountKycStruct, AccountKycStructOf,
+    AccountRoleGrantStructOf, EvercityAccountStructOf, EvercityAccountStructT,
+    KycAccreditationLevel, KycJurisdiction, OnAddAccount, TokenBurnRequestStruct,
+    TokenBurnRequestStructOf, TokenMintRequestStruct, TokenMintRequestStructOf, TokenRequestId,
+    AUDITOR_ROLE_MASK, COMPLIANCE_OFFICER_ROLE_MASK, CUSTODIAN_ROLE_MASK,
+    IMPACT_REPORTER_ROLE_MASK, INVESTOR_ROLE_MASK, ISSUER_ROLE_MASK, KYC_PROVIDER_ROLE_MASK,
+    MANAGER_ROLE_MASK, MASTER_ROLE_MASK, MAX_ACCOUNT_TOKEN_REQUESTS, MAX_ROLE_GRANT_BONDS,
+    MAX_TOKEN_REQUEST_REFERENCE_LEN, PAYING_AGENT_ROLE_MASK, RATING_AGENT_ROLE_MASK,
+    REGISTRAR_ROLE_MASK,
 };
+pub use account::RoleMask;
 use bond::{
-    transfer_bond_units, AccountYield, BondInnerStructOf, BondPeriodNumber, BondState,
-    BondUnitAmount, BondUnitSaleLotStructOf, OnAddBond,
+    calc_coupon_yield, surrender_bond_units, transfer_bond_units, AccountYield, BondInnerStructOf,
+    BondInterest, BondPeriodNumber, BondState, BondUnitAmount, BondUnitSaleLotId,
+    BondCreditRatingStruct, BondCreditRatingStructOf, BondUnitSaleLotStruct,
+    BondUnitSaleLotStructOf, OnAddBond, MAX_BOND_AUDITORS, MAX_BOND_HOLDERS, MAX_RATING_LEN,
 };
 pub use bond::{
     BondId, BondImpactReportStruct, BondPeriod, BondStruct, BondStructOf, BondUnitPackage,
     DEFAULT_DAY_DURATION,
 };
 pub use default_weight::WeightInfo;
+use document::{
+    apply_document_roots, BondDocumentId, BondDocumentStruct, BondDocumentStructOf,
+    BondDocumentType, MAX_BOND_DOCUMENTS, MAX_DOCUMENT_URI_LEN,
+};
 use frame_support::debug::native;
 use frame_support::{
+    codec::FullCodec,
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::Vec,
-    dispatch::{DispatchResult, DispatchResultWithPostInfo},
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
     ensure,
-    sp_std::cmp::{min, Eq, PartialEq},
+    sp_std::cmp::{max, min, Eq, PartialEq},
     sp_std::result::Result,
-    traits::Get,
+    storage::{StorageDoubleMap, StorageMap, StorageValue},
+    traits::{EnsureOrigin, Get},
+    transactional,
+    weights::Weight,
 };
+use custodian::{CustodianReservesStruct, CustodianStruct, CustodianStructOf};
 use frame_system::ensure_signed;
+use governance::{
+    BondProposalAction, BondProposalId, BondProposalStructOf, MAX_BOND_PROPOSALS,
+    MIN_PROPOSAL_STAKE_BPS,
+};
+use order::{
+    order_book_insert, BondOrderId, BondOrderSide, BondOrderStruct, BondOrderStructOf,
+    MAX_BOND_ORDERS,
+};
+use period::PeriodDescr;
 pub use period::{PeriodDataStruct, PeriodYield};
+use queue::{queue_slot, ExpiryItem, ExpiryItemOf, QueueIndex, QueueSlot};
+use restructuring::{BondRestructuringStructOf, BondRestructuringTermsStruct};
+use swap::{BondSwapId, BondSwapStruct, BondSwapStructOf};
 
 pub trait Config: frame_system::Config + pallet_timestamp::Config {
     type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
@@ -153,6 +182,11 @@ pub trait Config: frame_system::Config + pallet_timestamp::Config {
     type MintRequestTtl: Get<u32>;
     type MaxMintAmount: Get<EverUSDBalance>;
     type TimeStep: Get<BondPeriod>;
+    /// Origin, allowed to perform Master actions alongside accounts with MASTER role,
+    /// f.e. M-of-N collective or multisig
+    type MasterOrigin: EnsureOrigin<Self::Origin>;
+    /// Weight limit for coupon accruals and purging, performed at the beginning of block
+    type MaxAccrualWeight: Get<Weight>;
     type WeightInfo: WeightInfo;
     type OnAddAccount: OnAddAccount<Self::AccountId, Self::Moment>;
     type OnAddBond: OnAddBond<Self::AccountId, Self::Moment, Self::Hash>;
@@ -166,25 +200,45 @@ type Timestamp<T> = pallet_timestamp::Module<T>;
 
 /// EverUSD = USD * ( 10 ^ EVERUSD_DECIMALS )
 pub const EVERUSD_DECIMALS: u64 = 9;
-/// Bank's year in days
-const INTEREST_RATE_YEAR: u64 = 365;
 /// Gas limit settings for purge mint/burn requests
 const MAX_PURGE_REQUESTS: usize = 100;
+/// Amount of bondholders, assumed by weight estimation of calls, iterating over them.
+/// Equal to bondholders limit of the bond. Calls, that can count processed items,
+/// refund unused weight after dispatch
+const WEIGHT_BONDHOLDERS: u32 = MAX_BOND_HOLDERS;
+/// Amount of payment periods, assumed by weight estimation of coupon yield calculation.
+/// Equal to maximal amount of bond periods, including start period
+const WEIGHT_PERIODS: u32 = MAX_BOND_DURATION + 1;
+/// Amount of sale lots of single bondholder, assumed by weight estimation.
+/// Equal to sale lots limit of bondholder in the bond
+const WEIGHT_LOTS: u32 = 100;
 ///  Bond must have as least this amount of periods
 const MIN_BOND_DURATION: BondPeriodNumber = 1;
+///  Bond can have at most this amount of periods (without start period)
+const MAX_BOND_DURATION: BondPeriodNumber = 120;
 
 /// Evercity project types
 /// All these types must be put in CUSTOM_TYPES part of config for polkadot.js
 /// to be correctly presented in DApp
 pub mod account;
+mod benchmarking;
 pub mod bond;
+pub mod custodian;
 mod default_weight;
+pub mod document;
+pub mod governance;
+pub mod migration;
 #[cfg(test)]
 pub mod ledger;
 #[cfg(test)]
 mod mock;
+pub mod order;
 pub mod period;
+pub mod queue;
+pub mod restructuring;
 pub mod runtime_api;
+pub mod swap;
+pub mod weights;
 #[cfg(test)]
 mod tests;
 
@@ -204,31 +258,79 @@ decl_storage! {
         Fuse get(fn fuse)
             build(|config| !config.genesis_account_registry.is_empty()):
             bool;
+        /// Version of storage layout (see migration module)
+        StorageVersion get(fn storage_version)
+            build(|_| migration::STORAGE_VERSION):
+            u16;
         /// Storage map for accounts, their roles and corresponding info
         AccountRegistry
             get(fn account_registry)
             config(genesis_account_registry):
             map hasher(blake2_128_concat) T::AccountId => EvercityAccountStructOf<T>;
 
+        /// Time and bond restrictions of account roles. Key is a single role bit
+        AccountRoleGrant
+            get(fn account_role_grant):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(identity) RoleMask => Option<AccountRoleGrantStructOf<T>>;
+
+        /// KYC attestations of accounts, written by KYC providers
+        AccountKyc
+            get(fn account_kyc):
+            map hasher(blake2_128_concat) T::AccountId => Option<AccountKycStructOf<T>>;
+
+        /// EverUSD transfers between accounts require valid KYC attestation of both accounts
+        TransferKycRequired
+            get(fn transfer_kyc_required):
+            bool;
+
         /// Total supply of EverUSD token. Sum of all token balances in system
         TotalSupplyEverUSD
             get(fn total_supply_everusd):
             EverUSDBalance; // total supply of EverUSD token (u64)
 
+        /// Custodians, which back EverUSD supply with their reserves, and their minting limits.
+        /// Sum of custodian supplies is the attributed part of TotalSupplyEverUSD
+        CustodianRegistry
+            get(fn custodian_registry)
+            build(|config: &GenesisConfig<T>| config.genesis_account_registry.iter()
+                .filter(|(_, account)| account.roles & CUSTODIAN_ROLE_MASK != 0)
+                .map(|(acc, _)| (acc.clone(), CustodianStruct {
+                    mint_cap: T::MaxMintAmount::get(),
+                    ..Default::default()
+                }))
+                .collect::<Vec<_>>()):
+            map hasher(blake2_128_concat) T::AccountId => Option<CustodianStructOf<T>>;
+
+        /// EverUSD of pending burn requests, routed to the custodian.
+        /// Custodian supply must cover all of them
+        CustodianPendingBurn
+            get(fn custodian_pending_burn):
+            map hasher(blake2_128_concat) T::AccountId => EverUSDBalance;
+
         /// Storage map for EverUSD token balances
         BalanceEverUSD
             get(fn balances_everusd):
             map hasher(blake2_128_concat) T::AccountId => EverUSDBalance;
 
-        /// Storage map for EverUSD token mint requests (see TokenMintRequestStruct)
+        /// Storage map for EverUSD token mint requests of accounts (see TokenMintRequestStruct)
         MintRequestEverUSD
             get(fn mint_request_everusd):
-                map hasher(blake2_128_concat) T::AccountId => TokenMintRequestStructOf<T>;
+                double_map hasher(blake2_128_concat) T::AccountId, hasher(identity) TokenRequestId => Option<TokenMintRequestStructOf<T>>;
+
+        /// Amount of mint requests, created on the platform. Next request gets this number as id
+        MintRequestCount
+            get(fn mint_request_count):
+                TokenRequestId;
 
-        /// Storage map for EverUSD token burn requests (see TokenBurnRequestStruct)
+        /// Storage map for EverUSD token burn requests of accounts (see TokenBurnRequestStruct)
         BurnRequestEverUSD
             get(fn burn_request_everusd):
-                map hasher(blake2_128_concat) T::AccountId => TokenBurnRequestStructOf<T>;
+                double_map hasher(blake2_128_concat) T::AccountId, hasher(identity) TokenRequestId => Option<TokenBurnRequestStructOf<T>>;
+
+        /// Amount of burn requests, created on the platform. Next request gets this number as id
+        BurnRequestCount
+            get(fn burn_request_count):
+                TokenRequestId;
 
         /// Structure for storing all platform bonds.
         /// BondId is now a ticker [u8; 8]: 8-bytes unique identifier like "MUSKPWR1" or "WINDGEN2"
@@ -241,6 +343,11 @@ decl_storage! {
             get(fn bond_unit_registry):
                 double_map hasher(blake2_128_concat) BondId, hasher(blake2_128_concat) T::AccountId => Vec<BondUnitPackage>;
 
+        /// Amount of bondholders of the bond, holding non-empty Bond Unit packages
+        BondHolderCount
+            get(fn bond_holder_count):
+                map hasher(blake2_128_concat) BondId => u32;
+
         /// Bond coupon yield storage
         /// Every element has total bond yield of passed period recorded on accrual basis
         BondCouponYield
@@ -257,10 +364,143 @@ decl_storage! {
             get(fn bond_unit_lots):
                 double_map hasher(blake2_128_concat) BondId, hasher(blake2_128_concat) T::AccountId => Vec<BondUnitSaleLotStructOf<T>>;
 
+        /// Amount of sale lots, created for the bond. Next lot gets this number as id
+        BondUnitSaleLotCount
+            get(fn bond_unit_lot_count):
+                map hasher(blake2_128_concat) BondId => BondUnitSaleLotId;
+
         /// Bond impact report storage
         BondImpactReport
             get(fn impact_reports):
                 map hasher(blake2_128_concat) BondId => Vec<BondImpactReportStruct>;
+
+        /// Impact report approvals, recorded per auditor.
+        /// Every element is impact_data, confirmed by the auditor for given bond and period
+        BondImpactReportApproval
+            get(fn impact_report_approvals):
+                double_map hasher(blake2_128_concat) (BondId, BondPeriodNumber), hasher(blake2_128_concat) T::AccountId => Option<Vec<u64>>;
+
+        /// Credit rating of the bond, published by Rating Agent
+        BondCreditRating
+            get(fn bond_credit_rating):
+                map hasher(blake2_128_concat) BondId => Option<BondCreditRatingStructOf<T>>;
+
+        /// Allow-list of investors of the bond, see BondEligibilityStruct
+        BondAllowList
+            get(fn bond_allow_list):
+                double_map hasher(blake2_128_concat) BondId, hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Trading of the bond is halted by bond Manager or Master
+        BondTradingHalt
+            get(fn bond_trading_halt):
+                map hasher(blake2_128_concat) BondId => bool;
+
+        /// Lock-ups of bondholders: Bond Units of bondholder cannot be sold before the moment
+        BondUnitLockup
+            get(fn bond_unit_lockup):
+                double_map hasher(blake2_128_concat) BondId, hasher(blake2_128_concat) T::AccountId => Option<T::Moment>;
+
+        /// Bond documentation packs: documents and their amendments
+        BondDocument
+            get(fn bond_document):
+                double_map hasher(blake2_128_concat) BondId, hasher(identity) BondDocumentId => Option<BondDocumentStructOf<T>>;
+
+        /// Amount of documents, registered in bond. Next document gets this number as id
+        BondDocumentCount
+            get(fn bond_document_count):
+                map hasher(blake2_128_concat) BondId => BondDocumentId;
+
+        /// Bond order book. Orders of each side are sorted by price-time priority
+        BondOrderBook
+            get(fn bond_order_book):
+                double_map hasher(blake2_128_concat) BondId, hasher(blake2_128_concat) BondOrderSide => Vec<BondOrderStructOf<T>>;
+
+        /// Amount of orders, placed for the bond. Next order gets this number as id
+        BondOrderCount
+            get(fn bond_order_count):
+                map hasher(blake2_128_concat) BondId => BondOrderId;
+
+        /// Delivery-versus-payment swaps of Bond Units between two bonds
+        BondSwap
+            get(fn bond_swap):
+                map hasher(identity) BondSwapId => Option<BondSwapStructOf<T>>;
+
+        /// Amount of swaps, offered by investors. Next swap gets this number as id
+        BondSwapCount
+            get(fn bond_swap_count):
+                BondSwapId;
+
+        /// Restructuring proposal of BANKRUPT bond
+        BondRestructuring
+            get(fn bond_restructuring):
+                map hasher(blake2_128_concat) BondId => Option<BondRestructuringStructOf<T>>;
+
+        /// Votes of bondholders for the restructuring proposal: true - for, false - against
+        BondRestructuringVote
+            get(fn bond_restructuring_vote):
+                double_map hasher(blake2_128_concat) BondId, hasher(blake2_128_concat) T::AccountId => Option<bool>;
+
+        /// Bondholder meeting proposals of ACTIVE bond
+        BondProposal
+            get(fn bond_proposal):
+                double_map hasher(blake2_128_concat) BondId, hasher(identity) BondProposalId => Option<BondProposalStructOf<T>>;
+
+        /// Amount of proposals, opened in bond. Next proposal gets this number as id
+        BondProposalCount
+            get(fn bond_proposal_count):
+                map hasher(blake2_128_concat) BondId => BondProposalId;
+
+        /// Bond Units of bondholders at the moment of opening the proposal (voting power)
+        BondProposalSnapshot
+            get(fn bond_proposal_snapshot):
+                double_map hasher(blake2_128_concat) (BondId, BondProposalId), hasher(blake2_128_concat) T::AccountId => BondUnitAmount;
+
+        /// Votes of bondholders for the proposal: true - for, false - against
+        BondProposalVote
+            get(fn bond_proposal_vote):
+                double_map hasher(blake2_128_concat) (BondId, BondProposalId), hasher(blake2_128_concat) T::AccountId => Option<bool>;
+
+        /// Queue of ACTIVE or BANKRUPT bonds, waiting for coupon yield accrual, grouped by slots
+        /// of TimeStep duration. Every element is the moment when the next payment_period ends
+        /// and the bond. Elements of the slot are stored in order of insertion
+        BondAccrualQueue
+            get(fn bond_accrual_queue):
+                double_map hasher(twox_64_concat) QueueSlot, hasher(twox_64_concat) QueueIndex => Option<(T::Moment, BondId)>;
+
+        /// Amount of elements, ever inserted into the slot of BondAccrualQueue
+        BondAccrualQueueLen
+            get(fn bond_accrual_queue_len):
+                map hasher(twox_64_concat) QueueSlot => QueueIndex;
+
+        /// First slot of BondAccrualQueue, which can have queued bonds,
+        /// and index of the first element of the slot, which is not processed yet
+        BondAccrualCursor
+            get(fn bond_accrual_cursor):
+                Option<(QueueSlot, QueueIndex)>;
+
+        /// Moment, the bond is queued for coupon yield accrual at.
+        /// Queue elements with other moment of the bond are obsolete
+        BondAccrualMoment
+            get(fn bond_accrual_moment):
+                map hasher(blake2_128_concat) BondId => Option<T::Moment>;
+
+        /// Queue of mint and burn requests, sale lots, orders and swaps, waiting for removal
+        /// after deadline, grouped by slots of TimeStep duration.
+        /// Elements of the slot are stored in order of insertion
+        ExpiryQueue
+            get(fn expiry_queue):
+                double_map hasher(twox_64_concat) QueueSlot, hasher(twox_64_concat) QueueIndex => Option<(T::Moment, ExpiryItemOf<T>)>;
+
+        /// Amount of elements, ever inserted into the slot of ExpiryQueue
+        ExpiryQueueLen
+            get(fn expiry_queue_len):
+                map hasher(twox_64_concat) QueueSlot => QueueIndex;
+
+        /// First slot of ExpiryQueue, which can have queued items,
+        /// and index of the first element of the slot, which is not processed yet
+        ExpiryCursor
+            get(fn expiry_cursor):
+                Option<(QueueSlot, QueueIndex)>;
     }
 }
 
@@ -268,47 +508,89 @@ decl_event!(
     pub enum Event<T>
     where
         AccountId = <T as frame_system::Config>::AccountId,
+        Moment = <T as pallet_timestamp::Config>::Moment,
         BondUnitSaleLotStructOf = BondUnitSaleLotStructOf<T>,
     {
+        // caller of the events below is None, when the call is made by MasterOrigin
         /// \[master, account, role, data\]
-        AccountAdd(AccountId, AccountId, u8, u64),
+        AccountAdd(Option<AccountId>, AccountId, RoleMask, u64),
         /// \[master, account, role, data\]
-        AccountSet(AccountId, AccountId, u8, u64),
+        AccountSet(Option<AccountId>, AccountId, RoleMask, u64),
         /// \[master, account\]
-        AccountDisable(AccountId, AccountId),
-        /// \[account, everusd\]
-        MintRequestCreated(AccountId, EverUSDBalance),
-        /// \[account, everusd\]
-        MintRequestRevoked(AccountId, EverUSDBalance),
-        /// \[custodian, account, everusd\]
-        MintRequestConfirmed(AccountId, AccountId, EverUSDBalance),
-        /// \[custodian, account, everusd\]
-        MintRequestDeclined(AccountId, AccountId, EverUSDBalance),
-        /// \[account, everusd\]
-        BurnRequestCreated(AccountId, EverUSDBalance),
-        /// \[account, everusd\]
-        BurnRequestRevoked(AccountId, EverUSDBalance),
-        /// \[custodian, account, everusd\]
-        BurnRequestConfirmed(AccountId, AccountId, EverUSDBalance),
-        /// \[custodian,account, everusd\]
-        BurnRequestDeclined(AccountId, AccountId, EverUSDBalance),
+        AccountDisable(Option<AccountId>, AccountId),
+        /// \[master, account, role\]
+        AccountRoleGrantSet(Option<AccountId>, AccountId, RoleMask),
+        /// \[provider, account\]
+        AccountKycSet(AccountId, AccountId),
+        /// \[caller, account\]
+        AccountKycRevoked(Option<AccountId>, AccountId),
+        /// \[master, required\]
+        TransferKycRequiredSet(Option<AccountId>, bool),
+        /// \[account, request, everusd\]
+        MintRequestCreated(AccountId, TokenRequestId, EverUSDBalance),
+        /// \[account, request, everusd\]
+        MintRequestRevoked(AccountId, TokenRequestId, EverUSDBalance),
+        /// \[custodian, account, request, everusd\]
+        MintRequestConfirmed(AccountId, AccountId, TokenRequestId, EverUSDBalance),
+        /// \[custodian, account, request, everusd\]
+        MintRequestDeclined(AccountId, AccountId, TokenRequestId, EverUSDBalance),
+        /// \[account, request, everusd\]
+        BurnRequestCreated(AccountId, TokenRequestId, EverUSDBalance),
+        /// \[account, request, everusd\]
+        BurnRequestRevoked(AccountId, TokenRequestId, EverUSDBalance),
+        /// \[custodian, account, request, everusd\]
+        BurnRequestConfirmed(AccountId, AccountId, TokenRequestId, EverUSDBalance),
+        /// \[custodian, account, request, everusd\]
+        BurnRequestDeclined(AccountId, AccountId, TokenRequestId, EverUSDBalance),
+        /// \[master, custodian, mint_cap\]
+        CustodianSet(Option<AccountId>, AccountId, EverUSDBalance),
+        /// \[master, custodian, everusd\]
+        CustodianSupplyAssigned(Option<AccountId>, AccountId, EverUSDBalance),
+        /// \[auditor, custodian, reserves\]
+        CustodianReservesAttested(AccountId, AccountId, EverUSDBalance),
+        /// \[from, to, everusd\]
+        TransferEverUSD(AccountId, AccountId, EverUSDBalance),
         // Bond events
         /// \[issuer,bond\]
         BondAdded(AccountId, BondId),
         /// \[sender,bond\]
-        BondChanged(AccountId, BondId),
+        BondChanged(Option<AccountId>, BondId),
         /// \[issuer,bond\]
         BondRevoked(AccountId, BondId),
         /// \[sender,bond\]
-        BondReleased(AccountId, BondId),
+        BondReleased(Option<AccountId>, BondId),
         /// \[sender,bond,bondfund\]
-        BondActivated(AccountId, BondId, EverUSDBalance),
+        BondActivated(Option<AccountId>, BondId, EverUSDBalance),
         /// \[issuer,bond\]
         BondWithdrawal(AccountId, BondId),
         /// \[issuer,bond,bondfund\]
         BondRedeemed(AccountId, BondId, EverUSDBalance),
+        /// \[issuer,bond,bondfund\]
+        BondCalled(AccountId, BondId, EverUSDBalance),
+        /// \[bondholder,bond,units,everusd\]
+        BondUnitPut(AccountId, BondId, BondUnitAmount, EverUSDBalance),
         /// \[sender,bond,credit,debit\]
-        BondBankrupted(AccountId, BondId, EverUSDBalance, EverUSDBalance),
+        BondBankrupted(Option<AccountId>, BondId, EverUSDBalance, EverUSDBalance),
+        /// \[proposer,bond\]
+        BondRestructuringProposed(AccountId, BondId),
+        /// \[bondholder,bond,approve,units\]
+        BondRestructuringVoted(AccountId, BondId, bool, BondUnitAmount),
+        /// \[sender,bond\]
+        BondRestructuringCancelled(AccountId, BondId),
+        /// \[bond\]
+        BondRestructuringRejected(BondId),
+        /// \[bond,nonce\]
+        BondRestructured(BondId, u64),
+        /// \[proposer,bond,proposal\]
+        BondProposalCreated(AccountId, BondId, BondProposalId),
+        /// \[bondholder,bond,proposal,approve,units\]
+        BondProposalVoted(AccountId, BondId, BondProposalId, bool, BondUnitAmount),
+        /// \[bond,proposal\]
+        BondProposalPassed(BondId, BondProposalId),
+        /// \[bond,proposal\]
+        BondProposalRejected(BondId, BondProposalId),
+        /// \[sender,bond,proposal\]
+        BondProposalCancelled(AccountId, BondId, BondProposalId),
         /// \[sender,bond,everusd\]
         BondWithdrawEverUSD(AccountId, BondId, EverUSDBalance),
         /// \[issuer,bond,everusd\]
@@ -318,15 +600,48 @@ decl_event!(
         /// \[bondholder,bond,units,everusd\]
         BondUnitReturned(AccountId, BondId, u32, EverUSDBalance),
         /// \[issuer,bond,period,impact_data\]
-        BondImpactReportSent(AccountId, BondId, BondPeriodNumber, u64),
+        BondImpactReportSent(AccountId, BondId, BondPeriodNumber, Vec<u64>),
+        /// \[auditor,bond,period,impact_data\]
+        BondImpactReportApproved(AccountId, BondId, BondPeriodNumber, Vec<u64>),
         /// \[auditor,bond,period,impact_data\]
-        BondImpactReportApproved(AccountId, BondId, BondPeriodNumber, u64),
+        BondImpactReportDisputed(AccountId, BondId, BondPeriodNumber, Vec<u64>),
+        /// \[agent,bond,rating\]
+        BondCreditRatingSet(AccountId, BondId, Vec<u8>),
+        /// \[caller, bond, investor, allowed\]
+        BondAllowListSet(AccountId, BondId, AccountId, bool),
+        /// \[caller, bond, halted\]
+        BondTradingHaltSet(Option<AccountId>, BondId, bool),
+        /// \[caller, bond, bondholder, until\]
+        BondUnitLockupSet(AccountId, BondId, AccountId, Option<Moment>),
+        /// \[uploader,bond,document\]
+        BondDocumentAdded(AccountId, BondId, BondDocumentId),
+        /// \[uploader,bond,document,amendment\]
+        BondDocumentAmended(AccountId, BondId, BondDocumentId, BondDocumentId),
+        /// \[auditor,bond,document\]
+        BondDocumentAcknowledged(AccountId, BondId, BondDocumentId),
+        /// \[owner,bond,order\]
+        BondOrderPlaced(AccountId, BondId, BondOrderId),
+        /// \[owner,bond,order\]
+        BondOrderCancelled(AccountId, BondId, BondOrderId),
+        /// \[seller,buyer,bond,units,everusd\]
+        BondOrderMatched(AccountId, AccountId, BondId, BondUnitAmount, EverUSDBalance),
         /// \[bond,everusd\]
         BondCouponYield(BondId, EverUSDBalance),
         /// \[bondholder, bond, lot\]
         BondSaleLotBid(AccountId, BondId, BondUnitSaleLotStructOf),
         /// \[from, to, bond, lot\]
         BondSaleLotSettle(AccountId, AccountId, BondId, BondUnitSaleLotStructOf),
+        /// \[from, to, bond, bond_units\]
+        BondUnitTransfer(AccountId, AccountId, BondId, BondUnitAmount),
+        /// \[maker, swap\]
+        BondSwapOffered(AccountId, BondSwapId),
+        /// \[counterparty, maker, swap\]
+        BondSwapSettled(AccountId, AccountId, BondSwapId),
+        /// \[caller, swap\]
+        BondSwapCancelled(AccountId, BondSwapId),
+        /// Resting order, which cannot be settled, is removed from order book
+        /// \[owner,bond,order\]
+        BondOrderDropped(AccountId, BondId, BondOrderId),
     }
 );
 
@@ -344,13 +659,19 @@ decl_error! {
         AccountNotExist,
         /// Role parameter is invalid (bit mask of available roles includes non-existent role)
         AccountRoleParamIncorrect,
-        /// Account already created one mint request, only one allowed at a time(to be changed in future)
+        /// Incorrect parameters of KYC attestation
+        KycParamIncorrect,
+        /// Account doesn't have KYC attestation
+        KycNotFound,
+        /// Account doesn't have valid KYC attestation, required for the action
+        KycNotValid,
+        /// Deprecated: account may have several pending mint requests, see MintRequestLimitExceeded
         MintRequestAlreadyExist,
         /// Mint request for given account doesnt exist
         MintRequestDoesntExist,
         /// Incorrect parameters for mint request(miant amount > MAX_MINT_AMOUNT)
         MintRequestParamIncorrect,
-        /// Account already created one burn request, only one allowed at a time(to be changed in future)
+        /// Deprecated: account may have several pending burn requests, see BurnRequestLimitExceeded
         BurnRequestAlreadyExist,
         /// Mint request for given account doesnt exist
         BurnRequestDoesntExist,
@@ -360,6 +681,12 @@ decl_error! {
         BurnRequestObsolete,
         /// Mint request exists but outdated
         MintRequestObsolete,
+        /// Custodian is not registered in CustodianRegistry
+        CustodianNotRegistered,
+        /// Custodian has minted maximal amount of EverUSD within the mint window
+        CustodianMintCapExceeded,
+        /// Custodian backs less EverUSD than requested to burn
+        CustodianSupplyShortage,
         /// Bond with same ticker already exists
         /// Every bond on the platform has unique BondId: 8 bytes, like "MUSKPWR1" or "SOLGEN02"
         BondAlreadyExists,
@@ -377,12 +704,68 @@ decl_error! {
         BondOutOfOrder,
         /// Bond version is outdated
         BondNonceObsolete,
+        /// Investor is not in allow-list of the bond
+        BondInvestorNotAllowed,
+        /// Holding of investor would be out of holding limits of the bond
+        BondHoldingOutOfLimits,
+        /// Maximal number of bondholders of the bond or of the platform limit is reached
+        BondHoldersLimitExceeded,
+        /// Trading of the bond is halted
+        BondTradingHalted,
+        /// Bond Units cannot be sold during lock-up period
+        BondUnitsLocked,
+        /// Bond fund doesn't have enough EverUSD to pay off
+        BondFundShortage,
         /// Bid lot not found
         LotNotFound,
         /// Bid lot expired
         LotObsolete,
         /// Incorrect parameter for the bond sale lot
         LotParamIncorrect,
+        /// Bond document not found
+        DocumentNotFound,
+        /// Incorrect parameter for the bond document
+        DocumentParamIncorrect,
+        /// Order not found in order book
+        OrderNotFound,
+        /// Incorrect parameter for the bond order
+        OrderParamIncorrect,
+        /// Order book side has maximal amount of orders
+        OrderBookFull,
+        /// Swap not found
+        SwapNotFound,
+        /// Swap expired
+        SwapObsolete,
+        /// Incorrect parameter for the swap
+        SwapParamIncorrect,
+        /// Restructuring proposal not found
+        RestructuringNotFound,
+        /// Restructuring proposal expired
+        RestructuringObsolete,
+        /// Incorrect parameter for the restructuring proposal
+        RestructuringParamIncorrect,
+        /// Bondholder already voted for the restructuring proposal
+        RestructuringAlreadyVoted,
+        /// Action is not allowed while bondholders vote for the restructuring proposal
+        RestructuringInProgress,
+        /// Bondholder meeting proposal not found
+        ProposalNotFound,
+        /// Bondholder meeting proposal expired
+        ProposalObsolete,
+        /// Incorrect parameter for the bondholder meeting proposal
+        ProposalParamIncorrect,
+        /// Bondholder already voted for the proposal
+        ProposalAlreadyVoted,
+        /// Bond has maximal amount of open proposals
+        ProposalLimitExceeded,
+        /// Account has maximal amount of pending mint requests
+        MintRequestLimitExceeded,
+        /// Account has maximal amount of pending burn requests
+        BurnRequestLimitExceeded,
+        /// Attested reserves of custodian don't cover the EverUSD it would supply
+        CustodianReservesShortage,
+        /// Bondholder has maximal amount of sale lots in the bond
+        LotLimitExceeded,
     }
 }
 
@@ -395,10 +778,21 @@ decl_module! {
         const MintRequestTtl:u32 = T::MintRequestTtl::get();
         const MaxMintAmount: EverUSDBalance = T::MaxMintAmount::get();
         const TimeStep: BondPeriod = T::TimeStep::get();
+        const MaxAccrualWeight: Weight = T::MaxAccrualWeight::get();
 
         // Events must be initialized if they are used by the pallet.
         fn deposit_event() = default;
 
+        /// Accrues coupon yield of bonds, which payment_period has ended,
+        /// and purges expired requests and lots
+        fn on_initialize(_n: T::BlockNumber) -> Weight {
+            Self::process_coupon_accruals(T::MaxAccrualWeight::get())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migration::on_runtime_upgrade::<T>()
+        }
+
         // Account management functions
 
         #[weight = T::DbWeight::get().reads_writes(2,1)]
@@ -419,43 +813,48 @@ decl_module! {
         /// Method: account_disable(who: AccountId)
         /// Arguments: origin: AccountId - transaction caller
         ///            who: AccountId - account to disable
-        /// Access: Master role
+        /// Access: Master role, MasterOrigin or Compliance Officer role
         ///
         /// Disables all roles of account, setting roles bitmask to 0.
         /// Accounts are not allowed to perform any actions without role,
-        /// but still have its data in blockchain (to not loose related entities)
+        /// but still have its data in blockchain (to not loose related entities).
+        /// Compliance Officer can disable accounts, failed compliance checks, except Master accounts
         /// </pre>
         #[weight = <T as Config>::WeightInfo::account_disable()]
         fn account_disable(origin, who: T::AccountId) -> DispatchResult {
-            let caller = ensure_signed(origin)?;
-            ensure!(Self::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
-            ensure!(caller != who, Error::<T>::InvalidAction);
+            let caller = Self::ensure_master_or(origin, COMPLIANCE_OFFICER_ROLE_MASK, None)?;
+            ensure!(caller.as_ref() != Some(&who), Error::<T>::InvalidAction);
             ensure!(AccountRegistry::<T>::contains_key(&who), Error::<T>::AccountNotExist);
+            ensure!(
+                caller.as_ref().map_or(true, |caller| !Self::account_is_master(&who) || Self::account_is_master(caller)
+                    || !Self::account_is_compliance_officer(caller)),
+                Error::<T>::AccountNotAuthorized
+            );
 
             AccountRegistry::<T>::mutate(&who,|acc|{
                 acc.roles = 0; // set no roles
             });
+            AccountRoleGrant::<T>::remove_prefix(&who);
 
             Self::deposit_event(RawEvent::AccountDisable(caller, who));
             Ok(())
         }
 
         /// <pre>
-        /// Method: account_add_with_role_and_data(origin, who: T::AccountId, role: u8, identity: u64)
+        /// Method: account_add_with_role_and_data(origin, who: T::AccountId, role: RoleMask, identity: u64)
         /// Arguments:  origin: AccountId - transaction caller
         ///             who: AccountId - id of account to add to accounts registry of platform
-        ///             role: u8 - role(s) of account (see ALL_ROLES_MASK for allowed roles)
+        ///             role: RoleMask - role(s) of account (see ALL_ROLES_MASK for allowed roles)
         ///             identity: u64 - reserved field for integration with external platforms
-        /// Access: Master role
+        /// Access: Master role or MasterOrigin
         ///
         /// Adds new account with given role(s). Roles are set as bitmask. Contains parameter
         /// "identity", planned to use in the future to connect accounts with external services like
         /// KYC providers
         /// </pre>
         #[weight = <T as Config>::WeightInfo::account_add_with_role_and_data()]
-        fn account_add_with_role_and_data(origin, who: T::AccountId, role: u8,#[compact]  identity: u64) -> DispatchResult {
-            let caller = ensure_signed(origin)?;
-            ensure!(Self::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
+        fn account_add_with_role_and_data(origin, who: T::AccountId, role: RoleMask,#[compact]  identity: u64) -> DispatchResult {
+            let caller = Self::ensure_master(origin)?;
             ensure!(!AccountRegistry::<T>::contains_key(&who), Error::<T>::AccountToAddAlreadyExists);
             ensure!(is_roles_correct(role), Error::<T>::AccountRoleParamIncorrect);
 
@@ -466,20 +865,19 @@ decl_module! {
         }
 
         /// <pre>
-        /// Method: account_set_with_role_and_data(origin, who: T::AccountId, role: u8, identity: u64)
+        /// Method: account_set_with_role_and_data(origin, who: T::AccountId, role: RoleMask, identity: u64)
         /// Arguments:  origin: AccountId - transaction caller
         ///             who: AccountId - account to modify
-        ///             role: u8 - role(s) of account (see ALL_ROLES_MASK for allowed roles)
+        ///             role: RoleMask - role(s) of account (see ALL_ROLES_MASK for allowed roles)
         ///             identity: u64 - reserved field for integration with external platforms
-        /// Access: Master role
+        /// Access: Master role or MasterOrigin
         ///
         /// Modifies existing account, assigning new role(s) or identity to it
         /// </pre>
         #[weight = <T as Config>::WeightInfo::account_set_with_role_and_data()]
-        fn account_set_with_role_and_data(origin, who: T::AccountId, role: u8,#[compact]  identity: u64) -> DispatchResult {
-            let caller = ensure_signed(origin)?;
-            ensure!(caller != who, Error::<T>::InvalidAction);
-            ensure!(Self::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
+        fn account_set_with_role_and_data(origin, who: T::AccountId, role: RoleMask,#[compact]  identity: u64) -> DispatchResult {
+            let caller = Self::ensure_master(origin)?;
+            ensure!(caller.as_ref() != Some(&who), Error::<T>::InvalidAction);
             ensure!(AccountRegistry::<T>::contains_key(&who), Error::<T>::AccountNotExist);
             ensure!(is_roles_correct(role), Error::<T>::AccountRoleParamIncorrect);
 
@@ -491,81 +889,212 @@ decl_module! {
             Ok(())
         }
 
+        /// <pre>
+        /// Method: account_set_role_grant(origin, who: T::AccountId, role: RoleMask, grant: Option<AccountRoleGrantStruct>)
+        /// Arguments:  origin: AccountId - transaction caller
+        ///             who: AccountId - account to modify
+        ///             role: RoleMask - single role of account
+        ///             grant: Option<AccountRoleGrantStruct> - expiry moment and bonds of the role
+        /// Access: Master role or MasterOrigin
+        ///
+        /// Restricts the role of account by expiry moment and (or) list of bonds, f.e. auditor
+        /// accreditation for one year, or auditor of specific bonds only. None removes restriction,
+        /// making the role permanent and global again. Master role cannot be restricted
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::account_set_role_grant()]
+        fn account_set_role_grant(origin, who: T::AccountId, role: RoleMask, grant: Option<AccountRoleGrantStructOf<T>>) -> DispatchResult {
+            let caller = Self::ensure_master(origin)?;
+            ensure!(caller.as_ref() != Some(&who), Error::<T>::InvalidAction);
+            ensure!(AccountRegistry::<T>::contains_key(&who), Error::<T>::AccountNotExist);
+            ensure!(
+                is_roles_correct(role) && role.count_ones() == 1 && role != MASTER_ROLE_MASK,
+                Error::<T>::AccountRoleParamIncorrect
+            );
+            ensure!(AccountRegistry::<T>::get(&who).roles & role != 0, Error::<T>::AccountRoleParamIncorrect);
+
+            match grant {
+                Some(grant) => {
+                    ensure!(grant.bonds.len() <= MAX_ROLE_GRANT_BONDS, Error::<T>::AccountRoleParamIncorrect);
+                    AccountRoleGrant::<T>::insert(&who, role, grant);
+                }
+                None => AccountRoleGrant::<T>::remove(&who, role),
+            }
+
+            Self::deposit_event(RawEvent::AccountRoleGrantSet(caller, who, role));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: account_kyc_set(origin, who: T::AccountId, attestation_hash: Hash, jurisdiction: KycJurisdiction, accreditation: KycAccreditationLevel, expiry: Moment)
+        /// Arguments:  origin: AccountId - transaction caller
+        ///             who: AccountId - attested account
+        ///             attestation_hash: Hash - hash of attestation documents, kept by provider
+        ///             jurisdiction: KycJurisdiction - ISO 3166-1 alpha-2 country code, f.e. b"DE"
+        ///             accreditation: KycAccreditationLevel - accreditation level of investor
+        ///             expiry: Moment - attestation is valid before this moment
+        /// Access: KYC provider role
+        ///
+        /// Writes KYC attestation of registered account, replacing previous attestation (if any).
+        /// Attestation is checked by bonds with eligibility rules, and by EverUSD transfers
+        /// when TransferKycRequired is set
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::account_kyc_set()]
+        fn account_kyc_set(origin, who: T::AccountId, attestation_hash: T::Hash, jurisdiction: KycJurisdiction, accreditation: KycAccreditationLevel, #[compact] expiry: T::Moment) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(Self::account_is_kyc_provider(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(caller != who, Error::<T>::InvalidAction);
+            ensure!(Self::account_is_registered(&who), Error::<T>::AccountNotExist);
+            ensure!(
+                is_jurisdiction_correct(&jurisdiction) && expiry > Timestamp::<T>::get(),
+                Error::<T>::KycParamIncorrect
+            );
+
+            AccountKyc::<T>::insert(&who, AccountKycStruct {
+                provider: caller.clone(),
+                attestation_hash,
+                jurisdiction,
+                accreditation,
+                expiry,
+            });
+            Self::deposit_event(RawEvent::AccountKycSet(caller, who));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: account_kyc_revoke(origin, who: T::AccountId)
+        /// Arguments:  origin: AccountId - transaction caller
+        ///             who: AccountId - attested account
+        /// Access: KYC provider of the attestation, Master role or MasterOrigin
+        ///
+        /// Removes KYC attestation of account
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::account_kyc_revoke()]
+        fn account_kyc_revoke(origin, who: T::AccountId) -> DispatchResult {
+            let caller = Self::ensure_master_or(origin, KYC_PROVIDER_ROLE_MASK, None)?;
+            let kyc = AccountKyc::<T>::get(&who).ok_or(Error::<T>::KycNotFound)?;
+            // KYC provider can revoke own attestations only
+            ensure!(
+                caller.as_ref().map_or(true, |caller| kyc.provider == *caller
+                    || !Self::account_is_kyc_provider(caller) || Self::account_is_master(caller)),
+                Error::<T>::AccountNotAuthorized
+            );
+
+            AccountKyc::<T>::remove(&who);
+            Self::deposit_event(RawEvent::AccountKycRevoked(caller, who));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: set_transfer_kyc_required(origin, required: bool)
+        /// Arguments:  origin: AccountId - transaction caller
+        ///             required: bool - new value of TransferKycRequired
+        /// Access: Master role or MasterOrigin
+        ///
+        /// Requires valid KYC attestation of sender and recipient of EverUSD transfers
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::set_transfer_kyc_required()]
+        fn set_transfer_kyc_required(origin, required: bool) -> DispatchResult {
+            let caller = Self::ensure_master(origin)?;
+            TransferKycRequired::put(required);
+            Self::deposit_event(RawEvent::TransferKycRequiredSet(caller, required));
+            Ok(())
+        }
+
         // Token balances manipulation functions
 
         /// <pre>
-        /// Method: token_mint_request_create_everusd(origin, amount_to_mint: EverUSDBalance)
+        /// Method: token_mint_request_create_everusd(origin, amount_to_mint: EverUSDBalance, reference: Vec<u8>)
         /// Arguments:  origin: AccountId - transaction caller
         ///             amount_to_mint: EverUSDBalance - amount of tokens to mint
+        ///             reference: Vec<u8> - reference of the payment, f.e. bank transfer reference or SWIFT UETR
         /// Access: Investor or Issuer role
         ///
         /// Creates a request to mint given amount of EverUSD tokens on caller's balance.
-        /// Custodian account confirms request after receiving payment in USD from target account's owner
-        /// It's possible to create only one request per account. Mint request has a time-to-live
-        /// and becomes invalidated after it.
+        /// Custodian account confirms request after receiving payment in USD from target account's owner.
+        /// Request gets new id, so account can have several pending requests, f.e. one per wire transfer.
+        /// Mint request has a time-to-live and becomes invalidated after it.
+        /// Expired requests of the caller are purged.
         /// </pre>
         #[weight = <T as Config>::WeightInfo::token_mint_request_create_everusd()]
-        fn token_mint_request_create_everusd(origin, #[compact] amount_to_mint: EverUSDBalance) -> DispatchResult {
+        fn token_mint_request_create_everusd(origin, #[compact] amount_to_mint: EverUSDBalance, reference: Vec<u8>) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(Self::account_token_mint_burn_allowed(&caller), Error::<T>::AccountNotAuthorized);
-            ensure!(amount_to_mint <= T::MaxMintAmount::get(), Error::<T>::MintRequestParamIncorrect);
+            ensure!(
+                amount_to_mint <= T::MaxMintAmount::get() && reference.len() <= MAX_TOKEN_REQUEST_REFERENCE_LEN,
+                Error::<T>::MintRequestParamIncorrect
+            );
 
-            MintRequestEverUSD::<T>::try_mutate(&caller, |request|->DispatchResult{
-                let now = Timestamp::<T>::get();
-                if !request.is_expired(now) {
-                    Err( Error::<T>::MintRequestAlreadyExist.into() )
-                }else{
-                    *request = TokenMintRequestStruct{
-                        amount: amount_to_mint,
-                        deadline: now + T::MintRequestTtl::get().into(),
-                    };
-                    Self::deposit_event(RawEvent::MintRequestCreated(caller.clone(), amount_to_mint));
-                    Ok(())
+            let now = Timestamp::<T>::get();
+            let mut pending: usize = 0;
+            let requests: Vec<_> = MintRequestEverUSD::<T>::iter_prefix(&caller).collect();
+            for (id, request) in requests {
+                if request.is_expired(now) {
+                    MintRequestEverUSD::<T>::remove(&caller, id);
+                } else {
+                    pending += 1;
                 }
-            })
+            }
+            ensure!(pending < MAX_ACCOUNT_TOKEN_REQUESTS, Error::<T>::MintRequestLimitExceeded);
+
+            let id = MintRequestCount::get();
+            let deadline = now + T::MintRequestTtl::get().into();
+            MintRequestEverUSD::<T>::insert(&caller, id, TokenMintRequestStruct {
+                amount: amount_to_mint,
+                deadline,
+                reference,
+            });
+            MintRequestCount::put(id + 1);
+            Self::schedule_expiry(deadline, ExpiryItem::MintRequest(caller.clone(), id));
+            Self::deposit_event(RawEvent::MintRequestCreated(caller, id, amount_to_mint));
+            Ok(())
         }
 
         /// <pre>
-        /// Method: token_mint_request_revoke_everusd(origin)
+        /// Method: token_mint_request_revoke_everusd(origin, request: TokenRequestId)
         /// Arguments: origin: AccountId - transaction caller
+        ///            request: TokenRequestId - request identifier
         /// Access: Investor or Issuer role
         ///
-        /// Revokes and deletes currently existing mint request, created by caller's account
+        /// Revokes and deletes the mint request, created by caller's account
         /// </pre>
         #[weight = <T as Config>::WeightInfo::token_mint_request_revoke_everusd()]
-        fn token_mint_request_revoke_everusd(origin) -> DispatchResult {
+        fn token_mint_request_revoke_everusd(origin, #[compact] request: TokenRequestId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            ensure!(MintRequestEverUSD::<T>::contains_key(&caller), Error::<T>::MintRequestDoesntExist);
-            let _amount = MintRequestEverUSD::<T>::get(&caller).amount;
-            MintRequestEverUSD::<T>::remove(&caller);
-            Self::deposit_event(RawEvent::MintRequestRevoked(caller, _amount));
+            let mint_request = MintRequestEverUSD::<T>::take(&caller, request).ok_or(Error::<T>::MintRequestDoesntExist)?;
+            Self::deposit_event(RawEvent::MintRequestRevoked(caller, request, mint_request.amount));
             Ok(())
         }
 
         /// <pre>
-        /// Method: token_mint_request_confirm_everusd(origin, who: T::AccountId, amount: EverUSDBalance)
+        /// Method: token_mint_request_confirm_everusd(origin, who: T::AccountId, request: TokenRequestId, amount: EverUSDBalance)
         /// Arguments:  origin: AccountId - transaction caller
         ///             who: AccountId - target account
+        ///             request: TokenRequestId - request identifier
         ///             amount: EverUSDBalance - amount of tokens to mint, confirmed by Custodian
-        /// Access: Custodian role
+        /// Access: Custodian role, registered in CustodianRegistry
         ///
         /// Confirms the mint request of account, creating "amount" of tokens on its balance.
+        /// Minted tokens are attributed to the supply of Custodian and counted against
+        /// its mint cap within the current mint window. Once reserves of Custodian are attested,
+        /// its supply can't exceed them.
         /// (note) Amount of tokens is sent as parameter to avoid data race problem, when
         /// Custodian can confirm unwanted amount of tokens, because attacker is modified mint request
         /// while Custodian makes a decision
         /// </pre>
         #[weight = <T as Config>::WeightInfo::token_mint_request_confirm_everusd()]
-        fn token_mint_request_confirm_everusd(origin, who: T::AccountId, #[compact] amount: EverUSDBalance) -> DispatchResult {
+        fn token_mint_request_confirm_everusd(origin, who: T::AccountId, #[compact] request: TokenRequestId, #[compact] amount: EverUSDBalance) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(Self::account_is_custodian(&caller),Error::<T>::AccountNotAuthorized);
-            ensure!(MintRequestEverUSD::<T>::contains_key(&who), Error::<T>::MintRequestDoesntExist);
-            let mint_request = MintRequestEverUSD::<T>::get(&who);
+            let mint_request = MintRequestEverUSD::<T>::get(&who, request).ok_or(Error::<T>::MintRequestDoesntExist)?;
             let now = Timestamp::<T>::get();
             ensure!(!mint_request.is_expired(now), Error::<T>::MintRequestObsolete);
 
             // add tokens to user's balance and total supply of EverUSD
             let amount_to_add = mint_request.amount;
             ensure!(amount_to_add==amount,Error::<T>::MintRequestParamIncorrect );
+            let mut custodian = CustodianRegistry::<T>::get(&caller).ok_or(Error::<T>::CustodianNotRegistered)?;
+            ensure!(custodian.mint(amount_to_add, now), Error::<T>::CustodianMintCapExceeded);
+            ensure!(custodian.reserves.is_none() || custodian.is_backed(), Error::<T>::CustodianReservesShortage);
 
             Self::balance_add(&who, amount_to_add)?;
 
@@ -574,134 +1103,268 @@ decl_module! {
                 Ok(())
             })?;
 
-            MintRequestEverUSD::<T>::remove(&who);
-            Self::deposit_event(RawEvent::MintRequestConfirmed(caller, who, amount_to_add));
-            Self::purge_expired_mint_requests(now);
+            CustodianRegistry::<T>::insert(&caller, custodian);
+            MintRequestEverUSD::<T>::remove(&who, request);
+            Self::deposit_event(RawEvent::MintRequestConfirmed(caller, who, request, amount_to_add));
             Ok(())
         }
 
         /// <pre>
-        /// Method: token_mint_request_decline_everusd(origin, who: T::AccountId)
+        /// Method: token_mint_request_decline_everusd(origin, who: T::AccountId, request: TokenRequestId)
         /// Arguments:  origin: AccountId - transaction caller
         ///             who: AccountId - target account
+        ///             request: TokenRequestId - request identifier
         /// Access: Custodian role
         ///
         /// Declines and deletes the mint request of account (Custodian)
         /// </pre>
         #[weight = <T as Config>::WeightInfo::token_mint_request_decline_everusd()]
-        fn token_mint_request_decline_everusd(origin, who: T::AccountId) -> DispatchResult {
+        fn token_mint_request_decline_everusd(origin, who: T::AccountId, #[compact] request: TokenRequestId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(Self::account_is_custodian(&caller),Error::<T>::AccountNotAuthorized);
-            ensure!(MintRequestEverUSD::<T>::contains_key(&who), Error::<T>::MintRequestDoesntExist);
-            let amount = MintRequestEverUSD::<T>::get(&who).amount;
-            MintRequestEverUSD::<T>::remove(&who);
-            Self::deposit_event(RawEvent::MintRequestDeclined(caller, who, amount));
+            let mint_request = MintRequestEverUSD::<T>::take(&who, request).ok_or(Error::<T>::MintRequestDoesntExist)?;
+            Self::deposit_event(RawEvent::MintRequestDeclined(caller, who, request, mint_request.amount));
             Ok(())
         }
 
         /// <pre>
-        /// Method: token_burn_request_create_everusd(origin, amount_to_burn: EverUSDBalance)
+        /// Method: token_burn_request_create_everusd(origin, amount_to_burn: EverUSDBalance, reference: Vec<u8>, custodian: T::AccountId)
         /// Arguments:  origin: AccountId - transaction caller
         ///             amount_to_burn: EverUSDBalance - amount of tokens to burn
+        ///             reference: Vec<u8> - reference of the payout, f.e. bank account or payment instructions
+        ///             custodian: AccountId - custodian, holding reserves for the payout
         /// Access: Investor or Issuer role
         ///
         /// Creates a request to burn given amount of EverUSD tokens on caller's balance.
-        /// Custodian account confirms request after sending payment in USD to target account's owner
-        /// It's possible to create only one request per account. Burn request has a time-to-live
-        /// and becomes invalidated after it.
+        /// Request is routed to the custodian, which must back the burned amount together with
+        /// other pending burn requests, routed to it.
+        /// Custodian confirms request after sending payment in USD to target account's owner.
+        /// Request gets new id, so account can have several pending requests, all of them must be
+        /// covered by the balance. Burn request has a time-to-live and becomes invalidated after it.
+        /// Expired requests of the caller are purged.
         /// </pre>
         #[weight = <T as Config>::WeightInfo::token_burn_request_create_everusd()]
-        fn token_burn_request_create_everusd(origin, #[compact]  amount_to_burn: EverUSDBalance) -> DispatchResult {
+        fn token_burn_request_create_everusd(origin, #[compact]  amount_to_burn: EverUSDBalance, reference: Vec<u8>, custodian: T::AccountId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(Self::account_token_mint_burn_allowed(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(reference.len() <= MAX_TOKEN_REQUEST_REFERENCE_LEN, Error::<T>::BurnRequestParamIncorrect);
 
-            let current_balance = BalanceEverUSD::<T>::get(&caller);
-            ensure!(amount_to_burn <= current_balance, Error::<T>::BalanceOverdraft);
-
-            BurnRequestEverUSD::<T>::try_mutate(&caller,|request|->DispatchResult{
-                let now = Timestamp::<T>::get();
-                if !request.is_expired( now ) {
-                    Err( Error::<T>::BurnRequestAlreadyExist.into() )
-                }else{
-                    *request = TokenBurnRequestStruct {
-                        amount: amount_to_burn,
-                        deadline: now +  T::BurnRequestTtl::get().into(),
-                    };
-                    Self::deposit_event(RawEvent::BurnRequestCreated(caller.clone(), amount_to_burn));
-                    Ok(())
+            let now = Timestamp::<T>::get();
+            let mut pending: usize = 0;
+            let mut pending_amount: EverUSDBalance = 0;
+            let requests: Vec<_> = BurnRequestEverUSD::<T>::iter_prefix(&caller).collect();
+            for (id, request) in requests {
+                if request.is_expired(now) {
+                    Self::burn_request_take(&caller, id);
+                } else {
+                    pending += 1;
+                    pending_amount = pending_amount.saturating_add(request.amount);
                 }
-            })
+            }
+            ensure!(pending < MAX_ACCOUNT_TOKEN_REQUESTS, Error::<T>::BurnRequestLimitExceeded);
+            let current_balance = BalanceEverUSD::<T>::get(&caller);
+            ensure!(pending_amount.saturating_add(amount_to_burn) <= current_balance, Error::<T>::BalanceOverdraft);
+            let supply = CustodianRegistry::<T>::get(&custodian).ok_or(Error::<T>::CustodianNotRegistered)?.supply;
+            let custodian_pending = CustodianPendingBurn::<T>::get(&custodian).saturating_add(amount_to_burn);
+            ensure!(custodian_pending <= supply, Error::<T>::CustodianSupplyShortage);
+
+            let id = BurnRequestCount::get();
+            let deadline = now + T::BurnRequestTtl::get().into();
+            CustodianPendingBurn::<T>::insert(&custodian, custodian_pending);
+            BurnRequestEverUSD::<T>::insert(&caller, id, TokenBurnRequestStruct {
+                amount: amount_to_burn,
+                deadline,
+                reference,
+                custodian,
+            });
+            BurnRequestCount::put(id + 1);
+            Self::schedule_expiry(deadline, ExpiryItem::BurnRequest(caller.clone(), id));
+            Self::deposit_event(RawEvent::BurnRequestCreated(caller, id, amount_to_burn));
+            Ok(())
         }
 
         /// <pre>
-        /// Method: token_burn_request_revoke_everusd(origin)
+        /// Method: token_burn_request_revoke_everusd(origin, request: TokenRequestId)
         /// Arguments: origin: AccountId - transaction caller
+        ///            request: TokenRequestId - request identifier
         /// Access: Investor or Issuer role
         ///
-        /// Revokes and deletes currently existing burn request, created by caller's account
+        /// Revokes and deletes the burn request, created by caller's account
         /// </pre>
         #[weight = <T as Config>::WeightInfo::token_burn_request_revoke_everusd()]
-        fn token_burn_request_revoke_everusd(origin) -> DispatchResult {
+        fn token_burn_request_revoke_everusd(origin, #[compact] request: TokenRequestId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            ensure!(BurnRequestEverUSD::<T>::contains_key(&caller), Error::<T>::BurnRequestDoesntExist);
-            let amount = BurnRequestEverUSD::<T>::get(&caller).amount;
-            BurnRequestEverUSD::<T>::remove(&caller);
-            Self::deposit_event(RawEvent::BurnRequestRevoked(caller, amount));
+            let burn_request = Self::burn_request_take(&caller, request).ok_or(Error::<T>::BurnRequestDoesntExist)?;
+            Self::deposit_event(RawEvent::BurnRequestRevoked(caller, request, burn_request.amount));
             Ok(())
         }
 
         /// <pre>
-        /// Method: token_burn_request_confirm_everusd(origin, who: T::AccountId, amount: EverUSDBalance)
+        /// Method: token_burn_request_confirm_everusd(origin, who: T::AccountId, request: TokenRequestId, amount: EverUSDBalance)
         /// Arguments:  origin: AccountId - transaction caller
         ///             who: AccountId - target account
+        ///             request: TokenRequestId - request identifier
         ///             amount: EverUSDBalance - amount of tokens to mint, confirmed by Custodian
-        /// Access: Custodian role
+        /// Access: Custodian role, custodian of the request
         ///
         /// Confirms the burn request of account, destroying "amount" of tokens on its balance.
+        /// Burned tokens are deducted from the supply of Custodian.
         /// </pre>
         #[weight = <T as Config>::WeightInfo::token_burn_request_confirm_everusd()]
-        fn token_burn_request_confirm_everusd(origin, who: T::AccountId, #[compact]  amount: EverUSDBalance) -> DispatchResult {
+        fn token_burn_request_confirm_everusd(origin, who: T::AccountId, #[compact] request: TokenRequestId, #[compact]  amount: EverUSDBalance) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(Self::account_is_custodian(&caller),Error::<T>::AccountNotAuthorized);
-            ensure!(BurnRequestEverUSD::<T>::contains_key(&who), Error::<T>::BurnRequestDoesntExist);
-            let burn_request = BurnRequestEverUSD::<T>::get(&who);
+            let burn_request = BurnRequestEverUSD::<T>::get(&who, request).ok_or(Error::<T>::BurnRequestDoesntExist)?;
+            ensure!(burn_request.is_routed_to(&caller), Error::<T>::AccountNotAuthorized);
             let now = Timestamp::<T>::get();
             ensure!(!burn_request.is_expired(now), Error::<T>::BurnRequestObsolete);
             // remove tokens from user's balance and decrease total supply of EverUSD
             let amount_to_sub = burn_request.amount;
             // prevent unacceptable commit
             ensure!(amount_to_sub==amount, Error::<T>::MintRequestParamIncorrect );
+            let mut custodian = CustodianRegistry::<T>::get(&caller).ok_or(Error::<T>::CustodianNotRegistered)?;
+            custodian.supply = custodian.supply.checked_sub(amount_to_sub).ok_or(Error::<T>::CustodianSupplyShortage)?;
 
             Self::balance_sub(&who, amount_to_sub)?;
             TotalSupplyEverUSD::mutate(|total|{
                 *total-=amount_to_sub;
             });
+            CustodianRegistry::<T>::insert(&caller, custodian);
 
-            BurnRequestEverUSD::<T>::remove(&who);
-            Self::deposit_event(RawEvent::BurnRequestConfirmed(caller, who, amount_to_sub));
-            Self::purge_expired_burn_requests(now);
+            Self::burn_request_take(&who, request);
+            Self::deposit_event(RawEvent::BurnRequestConfirmed(caller, who, request, amount_to_sub));
             Ok(())
         }
 
         /// <pre>
-        /// Method: token_burn_request_decline_everusd(origin, who: T::AccountId)
+        /// Method: token_burn_request_decline_everusd(origin, who: T::AccountId, request: TokenRequestId)
         /// Arguments:  origin: AccountId - transaction caller
         ///             who: AccountId - target account
-        /// Access: Custodian role
+        ///             request: TokenRequestId - request identifier
+        /// Access: Custodian role, custodian of the request
         ///
         /// Declines and deletes the burn request of account (Custodian)
         /// </pre>
         #[weight = <T as Config>::WeightInfo::token_burn_request_decline_everusd()]
-        fn token_burn_request_decline_everusd(origin, who: T::AccountId) -> DispatchResult {
+        fn token_burn_request_decline_everusd(origin, who: T::AccountId, #[compact] request: TokenRequestId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(Self::account_is_custodian(&caller),Error::<T>::AccountNotAuthorized);
-            ensure!(BurnRequestEverUSD::<T>::contains_key(&who), Error::<T>::BurnRequestDoesntExist);
-            let amount = BurnRequestEverUSD::<T>::get(&who).amount;
-            BurnRequestEverUSD::<T>::remove(&who);
-            Self::deposit_event(RawEvent::BurnRequestDeclined(caller, who, amount));
+            let burn_request = BurnRequestEverUSD::<T>::get(&who, request).ok_or(Error::<T>::BurnRequestDoesntExist)?;
+            ensure!(burn_request.is_routed_to(&caller), Error::<T>::AccountNotAuthorized);
+            Self::burn_request_take(&who, request);
+            Self::deposit_event(RawEvent::BurnRequestDeclined(caller, who, request, burn_request.amount));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: custodian_set(origin, custodian: T::AccountId, mint_cap: EverUSDBalance, mint_window: Moment)
+        /// Arguments:  origin: AccountId - transaction caller
+        ///             custodian: AccountId - account with Custodian role
+        ///             mint_cap: EverUSDBalance - maximal amount of EverUSD, minted within the mint window
+        ///             mint_window: Moment - duration of the rolling mint window
+        /// Access: Master role or MasterOrigin
+        ///
+        /// Registers the custodian in CustodianRegistry or changes its minting limits.
+        /// Supply and reserves attestation of registered custodian are kept
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::custodian_set()]
+        fn custodian_set(origin, custodian: T::AccountId, #[compact] mint_cap: EverUSDBalance, #[compact] mint_window: T::Moment) -> DispatchResult {
+            let caller = Self::ensure_master(origin)?;
+            ensure!(Self::account_is_custodian(&custodian), Error::<T>::AccountRoleParamIncorrect);
+            CustodianRegistry::<T>::mutate(&custodian, |item| {
+                let item = item.get_or_insert_with(Default::default);
+                item.mint_cap = mint_cap;
+                item.mint_window = mint_window;
+            });
+            Self::deposit_event(RawEvent::CustodianSet(caller, custodian, mint_cap));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: custodian_supply_assign(origin, custodian: T::AccountId, amount: EverUSDBalance)
+        /// Arguments:  origin: AccountId - transaction caller
+        ///             custodian: AccountId - registered custodian
+        ///             amount: EverUSDBalance - amount of EverUSD to attribute
+        /// Access: Master role or MasterOrigin
+        ///
+        /// Attributes the part of EverUSD supply, that isn't backed by any registered custodian
+        /// (f.e. minted before CustodianRegistry was introduced), to the custodian
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::custodian_supply_assign()]
+        fn custodian_supply_assign(origin, custodian: T::AccountId, #[compact] amount: EverUSDBalance) -> DispatchResult {
+            let caller = Self::ensure_master(origin)?;
+            ensure!(amount <= Self::unattributed_supply_everusd(), Error::<T>::BalanceOverdraft);
+            CustodianRegistry::<T>::try_mutate(&custodian, |item| -> DispatchResult {
+                let item = item.as_mut().ok_or(Error::<T>::CustodianNotRegistered)?;
+                item.supply += amount;
+                Ok(())
+            })?;
+            Self::deposit_event(RawEvent::CustodianSupplyAssigned(caller, custodian, amount));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: custodian_reserves_attest(origin, custodian: T::AccountId, reserves: EverUSDBalance, attestation_hash: T::Hash)
+        /// Arguments:  origin: AccountId - transaction caller
+        ///             custodian: AccountId - registered custodian
+        ///             reserves: EverUSDBalance - reserves of custodian, expressed in EverUSD
+        ///             attestation_hash: Hash - hash of the attestation report
+        /// Access: Auditor role
+        ///
+        /// Stores attestation of the custodian reserves, replacing the previous one
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::custodian_reserves_attest()]
+        fn custodian_reserves_attest(origin, custodian: T::AccountId, #[compact] reserves: EverUSDBalance, attestation_hash: T::Hash) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(Self::account_is_auditor(&caller), Error::<T>::AccountNotAuthorized);
+            CustodianRegistry::<T>::try_mutate(&custodian, |item| -> DispatchResult {
+                let item = item.as_mut().ok_or(Error::<T>::CustodianNotRegistered)?;
+                item.reserves = Some(CustodianReservesStruct {
+                    auditor: caller.clone(),
+                    amount: reserves,
+                    attestation_hash,
+                    attested_at: Timestamp::<T>::get(),
+                });
+                Ok(())
+            })?;
+            Self::deposit_event(RawEvent::CustodianReservesAttested(caller, custodian, reserves));
             Ok(())
         }
 
+        /// <pre>
+        /// Method: transfer_everusd(origin, to: T::AccountId, amount: EverUSDBalance)
+        /// Arguments:  origin: AccountId - transaction caller
+        ///             to: AccountId - recipient account
+        ///             amount: EverUSDBalance - amount of tokens to transfer
+        /// Access: any account, registered in AccountRegistry with at least one role
+        ///
+        /// Transfers "amount" of EverUSD tokens from caller's balance to "to" account balance.
+        /// Both accounts must be registered on the platform and must not be disabled.
+        /// If TransferKycRequired is set, both accounts must have valid KYC attestation.
+        /// Total supply of EverUSD is not changed.
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::transfer_everusd()]
+        fn transfer_everusd(origin, to: T::AccountId, #[compact] amount: EverUSDBalance) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            Self::transfer_everusd_from(caller, to, amount, false)
+        }
+
+        /// <pre>
+        /// Method: transfer_everusd_keep_alive(origin, to: T::AccountId, amount: EverUSDBalance)
+        /// Arguments:  origin: AccountId - transaction caller
+        ///             to: AccountId - recipient account
+        ///             amount: EverUSDBalance - amount of tokens to transfer
+        /// Access: any account, registered in AccountRegistry with at least one role
+        ///
+        /// Same as transfer_everusd(), but fails if the transfer empties caller's balance
+        /// or leaves not enough tokens to cover caller's pending burn requests
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::transfer_everusd()]
+        fn transfer_everusd_keep_alive(origin, to: T::AccountId, #[compact] amount: EverUSDBalance) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            Self::transfer_everusd_from(caller, to, amount, true)
+        }
+
         // Bonds handling functions
 
         /// <pre>
@@ -722,10 +1385,10 @@ decl_module! {
         /// If all checks were passed, bond object is created in BondRegistry, receives state "PREPARE"
         /// and awaits when account with Master role allows it to be moved to state BOOKING
         /// </pre>
-        #[weight = <T as Config>::WeightInfo::bond_add_new()]
+        #[weight = <T as Config>::WeightInfo::bond_add_new(body.impact_data_baseline.len() as u32)]
         fn bond_add_new(origin, bond: BondId, body: BondInnerStructOf<T> ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            ensure!(Self::account_is_issuer(&caller),Error::<T>::AccountNotAuthorized);
+            ensure!(Self::account_has_role(&caller, ISSUER_ROLE_MASK, Some(&bond)),Error::<T>::AccountNotAuthorized);
             ensure!(body.is_valid(T::TimeStep::get()), Error::<T>::BondParamIncorrect );
             ensure!(!BondRegistry::<T>::contains_key(&bond), Error::<T>::BondAlreadyExists);
 
@@ -750,7 +1413,7 @@ decl_module! {
         /// Arguments: origin: AccountId - transaction caller, assigner
         ///            bond: BondId - bond identifier
         ///            acc: AccountId - assignee account
-        /// Access: Master role
+        /// Access: Master role or MasterOrigin
         ///
         /// Assigns target account to be the manager of the bond. Manager can make
         /// almost the same actions with bond as Issuer, instead of most important,
@@ -761,10 +1424,9 @@ decl_module! {
         /// </pre>
         #[weight = <T as Config>::WeightInfo::bond_set()]
         fn bond_set_manager(origin, bond: BondId, acc: T::AccountId) -> DispatchResult {
-            let caller = ensure_signed(origin)?;
             // Bond Auxiliary roles can be set only by Master
-            ensure!(Self::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
-            ensure!(Self::account_is_manager(&acc), Error::<T>::AccountRoleParamIncorrect);
+            let caller = Self::ensure_master(origin)?;
+            ensure!(Self::account_has_role(&acc, MANAGER_ROLE_MASK, Some(&bond)), Error::<T>::AccountRoleParamIncorrect);
 
             Self::with_bond(&bond, |item|{
                 ensure!(
@@ -783,7 +1445,7 @@ decl_module! {
         /// Arguments: origin: AccountId - transaction caller, assigner
         ///            bond: BondId - bond identifier
         ///            acc: AccountId - assignee
-        /// Access: Master role
+        /// Access: Master role or MasterOrigin
         ///
         /// Assigns target account to be the auditor of the bond. Auditor confirms
         /// impact data coming in bond, and performs other verification-related actions.
@@ -792,16 +1454,16 @@ decl_module! {
         /// </pre>
         #[weight = <T as Config>::WeightInfo::bond_set()]
         fn bond_set_auditor(origin, bond: BondId, acc: T::AccountId) -> DispatchResult {
-            let caller = ensure_signed(origin)?;
             // Bond auxiliary roles can be set only by Master
-            ensure!(Self::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
-            ensure!(Self::account_is_auditor(&acc), Error::<T>::AccountRoleParamIncorrect);
+            let caller = Self::ensure_master(origin)?;
+            ensure!(Self::account_has_role(&acc, AUDITOR_ROLE_MASK, Some(&bond)), Error::<T>::AccountRoleParamIncorrect);
 
             Self::with_bond(&bond, |item|{
                 ensure!(
                     matches!(item.state, BondState::PREPARE | BondState::BOOKING),
                     Error::<T>::BondStateNotPermitAction
                 );
+                ensure!(!item.auditors.contains(&acc), Error::<T>::BondParamIncorrect);
                 item.auditor = acc;
                 item.nonce += 1;
                 Self::deposit_event(RawEvent::BondChanged(caller, bond));
@@ -809,22 +1471,59 @@ decl_module! {
             })
         }
 
+        /// <pre>
+        /// Method: bond_set_auditors(origin, bond: BondId, auditors: Vec<T::AccountId>, quorum: u32)
+        /// Arguments: origin: AccountId - transaction caller, assigner
+        ///            bond: BondId - bond identifier
+        ///            auditors: Vec<AccountId> - additional auditors of the bond
+        ///            quorum: u32 - amount of auditor approvals, required to sign impact report
+        /// Access: Master role or MasterOrigin
+        ///
+        /// Assigns additional auditors, confirming impact data together with the bond auditor.
+        /// Impact report becomes signed only when "quorum" of auditors approve the same impact_data.
+        /// Assigned accounts must have a role AUDITOR and differ from each other and from the
+        /// bond auditor. Quorum cannot exceed total amount of bond auditors.
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_set_auditors(auditors.len() as u32)]
+        fn bond_set_auditors(origin, bond: BondId, auditors: Vec<T::AccountId>, #[compact] quorum: u32) -> DispatchResult {
+            // Bond auxiliary roles can be set only by Master
+            let caller = Self::ensure_master(origin)?;
+            ensure!(auditors.len() <= MAX_BOND_AUDITORS, Error::<T>::BondParamIncorrect);
+            ensure!(auditors.iter().all(|acc| Self::account_has_role(acc, AUDITOR_ROLE_MASK, Some(&bond))), Error::<T>::AccountRoleParamIncorrect);
+            ensure!(quorum >= 1 && quorum as usize <= auditors.len() + 1, Error::<T>::BondParamIncorrect);
+
+            Self::with_bond(&bond, |item|{
+                ensure!(
+                    matches!(item.state, BondState::PREPARE | BondState::BOOKING),
+                    Error::<T>::BondStateNotPermitAction
+                );
+                ensure!(
+                    auditors.iter().enumerate().all(|(index, acc)| *acc != item.auditor && !auditors[..index].contains(acc)),
+                    Error::<T>::BondParamIncorrect
+                );
+                item.auditors = auditors;
+                item.auditor_quorum = quorum;
+                item.nonce += 1;
+                Self::deposit_event(RawEvent::BondChanged(caller, bond));
+                Ok(())
+            })
+        }
+
         /// <pre>
         /// Method: bond_set_impact_reporter(origin, bond: BondId, acc: T::AccountId)
         /// Arguments: origin: AccountId - transaction caller, assigner
         ///            bond: BondId - bond identifier
         ///            acc: AccountId - assignee
-        /// Access: only accounts with Master role
+        /// Access: only accounts with Master role or MasterOrigin
         ///
         /// Assigns an account to be a publisher of impact_data for this bond. Only assigned
         /// by Master, target account must have IMPACT_REPORTER role.
         /// </pre>
         #[weight = <T as Config>::WeightInfo::bond_set()]
         fn bond_set_impact_reporter(origin, bond: BondId, acc: T::AccountId) -> DispatchResult {
-            let caller = ensure_signed(origin)?;
             // Bond auxiliary roles can be set only by Master
-            ensure!(Self::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
-            ensure!(Self::account_is_impact_reporter(&acc), Error::<T>::AccountRoleParamIncorrect);
+            let caller = Self::ensure_master(origin)?;
+            ensure!(Self::account_has_role(&acc, IMPACT_REPORTER_ROLE_MASK, Some(&bond)), Error::<T>::AccountRoleParamIncorrect);
 
             Self::with_bond(&bond, |item|{
                 item.impact_reporter = acc;
@@ -849,7 +1548,7 @@ decl_module! {
         /// Function accepts same information as bond_add(), but requires, that only allowed options was
         /// changed (calling "is_financial_options_eq()" with previous version of bond)
         /// </pre>
-        #[weight = <T as Config>::WeightInfo::bond_update()]
+        #[weight = <T as Config>::WeightInfo::bond_update(body.impact_data_baseline.len() as u32)]
         fn bond_update(origin, bond: BondId,#[compact] nonce: u64, body: BondInnerStructOf<T>) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(body.is_valid(T::TimeStep::get()), Error::<T>::BondParamIncorrect );
@@ -870,123 +1569,366 @@ decl_module! {
                     ensure!( item.inner.is_financial_options_eq(&body), Error::<T>::BondStateNotPermitAction );
                 }
                 item.inner = body;
+                // registered documents define root hashes of their packs
+                apply_document_roots::<T::Hashing, _, _>(&mut item.inner, &Self::bond_documents(&bond));
                 item.nonce += 1;
-                Self::deposit_event(RawEvent::BondChanged(caller, bond));
+                Self::deposit_event(RawEvent::BondChanged(Some(caller), bond));
 
                 Ok(())
             })
         }
 
         /// <pre>
-        /// Method: bond_release(origin, bond: BondId)
+        /// Method: bond_document_add(origin, bond: BondId, doc_type: BondDocumentType, content_hash: Hash, uri: Vec<u8>)
         /// Arguments: origin: AccountId - transaction caller
         ///            bond: BondId - bond identifier
-        ///            nonce: u64 - bond nonce
-        /// Access: only accounts with Master role
+        ///            doc_type: BondDocumentType - documentation pack of the document
+        ///            content_hash: Hash - hash of document content
+        ///            uri: Vec<u8> - URI or IPFS CID of the document
+        /// Access: bond Issuer, bond Manager or Registrar
         ///
-        /// Releases the bond on the market starting presale.
-        /// Moves bond form PREPARE to BOOKING state, allowing investors to buy
-        /// packs of bond units(BU), denying any changes of financial parameters,
-        /// and waiting until "mincap" of bond units will be booked. If it happens,
-        /// bond can further be move to ACTIVE state, allowing Investors to trade
-        /// their Bond Units on free market with other Investors/
-        /// Function requires PREPARE state, not expired "mincap_deadline" to be successful.
-        /// Other checks are: nonce and "is_valid()", to avoid release of incorrect bond
+        /// Registers new document in bond documentation pack. Documents can be added only
+        /// in PREPARE state, after release documentation can be changed only by amendments
+        /// (see bond_document_amend). Root hash of the documentation pack is recalculated as
+        /// Merkle root over actual versions of pack documents.
         /// </pre>
-
-        #[weight = <T as Config>::WeightInfo::bond_release()]
-        fn bond_release(origin, bond: BondId, #[compact]  nonce: u64) -> DispatchResult {
+        #[weight = <T as Config>::WeightInfo::bond_document_add(MAX_BOND_DOCUMENTS)]
+        fn bond_document_add(origin, bond: BondId, doc_type: BondDocumentType, content_hash: T::Hash, uri: Vec<u8>) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            // Bond can be released only by Master
-            ensure!(Self::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(uri.len() <= MAX_DOCUMENT_URI_LEN, Error::<T>::DocumentParamIncorrect);
+            let now = Timestamp::<T>::get();
+
             Self::with_bond(&bond, |item|{
-                ensure!(item.nonce == nonce, Error::<T>::BondNonceObsolete );
+                ensure!(
+                    item.issuer == caller || item.manager == caller
+                        || Self::account_has_role(&caller, REGISTRAR_ROLE_MASK, Some(&bond)),
+                    Error::<T>::BondAccessDenied
+                );
                 ensure!(item.state == BondState::PREPARE, Error::<T>::BondStateNotPermitAction);
-                ensure!(item.inner.is_valid(T::TimeStep::get()), Error::<T>::BondParamIncorrect);
-
-                let now = Timestamp::<T>::get();
-                // Ensure booking deadline is in the future
-                ensure!(item.inner.mincap_deadline>now, Error::<T>::BondStateNotPermitAction);
 
-                item.booking_start_date = now;
-                item.state = BondState::BOOKING;
+                let document = Self::document_insert(&bond, BondDocumentStruct{
+                    doc_type,
+                    content_hash,
+                    uri,
+                    uploader: caller.clone(),
+                    create_date: now,
+                    version: 1,
+                    amends: None,
+                    amended_by: None,
+                    acknowledged_by: Vec::new(),
+                })?;
+                apply_document_roots::<T::Hashing, _, _>(&mut item.inner, &Self::bond_documents(&bond));
                 item.nonce += 1;
-                Self::deposit_event(RawEvent::BondReleased(caller, bond));
+
+                Self::deposit_event(RawEvent::BondDocumentAdded(caller, bond, document));
                 Ok(())
             })
         }
 
-
         /// <pre>
-        /// Method: bond_unit_package_buy(origin, bond: BondId, unit_amount: BondUnitAmount )
+        /// Method: bond_document_amend(origin, bond: BondId, document: BondDocumentId, content_hash: Hash, uri: Vec<u8>)
         /// Arguments: origin: AccountId - transaction caller
         ///            bond: BondId - bond identifier
-        ///            nonce: u64 - bond nonce
-        ///            unit_amount: BondUnitAmount - amount of bond units
-        /// Access: only accounts with Investor role
-        ///
-        /// Buy bond units from bond in BOOKING, ACTIVE or BANKRUPT state.
-        /// Investor passes amount of BUs he wants to buy. Function calculates price of BUs
-        /// and adds new BondUnitsPackage, with amount of BUs that was bought. BondUnitsPackage
-        /// contains:
-        ///  - amount of BUs bought
-        ///  - aquisition time(number of period when this BUs package was bought)
-        ///  - accrued coupon yield(EverUSD): internal field, calculated and saved only when any
-        ///    financial operations with this package are performed
+        ///            document: BondDocumentId - document to amend, must be the latest version
+        ///            content_hash: Hash - hash of amendment content
+        ///            uri: Vec<u8> - URI or IPFS CID of the amendment
+        /// Access: bond Issuer, bond Manager or Registrar
         ///
-        /// Bond must be in BOOKING, ACTIVE, BANKRUPT state, amount of Bond Units
-        /// should not except "bond_units_maxcap_amount"
+        /// Appends new version of the document. The original document is kept in registry and
+        /// linked to the amendment. Amendment requires new acknowledgements of auditors.
+        /// Not available for FINISHED bonds.
         /// </pre>
-        #[weight = <T as Config>::WeightInfo::bond_unit_package_buy()]
-        fn bond_unit_package_buy(origin, bond: BondId,#[compact]  nonce: u64,#[compact] unit_amount: BondUnitAmount ) -> DispatchResult {
+        #[weight = <T as Config>::WeightInfo::bond_document_amend(MAX_BOND_DOCUMENTS)]
+        fn bond_document_amend(origin, bond: BondId, #[compact] document: BondDocumentId, content_hash: T::Hash, uri: Vec<u8>) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            ensure!(Self::account_is_investor(&caller), Error::<T>::AccountNotAuthorized);
-            Self::with_bond(&bond, |mut item|{
-                ensure!(item.nonce == nonce, Error::<T>::BondNonceObsolete);
-                ensure!(
-                    matches!(item.state, BondState::BANKRUPT | BondState::ACTIVE | BondState::BOOKING),
-                    Error::<T>::BondStateNotPermitAction
-                );
-                // issuer cannot buy his own bonds
-                ensure!(item.issuer != caller, Error::<T>::AccountNotAuthorized);
-
-                let issued_amount = unit_amount.checked_add(item.issued_amount)
-                    .ok_or(Error::<T>::BalanceOverdraft)?;
+            ensure!(uri.len() <= MAX_DOCUMENT_URI_LEN, Error::<T>::DocumentParamIncorrect);
+            let now = Timestamp::<T>::get();
 
+            Self::with_bond(&bond, |item|{
                 ensure!(
-                    issued_amount <= item.inner.bond_units_maxcap_amount,
-                    Error::<T>::BondParamIncorrect
+                    item.issuer == caller || item.manager == caller
+                        || Self::account_has_role(&caller, REGISTRAR_ROLE_MASK, Some(&bond)),
+                    Error::<T>::BondAccessDenied
                 );
+                ensure!(item.state != BondState::FINISHED, Error::<T>::BondStateNotPermitAction);
+
+                let mut original = BondDocument::<T>::get(&bond, document).ok_or(Error::<T>::DocumentNotFound)?;
+                ensure!(original.is_actual(), Error::<T>::DocumentParamIncorrect);
+
+                let amendment = Self::document_insert(&bond, BondDocumentStruct{
+                    doc_type: original.doc_type,
+                    content_hash,
+                    uri,
+                    uploader: caller.clone(),
+                    create_date: now,
+                    version: original.version + 1,
+                    amends: Some(document),
+                    amended_by: None,
+                    acknowledged_by: Vec::new(),
+                })?;
+                original.amended_by = Some(amendment);
+                BondDocument::<T>::insert(&bond, document, original);
 
-                let package_value =  item.par_value( unit_amount ) ;
-
-                Self::balance_sub(&caller, package_value)?;
+                apply_document_roots::<T::Hashing, _, _>(&mut item.inner, &Self::bond_documents(&bond));
+                item.nonce += 1;
 
-                let now = Timestamp::<T>::get();
+                Self::deposit_event(RawEvent::BondDocumentAmended(caller, bond, document, amendment));
+                Ok(())
+            })
+        }
 
-                // get the number of seconds after bond activation.
-                // zero value if the bond has not activated yet
-                let (acquisition,_) = item.time_passed_after_activation( now ).unwrap_or( (0,0) );
-                // @FIXME assess the costs of current array struct for storing packages and
-                // compare them with a more efficient way to store data
-                BondUnitPackageRegistry::<T>::mutate(&bond, &caller, |packages|{
-                    packages.push(
-                        BondUnitPackage{
-                             bond_units: unit_amount,
-                             acquisition,
-                             coupon_yield: 0,
-                        }
-                    );
-                });
+        /// <pre>
+        /// Method: bond_document_acknowledge(origin, bond: BondId, document: BondDocumentId)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            document: BondDocumentId - document identifier
+        /// Access: auditors assigned to the bond
+        ///
+        /// Auditor certifies the document. Each auditor can acknowledge the document once.
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_document_acknowledge()]
+        fn bond_document_acknowledge(origin, bond: BondId, #[compact] document: BondDocumentId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(Self::account_has_role(&caller, AUDITOR_ROLE_MASK, Some(&bond)), Error::<T>::AccountNotAuthorized);
+            ensure!(BondRegistry::<T>::get(&bond).is_auditor(&caller), Error::<T>::BondAccessDenied);
 
-                item.issued_amount = issued_amount;
+            BondDocument::<T>::try_mutate(&bond, document, |doc|->DispatchResult {
+                let doc = doc.as_mut().ok_or(Error::<T>::DocumentNotFound)?;
+                ensure!(!doc.acknowledged_by.contains(&caller), Error::<T>::DocumentParamIncorrect);
+                doc.acknowledged_by.push(caller.clone());
 
+                Self::deposit_event(RawEvent::BondDocumentAcknowledged(caller, bond, document));
+                Ok(())
+            })
+        }
 
-                if matches!(item.state, BondState::ACTIVE | BondState::BANKRUPT) {
-                    item.bond_debit += package_value;
-                    // in BondState::ACTIVE or BondState::BANKRUPT received everusd
-                    // can be forwarded to pay off the debt
-                    // @TODO add postdispatch weight
+        /// <pre>
+        /// Method: bond_credit_rating_set(origin, bond: BondId, rating: Vec<u8>, report_hash: Hash)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            rating: Vec<u8> - rating symbol, f.e. "AA+"
+        ///            report_hash: Hash - hash of the rating report
+        /// Access: Rating Agent role
+        ///
+        /// Publishes credit rating of the bond, replacing the previous one.
+        /// Not available for FINISHED bonds
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_credit_rating_set()]
+        fn bond_credit_rating_set(origin, bond: BondId, rating: Vec<u8>, report_hash: T::Hash) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(Self::account_has_role(&caller, RATING_AGENT_ROLE_MASK, Some(&bond)), Error::<T>::AccountNotAuthorized);
+            ensure!(!rating.is_empty() && rating.len() <= MAX_RATING_LEN, Error::<T>::BondParamIncorrect);
+            ensure!(BondRegistry::<T>::contains_key(&bond), Error::<T>::BondNotFound);
+            ensure!(BondRegistry::<T>::get(&bond).state != BondState::FINISHED, Error::<T>::BondStateNotPermitAction);
+
+            BondCreditRating::<T>::insert(&bond, BondCreditRatingStruct {
+                agent: caller.clone(),
+                rating: rating.clone(),
+                report_hash,
+                date: Timestamp::<T>::get(),
+            });
+            Self::deposit_event(RawEvent::BondCreditRatingSet(caller, bond, rating));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: bond_allow_list_set(origin, bond: BondId, investor: AccountId, allowed: bool)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            investor: AccountId - investor account
+        ///            allowed: bool - add to or remove from allow-list
+        /// Access: bond Issuer or bond Manager
+        ///
+        /// Changes allow-list of the bond. If "allow_list_only" eligibility rule is set, only
+        /// investors from allow-list can buy Bond Units. Removed investors keep their Bond Units
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_allow_list_set()]
+        fn bond_allow_list_set(origin, bond: BondId, investor: T::AccountId, allowed: bool) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(BondRegistry::<T>::contains_key(&bond), Error::<T>::BondNotFound);
+            let item = BondRegistry::<T>::get(&bond);
+            ensure!(item.issuer == caller || item.manager == caller, Error::<T>::BondAccessDenied);
+            ensure!(item.state != BondState::FINISHED, Error::<T>::BondStateNotPermitAction);
+
+            if allowed {
+                BondAllowList::<T>::insert(&bond, &investor, true);
+            } else {
+                BondAllowList::<T>::remove(&bond, &investor);
+            }
+            Self::deposit_event(RawEvent::BondAllowListSet(caller, bond, investor, allowed));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: bond_trading_halt_set(origin, bond: BondId, halted: bool)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            halted: bool - halt or resume trading
+        /// Access: bond Manager, Master role or MasterOrigin
+        ///
+        /// Halts or resumes trading of the bond: while trading is halted, Bond Units cannot be
+        /// offered in sale lots and sell orders, and cannot be transferred between bondholders
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_trading_halt_set()]
+        fn bond_trading_halt_set(origin, bond: BondId, halted: bool) -> DispatchResult {
+            let caller = Self::ensure_master_or(origin, MANAGER_ROLE_MASK, Some(&bond))?;
+            ensure!(BondRegistry::<T>::contains_key(&bond), Error::<T>::BondNotFound);
+            let item = BondRegistry::<T>::get(&bond);
+            ensure!(
+                caller.as_ref().map_or(true, |caller| item.manager == *caller || Self::account_is_master(caller)),
+                Error::<T>::BondAccessDenied
+            );
+
+            if halted {
+                BondTradingHalt::insert(&bond, true);
+            } else {
+                BondTradingHalt::remove(&bond);
+            }
+            Self::deposit_event(RawEvent::BondTradingHaltSet(caller, bond, halted));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: bond_unit_lockup_set(origin, bond: BondId, bondholder: AccountId, until: Option<Moment>)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            bondholder: AccountId - locked up bondholder, f.e. anchor investor
+        ///            until: Option<Moment> - Bond Units cannot be sold before this moment,
+        ///                   None removes the lock-up
+        /// Access: bond Issuer or bond Manager
+        ///
+        /// Sets individual lock-up of the bondholder, additional to lock-up period of the bond
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_unit_lockup_set()]
+        fn bond_unit_lockup_set(origin, bond: BondId, bondholder: T::AccountId, until: Option<T::Moment>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(BondRegistry::<T>::contains_key(&bond), Error::<T>::BondNotFound);
+            let item = BondRegistry::<T>::get(&bond);
+            ensure!(item.issuer == caller || item.manager == caller, Error::<T>::BondAccessDenied);
+            ensure!(item.state != BondState::FINISHED, Error::<T>::BondStateNotPermitAction);
+
+            match until {
+                Some(until) => BondUnitLockup::<T>::insert(&bond, &bondholder, until),
+                None => BondUnitLockup::<T>::remove(&bond, &bondholder),
+            }
+            Self::deposit_event(RawEvent::BondUnitLockupSet(caller, bond, bondholder, until));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: bond_release(origin, bond: BondId)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            nonce: u64 - bond nonce
+        /// Access: only accounts with Master role or MasterOrigin
+        ///
+        /// Releases the bond on the market starting presale.
+        /// Moves bond form PREPARE to BOOKING state, allowing investors to buy
+        /// packs of bond units(BU), denying any changes of financial parameters,
+        /// and waiting until "mincap" of bond units will be booked. If it happens,
+        /// bond can further be move to ACTIVE state, allowing Investors to trade
+        /// their Bond Units on free market with other Investors/
+        /// Function requires PREPARE state, not expired "mincap_deadline" to be successful.
+        /// Other checks are: nonce and "is_valid()", to avoid release of incorrect bond
+        /// </pre>
+
+        #[weight = <T as Config>::WeightInfo::bond_release()]
+        fn bond_release(origin, bond: BondId, #[compact]  nonce: u64) -> DispatchResult {
+            // Bond can be released only by Master
+            let caller = Self::ensure_master(origin)?;
+            Self::with_bond(&bond, |item|{
+                ensure!(item.nonce == nonce, Error::<T>::BondNonceObsolete );
+                ensure!(item.state == BondState::PREPARE, Error::<T>::BondStateNotPermitAction);
+                ensure!(item.inner.is_valid(T::TimeStep::get()), Error::<T>::BondParamIncorrect);
+
+                let now = Timestamp::<T>::get();
+                // Ensure booking deadline is in the future
+                ensure!(item.inner.mincap_deadline>now, Error::<T>::BondStateNotPermitAction);
+
+                item.booking_start_date = now;
+                item.state = BondState::BOOKING;
+                item.nonce += 1;
+                Self::deposit_event(RawEvent::BondReleased(caller, bond));
+                Ok(())
+            })
+        }
+
+
+        /// <pre>
+        /// Method: bond_unit_package_buy(origin, bond: BondId, unit_amount: BondUnitAmount )
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            nonce: u64 - bond nonce
+        ///            unit_amount: BondUnitAmount - amount of bond units
+        /// Access: only accounts with Investor role
+        ///
+        /// Buy bond units from bond in BOOKING, ACTIVE or BANKRUPT state.
+        /// Investor passes amount of BUs he wants to buy. Function calculates price of BUs
+        /// and adds new BondUnitsPackage, with amount of BUs that was bought. BondUnitsPackage
+        /// contains:
+        ///  - amount of BUs bought
+        ///  - aquisition time(number of period when this BUs package was bought)
+        ///  - accrued coupon yield(EverUSD): internal field, calculated and saved only when any
+        ///    financial operations with this package are performed
+        ///
+        /// Bond must be in BOOKING, ACTIVE, BANKRUPT state, amount of Bond Units
+        /// should not except "bond_units_maxcap_amount". Investor must comply with
+        /// eligibility rules of the bond
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_unit_package_buy(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS)]
+        fn bond_unit_package_buy(origin, bond: BondId,#[compact]  nonce: u64,#[compact] unit_amount: BondUnitAmount ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(Self::account_has_role(&caller, INVESTOR_ROLE_MASK, Some(&bond)), Error::<T>::AccountNotAuthorized);
+            Self::with_bond(&bond, |mut item|{
+                ensure!(item.nonce == nonce, Error::<T>::BondNonceObsolete);
+                ensure!(
+                    matches!(item.state, BondState::BANKRUPT | BondState::ACTIVE | BondState::BOOKING),
+                    Error::<T>::BondStateNotPermitAction
+                );
+                // issuer cannot buy his own bonds
+                ensure!(item.issuer != caller, Error::<T>::AccountNotAuthorized);
+                let now = Timestamp::<T>::get();
+                let holding = Self::bond_units_total(&bond, &caller).saturating_add(unit_amount);
+                Self::ensure_bond_eligible(&bond, &item, &caller, holding, None, now)?;
+
+                let issued_amount = unit_amount.checked_add(item.issued_amount)
+                    .ok_or(Error::<T>::BalanceOverdraft)?;
+
+                ensure!(
+                    issued_amount <= item.inner.bond_units_maxcap_amount,
+                    Error::<T>::BondParamIncorrect
+                );
+
+                ensure!(!Self::is_restructuring_open(&bond, now), Error::<T>::RestructuringInProgress);
+
+                // get the number of seconds after bond activation.
+                // zero value if the bond has not activated yet
+                let (acquisition, period) = item.time_passed_after_activation( now ).unwrap_or( (0,0) );
+                // principal of amortizing bond is partially repaid in passed periods
+                let package_value = item.outstanding_value( unit_amount, period ) ;
+
+                Self::balance_sub(&caller, package_value)?;
+
+                // @FIXME assess the costs of current array struct for storing packages and
+                // compare them with a more efficient way to store data
+                let mut packages = BondUnitPackageRegistry::<T>::get(&bond, &caller);
+                packages.push(
+                    BondUnitPackage{
+                         bond_units: unit_amount,
+                         acquisition,
+                         coupon_yield: 0,
+                    }
+                );
+                Self::set_bond_unit_packages(&bond, &caller, packages);
+
+                item.issued_amount = issued_amount;
+
+
+                if matches!(item.state, BondState::ACTIVE | BondState::BANKRUPT) {
+                    item.bond_debit += package_value;
+                    // in BondState::ACTIVE or BondState::BANKRUPT received everusd
+                    // can be forwarded to pay off the debt
+                    // @TODO add postdispatch weight
                     Self::calc_and_store_bond_coupon_yield(&bond, &mut item, now);
                     // surplus to the issuer balance
                     let free_balance = item.get_free_balance();
@@ -1020,7 +1962,7 @@ decl_module! {
         #[weight = <T as Config>::WeightInfo::bond_unit_package_return()]
         fn bond_unit_package_return(origin, bond: BondId,#[compact]  unit_amount: BondUnitAmount ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            ensure!(Self::account_is_investor(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(Self::account_has_role(&caller, INVESTOR_ROLE_MASK, Some(&bond)), Error::<T>::AccountNotAuthorized);
             ensure!(unit_amount > 0, Error::<T>::BondParamIncorrect);
             // Active Bond cannot be withdrawn
             Self::with_bond(&bond, |item|{
@@ -1029,18 +1971,16 @@ decl_module! {
                 let package_value = item.par_value( unit_amount ) ;
                 ensure!(item.bond_credit >= package_value, Error::<T>::BondParamIncorrect);
 
-                BondUnitPackageRegistry::<T>::try_mutate(&bond, &caller, |packages|->DispatchResult{
-                    ensure!(!packages.is_empty(), Error::<T>::BondParamIncorrect);
-                    if packages.iter().map(|item| item.bond_units).sum::<BondUnitAmount>() == unit_amount {
-                        packages.clear();
-                        Ok(())
-                    } else if let Some(index) = packages.iter().position(|item| item.bond_units == unit_amount ){
-                        packages.remove( index );
-                        Ok(())
-                    } else {
-                        Err( Error::<T>::BondParamIncorrect.into() )
-                    }
-                })?;
+                let mut packages = BondUnitPackageRegistry::<T>::get(&bond, &caller);
+                ensure!(!packages.is_empty(), Error::<T>::BondParamIncorrect);
+                if packages.iter().map(|item| item.bond_units).sum::<BondUnitAmount>() == unit_amount {
+                    packages.clear();
+                } else if let Some(index) = packages.iter().position(|item| item.bond_units == unit_amount ){
+                    packages.remove( index );
+                } else {
+                    return Err( Error::<T>::BondParamIncorrect.into() );
+                }
+                Self::set_bond_unit_packages(&bond, &caller, packages);
 
                 item.decrease( package_value );
                 item.issued_amount -= unit_amount;
@@ -1066,8 +2006,8 @@ decl_module! {
         /// for Investors parameters. Cannot be called until "mincap_deadline"
         /// </pre>
         // Called after the Bond was released but not raised enough tokens until the deadline
-        #[weight = <T as Config>::WeightInfo::bond_withdraw()]
-        fn bond_withdraw(origin, bond: BondId) -> DispatchResult {
+        #[weight = <T as Config>::WeightInfo::bond_withdraw(WEIGHT_BONDHOLDERS)]
+        fn bond_withdraw(origin, bond: BondId) -> DispatchResultWithPostInfo {
             let caller = ensure_signed(origin)?;
             // Bond issuer, bond Manager, or Master can do it
             Self::with_bond(&bond, |item|{
@@ -1088,7 +2028,9 @@ decl_module! {
                 // @TODO make it lazy. this implementation do much work to restore balances
                 // that is too CPU and memory expensive.
                 // For each bondholder
+                let mut bondholders: u32 = 0;
                 for (bondholder, package) in BondUnitPackageRegistry::<T>::iter_prefix(&bond){
+                      bondholders += 1;
                       let bondholder_total_amount: BondUnitAmount = package.iter()
                       .map(|item| item.bond_units )
                       .sum();
@@ -1104,9 +2046,10 @@ decl_module! {
                 assert!(item.issued_amount == 0);
 
                 BondUnitPackageRegistry::<T>::remove_prefix(&bond);
+                BondHolderCount::remove(&bond);
 
                 Self::deposit_event(RawEvent::BondWithdrawal(caller, bond));
-                Ok(())
+                Ok(Some(<T as Config>::WeightInfo::bond_withdraw(bondholders)).into())
             })
         }
 
@@ -1115,7 +2058,7 @@ decl_module! {
         /// Arguments: origin: AccountId - transaction caller
         ///            bond: BondId - bond identifier
         ///            nonce: u64 - bond nonce
-        /// Access: only accounts with Master role
+        /// Access: only accounts with Master role or MasterOrigin
         ///
         /// Activates the bond after it raised minimum capacity of bond units, opening
         /// BondUnitsPackages, owned by Investors, to be traded of free market. Function
@@ -1128,10 +2071,9 @@ decl_module! {
         /// </pre>
         #[weight = <T as Config>::WeightInfo::bond_activate()]
         fn bond_activate(origin, bond: BondId,#[compact]  nonce: u64) -> DispatchResult {
-            let caller = ensure_signed(origin)?;
             //Bond can be activated only by Master
-            ensure!(Self::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
             //if it's raised enough bond units during bidding process
+            let caller = Self::ensure_master(origin)?;
             Self::with_bond(&bond, |item|{
                 ensure!(item.nonce == nonce, Error::<T>::BondNonceObsolete );
                 ensure!(item.state == BondState::BOOKING, Error::<T>::BondStateNotPermitAction);
@@ -1158,6 +2100,10 @@ decl_module! {
 
                 BondImpactReport::insert(&bond, &reports);
 
+                if let Some(moment) = item.coupon_accrual_moment(0) {
+                    Self::schedule_accrual(bond, moment);
+                }
+
                 // withdraw all available bond fund
                 let amount = item.bond_debit;
                 Self::balance_add(&item.issuer, item.bond_debit)?;
@@ -1169,23 +2115,27 @@ decl_module! {
         }
 
         /// <pre>
-        /// Method: bond_impact_report_send(origin, bond: BondId, impact_data: u64 )
+        /// Method: bond_impact_report_send(origin, bond: BondId, period: u32, impact_data: Vec<u64> )
         /// Arguments: origin: AccountId - transaction caller
         ///            bond: BondId - bond identifier
-        ///            impact_data: u64 - report value
+        ///            period: u32 - report period starting from 0
+        ///            impact_data: Vec<u64> - report values: main KPI, followed by additional KPIs of bond
         /// Access: bond Issuer or ImpactReporter, assigned to the bond
         ///
         /// Saves inital impact_report_data for current period, for later confirmation by Auditor.
         /// Data must be send during correct period (depending on "impact_data_send_period" and "payment_period"
         /// properties of bond). Also, impact_data for this period must be not confirmed by Auditor yet.
+        /// Report must contain exactly one value for every KPI of bond.
+        /// Resending the report discards all auditor approvals of the previous version.
         /// </pre>
-        #[weight = <T as Config>::WeightInfo::bond_impact_report_send()]
-        fn bond_impact_report_send(origin, bond: BondId,#[compact] period: BondPeriodNumber,#[compact] impact_data: u64 ) -> DispatchResult {
+        #[weight = <T as Config>::WeightInfo::bond_impact_report_send(MAX_BOND_AUDITORS as u32 + 1)]
+        fn bond_impact_report_send(origin, bond: BondId,#[compact] period: BondPeriodNumber, impact_data: Vec<u64> ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             let now = Timestamp::<T>::get();
             let moment = {
                 let item = BondRegistry::<T>::get(bond);
                 ensure!(item.issuer == caller || item.impact_reporter == caller, Error::<T>::BondAccessDenied );
+                ensure!(impact_data.len() == item.inner.impact_data_len(), Error::<T>::BondParamIncorrect );
                 ensure!(Self::is_report_in_time(&item, now, period), Error::<T>::BondOutOfOrder );
                 item.time_passed_after_activation(now).map(|(moment, _period)| moment ).unwrap()
             };
@@ -1196,7 +2146,8 @@ decl_module! {
                 ensure!(index < reports.len() && !reports[index].signed, Error::<T>::BondParamIncorrect);
 
                 reports[index].create_period = moment;
-                reports[index].impact_data = impact_data;
+                reports[index].impact_data = impact_data.clone();
+                BondImpactReportApproval::<T>::remove_prefix((bond, period));
 
                 Self::deposit_event(RawEvent::BondImpactReportSent( caller, bond, period, impact_data));
                 Ok(())
@@ -1204,41 +2155,52 @@ decl_module! {
         }
 
         /// <pre>
-        /// Method: bond_impact_report_approve(origin, bond: BondId, period: u64, impact_data: u64 )
+        /// Method: bond_impact_report_approve(origin, bond: BondId, period: u32, impact_data: Vec<u64> )
         /// Arguments: origin: AccountId - transaction caller
         ///            bond: BondId - bond identifier
         ///            period: u32 - report period starting from 0
-        ///            impact_data: u64 - report value
+        ///            impact_data: Vec<u64> - report values: main KPI, followed by additional KPIs of bond
         ///
-        /// Access: only Auditor assigned to the bond
+        /// Access: only auditors assigned to the bond
         /// Confirms "impact_report_data", that already being sent by the bond Issuer or ImpactReporter.
         /// Auditor sends period number and exact value of impact_data, he confirms. Confirmation must be
         /// send during the correct period (depending on "impact_data_send_period" and "payment_period"
         /// properties of bond)
+        /// Approval of every auditor is recorded, report becomes signed when "auditor_quorum" of
+        /// current bond auditors approve the reported impact_data. Approval with other impact_data is
+        /// recorded as well and raises BondImpactReportDisputed event.
         /// </pre>
         // Auditor signs impact report
-        #[weight = <T as Config>::WeightInfo::bond_impact_report_approve()]
-        fn bond_impact_report_approve(origin, bond: BondId,#[compact] period: BondPeriodNumber,#[compact]  impact_data: u64 ) -> DispatchResult {
+        #[weight = <T as Config>::WeightInfo::bond_impact_report_approve(MAX_BOND_AUDITORS as u32 + 1)]
+        fn bond_impact_report_approve(origin, bond: BondId,#[compact] period: BondPeriodNumber, impact_data: Vec<u64> ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            ensure!(Self::account_is_auditor(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(Self::account_has_role(&caller, AUDITOR_ROLE_MASK, Some(&bond)), Error::<T>::AccountNotAuthorized);
             let now = Timestamp::<T>::get();
-            {
-                let item = BondRegistry::<T>::get(bond);
-                ensure!(item.auditor == caller, Error::<T>::BondAccessDenied );
-                ensure!(Self::is_report_in_time(&item, now, period), Error::<T>::BondOutOfOrder );
-            }
+            let item = BondRegistry::<T>::get(bond);
+            ensure!(item.is_auditor(&caller), Error::<T>::BondAccessDenied );
+            ensure!(Self::is_report_in_time(&item, now, period), Error::<T>::BondOutOfOrder );
+            ensure!(impact_data.len() == item.inner.impact_data_len(), Error::<T>::BondParamIncorrect);
 
             let index: usize = period as usize;
             BondImpactReport::try_mutate(&bond, |reports|->DispatchResult {
 
                 ensure!(index < reports.len(), Error::<T>::BondParamIncorrect );
                 let report = &reports[index];
-                ensure!(report.create_period > 0 , Error::<T>::BondParamIncorrect);
-                ensure!(!report.signed && report.impact_data == impact_data,
-                 Error::<T>::BondParamIncorrect
-                );
+                ensure!(report.create_period > 0 && !report.signed, Error::<T>::BondParamIncorrect);
+
+                BondImpactReportApproval::<T>::insert((bond, period), &caller, &impact_data);
+                if report.impact_data != impact_data {
+                    Self::deposit_event(RawEvent::BondImpactReportDisputed( caller, bond, period, impact_data));
+                    return Ok(());
+                }
 
-                reports[index].signed = true;
+                // approvals of replaced auditors don't count
+                let approvals = BondImpactReportApproval::<T>::iter_prefix((bond, period))
+                    .filter(|(auditor, approved)| item.is_auditor(auditor) && *approved == impact_data)
+                    .count();
+                if approvals >= item.approval_quorum() as usize {
+                    reports[index].signed = true;
+                }
 
                 Self::deposit_event(RawEvent::BondImpactReportApproved( caller, bond, period, impact_data));
                 Ok(())
@@ -1263,7 +2225,7 @@ decl_module! {
         /// withdraw all their accrued coupon yield and parts of bond maturity debt
         /// Bond becomes FINISHED.
         /// </pre>
-        #[weight = <T as Config>::WeightInfo::bond_redeem()]
+        #[weight = <T as Config>::WeightInfo::bond_redeem(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS)]
         fn bond_redeem(origin, bond: BondId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             let now = Timestamp::<T>::get();
@@ -1275,24 +2237,104 @@ decl_module! {
                     _ => return Err( Error::<T>::BondOutOfOrder.into() ),
                 };
 
+                let ytm = Self::redeem_bond(&bond, &mut item, now)?;
+                Self::deposit_event(RawEvent::BondRedeemed(caller, bond, ytm));
+                Ok(())
+            })
+        }
+
+        /// <pre>
+        /// Method: bond_call(origin, bond: BondId)
+        /// Arguments: origin: AccountId - bond issuer
+        ///            bond: BondId - bond identifier
+        ///
+        /// Access: Issuer of the bond
+        /// Redeems the bond before maturity date, using call option of the bond.
+        /// Bond can be called only during the payment period, following one of call dates
+        /// from "call_schedule" of the bond. Coupon yield is accrued up to the call date,
+        /// then Issuer pays principal value with call premium and accrued coupon yield, like
+        /// in bond_redeem(). Function moves bond from ACTIVE or BANKRUPT state to FINISHED.
+        /// Investors then withdraw principal value with call premium and coupon yield using
+        /// bond_withdraw_everusd()
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_call(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS)]
+        fn bond_call(origin, bond: BondId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+            Self::with_bond(&bond, |mut item|{
+                ensure!(item.issuer == caller, Error::<T>::BondAccessDenied);
+                ensure!( matches!(item.state, BondState::ACTIVE|BondState::BANKRUPT), Error::<T>::BondStateNotPermitAction );
+                let call_premium_bps = match (&item.inner.call_schedule, item.time_passed_after_activation(now)) {
+                    (Some(schedule), Some((_, period))) if period < item.get_periods() && schedule.is_callable(period) => schedule.premium_bps,
+                    _ => return Err( Error::<T>::BondOutOfOrder.into() ),
+                };
+
+                item.call_premium_bps = call_premium_bps;
+                let ytm = Self::redeem_bond(&bond, &mut item, now)?;
+                Self::deposit_event(RawEvent::BondCalled(caller, bond, ytm));
+                Ok(())
+            })
+        }
+
+        /// <pre>
+        /// Method: bond_unit_put(origin, bond: BondId, bond_units: BondUnitAmount)
+        /// Arguments: origin: AccountId - bondholder
+        ///            bond: BondId - bond identifier
+        ///            bond_units: BondUnitAmount - amount of Bond Units to return
+        ///
+        /// Access: any bondholder
+        /// Returns Bond Units to the bond before maturity date, using put option of the bond.
+        /// Bond Units can be put only during the payment period, following one of put dates
+        /// from "put_schedule" of the bond. Bond Units, put up for sale with lots or orders,
+        /// cannot be returned. Bondholder receives all accrued coupon yield and outstanding principal
+        /// value of returned Bond Units from the bond fund.
+        /// If the bond fund cannot pay off principal value during interest pay period, call fails.
+        /// After interest pay period the bond becomes BANKRUPT, like in bond_withdraw_everusd(),
+        /// and bondholder receives only accrued coupon yield, available in the bond fund.
+        /// Available only for ACTIVE bonds
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_unit_put(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS)]
+        fn bond_unit_put(origin, bond: BondId, #[compact] bond_units: BondUnitAmount) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+            Self::with_bond(&bond, |mut item|{
+                ensure!(item.state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
+                let period = match (&item.inner.put_schedule, item.time_passed_after_activation(now)) {
+                    (Some(schedule), Some((_, period))) if period < item.get_periods() && schedule.is_puttable(period) => period,
+                    _ => return Err( Error::<T>::BondOutOfOrder.into() ),
+                };
+                ensure!(
+                    bond_units > 0 && Self::bond_units_free(&bond, &caller, now) >= bond_units,
+                    Error::<T>::BalanceOverdraft
+                );
+
                 Self::calc_and_store_bond_coupon_yield(&bond, &mut item, now);
-                // now bond_credit has YTM ( yield to mature )
-                let amount = item.bond_credit + item.par_value( item.issued_amount ) ;
-                if amount <= item.bond_debit {
-                    // withdraw free balance
-                    Self::balance_add(&item.issuer, item.bond_debit - amount)?;
-                }else{
-                    let transfer = amount - item.bond_debit;
-                    // pay off debt
-                    Self::balance_sub(&item.issuer, transfer)?;
+                // principal of amortizing bond is partially repaid in passed periods
+                let principal = item.outstanding_value(bond_units, period);
+                if item.get_free_balance() < principal {
+                    ensure!(!Self::is_interest_pay_period(&item, now), Error::<T>::BondFundShortage);
+                    // set bankrupt state, bond fund cannot pay off
+                    item.state = BondState::BANKRUPT;
+                    Self::deposit_event(RawEvent::BondBankrupted(Some(caller.clone()), bond, item.bond_credit, item.bond_debit ));
+
+                    let amount = Self::request_coupon_yield(&bond, &mut item, &caller);
+                    if amount>0{
+                        Self::deposit_event(RawEvent::BondWithdrawEverUSD(caller, bond, amount));
+                    }
+                    return Ok(());
                 }
-                let ytm = item.bond_credit;
-                item.bond_credit = amount;
-                //item.coupon_yield = amount;
-                item.bond_debit = amount;
-                item.state = BondState::FINISHED;
-                item.nonce += 1;
-                Self::deposit_event(RawEvent::BondRedeemed(caller, bond, ytm));
+
+                // pay off all accrued coupon yield before Bond Units are returned
+                let coupon_yield = Self::request_coupon_yield(&bond, &mut item, &caller);
+                let mut packages = BondUnitPackageRegistry::<T>::get(&bond, &caller);
+                surrender_bond_units::<T>(&mut packages, bond_units)?;
+                Self::set_bond_unit_packages(&bond, &caller, packages);
+
+                Self::balance_add(&caller, principal)?;
+                // it's safe to do unchecked subtraction
+                item.bond_debit -= principal;
+                item.issued_amount -= bond_units;
+                Self::deposit_event(RawEvent::BondUnitPut(caller, bond, bond_units, principal + coupon_yield));
                 Ok(())
             })
         }
@@ -1301,17 +2343,16 @@ decl_module! {
         /// Method: bond_declare_bankrupt(origin, bond: BondId)
         /// Arguments: origin: AccountId - transaction caller
         ///            bond: BondId - bond identifier
-        /// Access: Master role
+        /// Access: Master role or MasterOrigin
         ///
         /// Marks the bond as bankrupt, moving it from ACTIVE to BANKRUPT state.
         /// Function checks, that "get_debt()" of bond is > 0 (bond_credit > bond_debit),
         /// and that "interest_pay_period" is not active(Issuer still have a chance to pay debt).
         /// Then function calculates and stores all accumulated coupon_yield and marks bond as BANKRUPT
         /// </pre>
-        #[weight = <T as Config>::WeightInfo::bond_declare_bankrupt()]
+        #[weight = <T as Config>::WeightInfo::bond_declare_bankrupt(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS)]
         fn bond_declare_bankrupt(origin, bond: BondId) -> DispatchResult {
-            let caller = ensure_signed(origin)?;
-             ensure!(Self::account_is_master(&caller), Error::<T>::AccountNotAuthorized);
+            let caller = Self::ensure_master(origin)?;
 
             Self::with_bond(&bond, |mut item|{
                 ensure!(item.state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
@@ -1322,67 +2363,341 @@ decl_module! {
 
                 item.state = BondState::BANKRUPT;
                 item.nonce += 1;
-                Self::deposit_event(RawEvent::BondBankrupted(caller.clone(), bond, item.bond_credit, item.bond_debit));
+                Self::deposit_event(RawEvent::BondBankrupted(caller, bond, item.bond_credit, item.bond_debit));
                 Ok(())
             })
         }
 
         /// <pre>
-        /// Method: bond_accrue_coupon_yield(origin, bond: BondId)
+        /// Method: bond_restructuring_propose(origin, bond: BondId, terms: BondRestructuringTermsStruct, deadline: Moment)
         /// Arguments: origin: AccountId - transaction caller
         ///            bond: BondId - bond identifier
-        /// Access: any account
+        ///            terms: BondRestructuringTermsStruct - new terms of the bond
+        ///            deadline: Moment - end of voting
+        /// Access: bond Issuer or Manager, assigned to the bond
         ///
-        /// Calculates total bond coupon yield(EverUSD) and stores it in "bond_credit"
-        /// by calculating effective interest rates for each passed payment_period.
-        /// This function is a call to "lazy" function "calc_and_store_bond_coupon_yield()"
-        /// that is called in many operations, changing Investors BondUnitsPackage-s (like buy/sell BUs).
-        /// Have the complexity O(N), where N - amount of BondUnitsPackage-s
+        /// Proposes new terms of BANKRUPT bond: extended maturity date, new interest rate and
+        /// haircut of par value. Bondholders vote for the proposal with their Bond Units
+        /// (see bond_restructuring_vote()). Bond can have only one restructuring proposal,
+        /// expired proposal is replaced. While voting is open, Bond Units cannot be bought or sold
         /// </pre>
-        #[weight = <T as Config>::WeightInfo::bond_accrue_coupon_yield()]
-        fn bond_accrue_coupon_yield(origin, bond: BondId) -> DispatchResultWithPostInfo {
-            let _ = ensure_signed(origin)?;
-
-            Self::with_bond(&bond, |mut item|->DispatchResultWithPostInfo {
-                let now = Timestamp::<T>::get();
-                let processed: u64 = Self::calc_and_store_bond_coupon_yield(&bond, &mut item, now) as u64;
-                Ok(Some( T::DbWeight::get().reads_writes(processed+2, processed+1) ).into())
-            })
+        #[weight = <T as Config>::WeightInfo::bond_restructuring_propose()]
+        fn bond_restructuring_propose(origin, bond: BondId, terms: BondRestructuringTermsStruct, #[compact] deadline: T::Moment) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+            ensure!(BondRegistry::<T>::contains_key(&bond), Error::<T>::BondNotFound);
+            let item = BondRegistry::<T>::get(&bond);
+            ensure!(item.issuer == caller || item.manager == caller, Error::<T>::BondAccessDenied);
+            ensure!(item.state == BondState::BANKRUPT, Error::<T>::BondStateNotPermitAction);
+            ensure!(
+                BondRestructuring::<T>::get(&bond).map_or(true, |proposal| proposal.is_expired(now)),
+                Error::<T>::RestructuringInProgress
+            );
+            ensure!(deadline > now, Error::<T>::RestructuringParamIncorrect);
+            ensure!(terms.haircut_bps < bond::BASIS_POINTS, Error::<T>::RestructuringParamIncorrect);
+            ensure!(
+                item.inner.bond_duration.saturating_add(terms.duration_extension) <= MAX_BOND_DURATION,
+                Error::<T>::RestructuringParamIncorrect
+            );
+            let mut inner = item.inner;
+            terms.apply(&mut inner);
+            ensure!(inner.is_valid(T::TimeStep::get()), Error::<T>::RestructuringParamIncorrect);
+
+            BondRestructuringVote::<T>::remove_prefix(&bond);
+            BondRestructuring::<T>::insert(&bond, BondRestructuringStructOf::<T> {
+                proposer: caller.clone(),
+                terms,
+                deadline,
+                votes_for: 0,
+                votes_against: 0,
+            });
+            Self::deposit_event(RawEvent::BondRestructuringProposed(caller, bond));
+            Ok(())
         }
 
         /// <pre>
-        /// Method: bond_revoke(origin, bond: BondId)
+        /// Method: bond_restructuring_vote(origin, bond: BondId, approve: bool)
         /// Arguments: origin: AccountId - transaction caller
         ///            bond: BondId - bond identifier
-        /// Access: Bond Issuer or Manager, assigned to the bond
+        ///            approve: bool - vote for (true) or against (false) the proposal
+        /// Access: any bondholder
         ///
-        /// Totally removes bond from BondsRegistry before it was issued(in PREPARE state)
+        /// Votes for the restructuring proposal of the bond with all Bond Units of bondholder.
+        /// Every bondholder can vote only once. When more than half of issued Bond Units vote
+        /// for the proposal, coupon yield of passed periods is accrued, new terms are applied
+        /// to the bond and the bond moves back to ACTIVE state with new nonce.
+        /// When at least half of issued Bond Units vote against the proposal, it's rejected
         /// </pre>
-        #[weight = <T as Config>::WeightInfo::bond_revoke()]
-        fn bond_revoke(origin, bond: BondId) -> DispatchResult {
+        #[weight = <T as Config>::WeightInfo::bond_restructuring_vote(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS)]
+        fn bond_restructuring_vote(origin, bond: BondId, approve: bool) -> DispatchResult {
             let caller = ensure_signed(origin)?;
-            // Bond can be revoked only by Owner or by Manager assigned to the Bond
-            // Bond should be in Prepare state, so no bids can exist at this time
-            ensure!( BondRegistry::<T>::contains_key(&bond), Error::<T>::BondNotFound );
-            let item = BondRegistry::<T>::get(bond);
-            ensure!(item.issuer == caller || item.manager == caller, Error::<T>::BondAccessDenied);
-            ensure!(item.state == BondState::PREPARE, Error::<T>::BondStateNotPermitAction);
-            assert!( BondRegistry::<T>::contains_key(bond) );
-            BondRegistry::<T>::remove( &bond );
+            let now = Timestamp::<T>::get();
+            Self::with_bond(&bond, |mut item|{
+                ensure!(item.state == BondState::BANKRUPT, Error::<T>::BondStateNotPermitAction);
+                let mut proposal = BondRestructuring::<T>::get(&bond).ok_or(Error::<T>::RestructuringNotFound)?;
+                ensure!(!proposal.is_expired(now), Error::<T>::RestructuringObsolete);
+                ensure!(
+                    !BondRestructuringVote::<T>::contains_key(&bond, &caller),
+                    Error::<T>::RestructuringAlreadyVoted
+                );
+                let bond_units = BondUnitPackageRegistry::<T>::get(&bond, &caller)
+                    .iter()
+                    .map(|package| package.bond_units)
+                    .sum::<BondUnitAmount>();
+                ensure!(bond_units > 0, Error::<T>::BondAccessDenied);
 
-            Self::deposit_event(RawEvent::BondRevoked(caller, bond));
-            Ok(())
+                BondRestructuringVote::<T>::insert(&bond, &caller, approve);
+                if approve {
+                    proposal.votes_for += bond_units;
+                } else {
+                    proposal.votes_against += bond_units;
+                }
+                Self::deposit_event(RawEvent::BondRestructuringVoted(caller, bond, approve, bond_units));
+
+                if proposal.is_approved(item.issued_amount) {
+                    Self::restructure_bond(&bond, &mut item, &proposal.terms, now);
+                    BondRestructuring::<T>::remove(&bond);
+                    BondRestructuringVote::<T>::remove_prefix(&bond);
+                    Self::deposit_event(RawEvent::BondRestructured(bond, item.nonce));
+                } else if proposal.is_rejected(item.issued_amount) {
+                    BondRestructuring::<T>::remove(&bond);
+                    BondRestructuringVote::<T>::remove_prefix(&bond);
+                    Self::deposit_event(RawEvent::BondRestructuringRejected(bond));
+                } else {
+                    BondRestructuring::<T>::insert(&bond, proposal);
+                }
+                Ok(())
+            })
         }
 
         /// <pre>
-        /// Method: bond_withdraw_everusd(origin, bond: BondId, amount: EverUSDBalance)
+        /// Method: bond_restructuring_cancel(origin, bond: BondId)
         /// Arguments: origin: AccountId - transaction caller
         ///            bond: BondId - bond identifier
+        /// Access: proposer or bond Issuer. Any account, if the proposal is expired
+        /// or the bond is not BANKRUPT anymore
         ///
-        /// Access: Bond Issuer or any Investor
-        ///
-        /// This function is mainly used by Investors to recieve accrued coupon yield in any moment.
-        /// Also, this function is used by bond Issuer to withdraw EverUSD from `free bond balance`.
+        /// Removes the restructuring proposal of the bond with all votes
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_restructuring_cancel()]
+        fn bond_restructuring_cancel(origin, bond: BondId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+            let proposal = BondRestructuring::<T>::get(&bond).ok_or(Error::<T>::RestructuringNotFound)?;
+            let item = BondRegistry::<T>::get(&bond);
+            ensure!(
+                proposal.proposer == caller || item.issuer == caller
+                    || proposal.is_expired(now) || item.state != BondState::BANKRUPT,
+                Error::<T>::BondAccessDenied
+            );
+            BondRestructuring::<T>::remove(&bond);
+            BondRestructuringVote::<T>::remove_prefix(&bond);
+            Self::deposit_event(RawEvent::BondRestructuringCancelled(caller, bond));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: bond_proposal_create(origin, bond: BondId, action: BondProposalAction, deadline: Moment)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            action: BondProposalAction - action, performed when the proposal passes
+        ///            deadline: Moment - end of voting
+        /// Access: bond Issuer, Manager or bondholder, holding at least MIN_PROPOSAL_STAKE_BPS
+        /// of issued Bond Units
+        ///
+        /// Opens bondholder meeting proposal of ACTIVE bond. Bond Units of every bondholder are
+        /// recorded at the moment of opening and used as voting power (see bond_proposal_vote()),
+        /// so Bond Units traded during the voting cannot be voted twice.
+        /// Have the complexity O(N), where N - amount of bondholders, limited by MAX_BOND_HOLDERS
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_proposal_create(WEIGHT_BONDHOLDERS)]
+        fn bond_proposal_create(origin, bond: BondId, action: BondProposalAction<T::AccountId, T::Hash>, #[compact] deadline: T::Moment) -> DispatchResultWithPostInfo {
+            let caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+            ensure!(BondRegistry::<T>::contains_key(&bond), Error::<T>::BondNotFound);
+            let item = BondRegistry::<T>::get(&bond);
+            // shares of issued Bond Units, basis points
+            let stake = Self::bond_units_total(&bond, &caller) as u64 * bond::BASIS_POINTS as u64;
+            let min_stake = item.issued_amount as u64 * MIN_PROPOSAL_STAKE_BPS as u64;
+            ensure!(
+                item.issuer == caller || item.manager == caller || (stake > 0 && stake >= min_stake),
+                Error::<T>::BondAccessDenied
+            );
+            ensure!(item.state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
+            ensure!(deadline > now, Error::<T>::ProposalParamIncorrect);
+            Self::ensure_bond_proposal_action(&bond, &item, &action)?;
+            ensure!(
+                BondProposal::<T>::iter_prefix_values(&bond).count() < MAX_BOND_PROPOSALS,
+                Error::<T>::ProposalLimitExceeded
+            );
+
+            let id = BondProposalCount::mutate(&bond, |count| {
+                let id = *count;
+                *count += 1;
+                id
+            });
+            let mut snapshot_units: BondUnitAmount = 0;
+            let mut processed: u32 = 0;
+            for (bondholder, packages) in BondUnitPackageRegistry::<T>::iter_prefix(&bond) {
+                let bond_units = packages.iter().map(|package| package.bond_units).sum::<BondUnitAmount>();
+                if bond_units > 0 {
+                    BondProposalSnapshot::<T>::insert((bond, id), &bondholder, bond_units);
+                    snapshot_units += bond_units;
+                }
+                processed += 1;
+            }
+            BondProposal::<T>::insert(&bond, id, BondProposalStructOf::<T> {
+                proposer: caller.clone(),
+                action,
+                deadline,
+                snapshot_units,
+                votes_for: 0,
+                votes_against: 0,
+            });
+            Self::deposit_event(RawEvent::BondProposalCreated(caller, bond, id));
+            Ok(Some( <T as Config>::WeightInfo::bond_proposal_create(processed) ).into())
+        }
+
+        /// <pre>
+        /// Method: bond_proposal_vote(origin, bond: BondId, proposal: BondProposalId, approve: bool)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            proposal: BondProposalId - proposal identifier
+        ///            approve: bool - vote for (true) or against (false) the proposal
+        /// Access: bondholder at the moment of opening the proposal
+        ///
+        /// Votes for the proposal with all Bond Units, owned by bondholder when the proposal
+        /// was opened. Every bondholder can vote only once. When more than half of snapshot
+        /// Bond Units vote for the proposal, its action is performed without Master.
+        /// When at least half of snapshot Bond Units vote against the proposal, it's rejected
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_proposal_vote(WEIGHT_BONDHOLDERS)]
+        fn bond_proposal_vote(origin, bond: BondId, #[compact] proposal: BondProposalId, approve: bool) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+            Self::with_bond(&bond, |item|{
+                ensure!(item.state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
+                let mut body = BondProposal::<T>::get(&bond, proposal).ok_or(Error::<T>::ProposalNotFound)?;
+                ensure!(!body.is_expired(now), Error::<T>::ProposalObsolete);
+                ensure!(
+                    !BondProposalVote::<T>::contains_key((bond, proposal), &caller),
+                    Error::<T>::ProposalAlreadyVoted
+                );
+                let bond_units = BondProposalSnapshot::<T>::get((bond, proposal), &caller);
+                ensure!(bond_units > 0, Error::<T>::BondAccessDenied);
+
+                if approve {
+                    body.votes_for += bond_units;
+                } else {
+                    body.votes_against += bond_units;
+                }
+                let passed = body.is_passed();
+                if passed {
+                    Self::execute_bond_proposal(&bond, item, &body.action)?;
+                }
+                BondProposalVote::<T>::insert((bond, proposal), &caller, approve);
+                Self::deposit_event(RawEvent::BondProposalVoted(caller, bond, proposal, approve, bond_units));
+
+                if passed {
+                    Self::remove_bond_proposal(&bond, proposal);
+                    Self::deposit_event(RawEvent::BondProposalPassed(bond, proposal));
+                } else if body.is_rejected() {
+                    Self::remove_bond_proposal(&bond, proposal);
+                    Self::deposit_event(RawEvent::BondProposalRejected(bond, proposal));
+                } else {
+                    BondProposal::<T>::insert(&bond, proposal, body);
+                }
+                Ok(())
+            })
+        }
+
+        /// <pre>
+        /// Method: bond_proposal_cancel(origin, bond: BondId, proposal: BondProposalId)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///            proposal: BondProposalId - proposal identifier
+        /// Access: proposer. Any account, if the proposal is expired
+        /// or the bond is not ACTIVE anymore
+        ///
+        /// Removes the proposal of the bond with snapshot and all votes
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_proposal_cancel(WEIGHT_BONDHOLDERS)]
+        fn bond_proposal_cancel(origin, bond: BondId, #[compact] proposal: BondProposalId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let now = Timestamp::<T>::get();
+            let body = BondProposal::<T>::get(&bond, proposal).ok_or(Error::<T>::ProposalNotFound)?;
+            let item = BondRegistry::<T>::get(&bond);
+            ensure!(
+                body.proposer == caller || body.is_expired(now) || item.state != BondState::ACTIVE,
+                Error::<T>::BondAccessDenied
+            );
+            Self::remove_bond_proposal(&bond, proposal);
+            Self::deposit_event(RawEvent::BondProposalCancelled(caller, bond, proposal));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: bond_accrue_coupon_yield(origin, bond: BondId)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        /// Access: any account
+        ///
+        /// Calculates total bond coupon yield(EverUSD) and stores it in "bond_credit"
+        /// by calculating effective interest rates for each passed payment_period.
+        /// This function is a call to "lazy" function "calc_and_store_bond_coupon_yield()"
+        /// that is called in many operations, changing Investors BondUnitsPackage-s (like buy/sell BUs).
+        /// Have the complexity O(N), where N - amount of BondUnitsPackage-s
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_accrue_coupon_yield(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS)]
+        fn bond_accrue_coupon_yield(origin, bond: BondId) -> DispatchResultWithPostInfo {
+            let _ = ensure_signed(origin)?;
+
+            Self::with_bond(&bond, |mut item|->DispatchResultWithPostInfo {
+                let now = Timestamp::<T>::get();
+                let processed = Self::calc_and_store_bond_coupon_yield(&bond, &mut item, now) as u32;
+                Ok(Some( <T as Config>::WeightInfo::bond_accrue_coupon_yield(WEIGHT_BONDHOLDERS, processed) ).into())
+            })
+        }
+
+        /// <pre>
+        /// Method: bond_revoke(origin, bond: BondId)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        /// Access: Bond Issuer or Manager, assigned to the bond
+        ///
+        /// Totally removes bond from BondsRegistry before it was issued(in PREPARE state)
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_revoke()]
+        fn bond_revoke(origin, bond: BondId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            // Bond can be revoked only by Owner or by Manager assigned to the Bond
+            // Bond should be in Prepare state, so no bids can exist at this time
+            ensure!( BondRegistry::<T>::contains_key(&bond), Error::<T>::BondNotFound );
+            let item = BondRegistry::<T>::get(bond);
+            ensure!(item.issuer == caller || item.manager == caller, Error::<T>::BondAccessDenied);
+            ensure!(item.state == BondState::PREPARE, Error::<T>::BondStateNotPermitAction);
+            assert!( BondRegistry::<T>::contains_key(bond) );
+            BondRegistry::<T>::remove( &bond );
+            BondDocument::<T>::remove_prefix(&bond);
+            BondDocumentCount::remove(&bond);
+            BondCreditRating::<T>::remove(&bond);
+            BondAllowList::<T>::remove_prefix(&bond);
+            BondUnitLockup::<T>::remove_prefix(&bond);
+            BondTradingHalt::remove(&bond);
+
+            Self::deposit_event(RawEvent::BondRevoked(caller, bond));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: bond_withdraw_everusd(origin, bond: BondId, amount: EverUSDBalance)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            bond: BondId - bond identifier
+        ///
+        /// Access: Bond Issuer or any Investor
+        ///
+        /// This function is mainly used by Investors to recieve accrued coupon yield in any moment.
+        /// Also, this function is used by bond Issuer to withdraw EverUSD from `free bond balance`.
         /// Available only in ACTIVE, BANKRUPT of FINISHED states.
         /// If caller is Issuer of this bond, "get_free_balance()" amount of EverUSD tokens are transfered
         /// to bond.issuer address. If caller is the Investor of bond, then, in FINISHED state he
@@ -1392,7 +2707,7 @@ decl_module! {
         /// </pre>
         //  @TODO add parameter beneficiary:AccountId  who will receive coupon yield
         //  @TODO consider separate functions for Issuer and Investor
-        #[weight = <T as Config>::WeightInfo::bond_withdraw_everusd()]
+        #[weight = <T as Config>::WeightInfo::bond_withdraw_everusd(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS)]
         fn bond_withdraw_everusd(origin, bond: BondId) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             Self::with_bond(&bond, |mut item|{
@@ -1418,7 +2733,7 @@ decl_module! {
                     // set bankrupt state if bond fund cannot pay off
                     if item.state == BondState::ACTIVE && item.get_debt()>0 && !Self::is_interest_pay_period(&item, now){
                         item.state = BondState::BANKRUPT;
-                        Self::deposit_event(RawEvent::BondBankrupted(caller.clone(), bond, item.bond_credit, item.bond_debit ));
+                        Self::deposit_event(RawEvent::BondBankrupted(Some(caller.clone()), bond, item.bond_credit, item.bond_debit ));
                     }
 
                     Self::request_coupon_yield(&bond, &mut item, &caller)
@@ -1436,16 +2751,16 @@ decl_module! {
         /// Arguments: origin: AccountId - transaction caller
         ///            bond: BondId - bond identifier
         ///            amount: EverUSDBalance - the number of EverUSD  deposited to bond fund
-        /// Access: Bond issuer
+        /// Access: Bond issuer or Paying Agent
         ///
-        /// Transfers `amount` of EverUSD tokens from bond Issuer's balance to the bond fund
+        /// Transfers `amount` of EverUSD tokens from caller's balance to the bond fund
         /// to pay coupon yield and principal debt to Investors. Bond must be in ACTIVE or BANKRUPT
         /// state. Transfered EverUSD amount is added to bond_debit, and will be distributed between
         /// Investors, according to their ownership of Bond Units.
         /// "Lazy" call for function "calc_and_store_bond_coupon_yield()", that calculates and stores
         /// coupon yield amounts for each payment_period, where it's possible
         /// </pre>
-        #[weight = <T as Config>::WeightInfo::bond_deposit_everusd()]
+        #[weight = <T as Config>::WeightInfo::bond_deposit_everusd(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS)]
         fn bond_deposit_everusd(origin, bond: BondId,#[compact]  amount: EverUSDBalance) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             Self::with_bond(&bond, |mut item|{
@@ -1453,7 +2768,11 @@ decl_module! {
                     matches!(item.state , BondState::ACTIVE | BondState::BANKRUPT),
                     Error::<T>::BondStateNotPermitAction
                 );
-                ensure!(item.issuer == caller, Error::<T>::BondAccessDenied);
+                ensure!(
+                    item.issuer == caller
+                        || Self::account_has_role(&caller, PAYING_AGENT_ROLE_MASK, Some(&bond)),
+                    Error::<T>::BondAccessDenied
+                );
 
                 Self::balance_sub(&caller, amount)?;
 
@@ -1484,16 +2803,20 @@ decl_module! {
         ///   - "deadline": moment, after that lot cannot be sold
         ///   - "new_bondholder": (optional) target buyer(to restrict sale of this lot to given buyer)
         /// Function checks, that seller have BondUnitsPackage-s, containing enough BUs for lot creation,
-        /// expiration date. Then, creates new lot in BondUnitPackageLot registry. Later, buyers can
-        /// choose this lot and buy it.
+        /// expiration date. Then, creates new lot in BondUnitPackageLot registry, assigning
+        /// new lot id (value of "id" field of the argument is ignored). Later, buyers can
+        /// choose this lot by id and buy it whole or partially.
         /// Also, function purges expired lots for this bond and seller from BondUnitPackageLot storage.
+        /// Lot cannot be created while trading of the bond is halted or Bond Units of seller are locked up.
+        /// Seller can have at most WEIGHT_LOTS not expired lots in the bond.
         /// </pre>
-        #[weight = <T as Config>::WeightInfo::bond_unit_lot_bid()]
-        fn bond_unit_lot_bid(origin, bond: BondId, lot: BondUnitSaleLotStructOf<T>) -> DispatchResult{
+        #[weight = <T as Config>::WeightInfo::bond_unit_lot_bid(WEIGHT_LOTS)]
+        fn bond_unit_lot_bid(origin, bond: BondId, mut lot: BondUnitSaleLotStructOf<T>) -> DispatchResultWithPostInfo{
             let caller = ensure_signed(origin)?;
             let now = Timestamp::<T>::get();
             // @TODO - maybe restrict this operation only to Investors?
             ensure!(!lot.is_expired(now), Error::<T>::LotParamIncorrect);
+            Self::ensure_bond_units_transferable(&bond, &BondRegistry::<T>::get(&bond), &caller, now)?;
 
             let packages = BondUnitPackageRegistry::<T>::get(&bond, &caller);
             // how many bond units does the caller have
@@ -1505,84 +2828,325 @@ decl_module! {
 
             // all lots of the caller.
             let mut lots: Vec<_> = BondUnitPackageLot::<T>::get(&bond, &caller);
+            let lots_count = lots.len() as u32;
             // purge expired lots
             lots.retain(|lot| !lot.is_expired(now) );
+            ensure!(lots.len() < WEIGHT_LOTS as usize, Error::<T>::LotLimitExceeded);
 
             let total_bond_units_inlot: BondUnitAmount = lots.iter().map(|lot| lot.bond_units).sum();
             // prevent new bid if the caller doesn't have enough bond units
-            ensure!(total_bond_units>= total_bond_units_inlot+lot.bond_units, Error::<T>::BalanceOverdraft);
+            ensure!(
+                total_bond_units >= total_bond_units_inlot + lot.bond_units + Self::bond_units_in_orders(&bond, &caller),
+                Error::<T>::BalanceOverdraft
+            );
 
+            lot.id = BondUnitSaleLotCount::get(&bond);
             lots.push(
                 lot.clone()
             );
             // save  lots
             BondUnitPackageLot::<T>::insert(&bond, &caller, lots);
+            BondUnitSaleLotCount::insert(&bond, lot.id + 1);
+            Self::schedule_expiry(lot.deadline, ExpiryItem::SaleLots(bond, caller.clone()));
             Self::deposit_event(RawEvent::BondSaleLotBid(caller, bond, lot));
-            Ok(())
+            Ok(Some(<T as Config>::WeightInfo::bond_unit_lot_bid(lots_count)).into())
         }
 
         /// <pre>
-        /// Method: bond_unit_lot_settle(origin, bond: BondId,bondholder: AccountId, lot: BondUnitSaleLotStruct)
+        /// Method: bond_unit_lot_settle(origin, bond: BondId,bondholder: AccountId, lot: BondUnitSaleLotId, bond_units: BondUnitAmount)
         /// Arguments: origin: AccountId - bond unit bondholder
         ///            bond: BondId - bond identifier
         ///            bondholder: Current bondholder of of bond
-        ///            lot: BondUnitSaleLotStruct - lot data
+        ///            lot: BondUnitSaleLotId - lot identifier
+        ///            bond_units: BondUnitAmount - amount of Bond Units to buy from the lot
         /// Access: any account with Investor role
         ///
-        /// Buy the lot created by bond_unit_lot_bid() call. Lot should not be expired.
+        /// Buy the lot created by bond_unit_lot_bid() call, whole or partially. Lot should not be expired.
         /// If "new_bondholder" field of lot is set, only given account can buy this lot.
-        /// Buyer should have enough EverUSD to buy the lot. When lot is sold,
+        /// Part of the lot is sold at pro-rata price (rounded down), the remainder stays in the lot
+        /// with reduced "bond_units" and "amount". Buyer should have enough EverUSD to buy
+        /// the part of the lot. When lot is sold out, it's removed, and
         /// expired lots (fixed amount) are purged from BondUnitPackageLot registry.
         ///
         /// Before transfer of EverUSD, function caluclates and stores bond coupon yield for bondholder(seller)
         /// and caller(buyer), because this deal changes BondUnitsPackages of buyer and seller
-        /// (buyer receives "newer" BondUnitsPackage, that buyer owned)
+        /// (buyer receives "newer" BondUnitsPackage, that buyer owned).
+        /// Buyer must comply with eligibility rules of the bond. Lot cannot be settled while
+        /// trading of the bond is halted or Bond Units of seller are locked up
         /// </pre>
-        #[weight = <T as Config>::WeightInfo::bond_unit_lot_settle()]
-        fn bond_unit_lot_settle(origin, bond: BondId, bondholder: T::AccountId, lot: BondUnitSaleLotStructOf<T>)->DispatchResult{
+        #[weight = <T as Config>::WeightInfo::bond_unit_lot_settle(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS, WEIGHT_LOTS)]
+        fn bond_unit_lot_settle(origin, bond: BondId, bondholder: T::AccountId, #[compact] lot: BondUnitSaleLotId, #[compact] bond_units: BondUnitAmount)->DispatchResult{
             let caller = ensure_signed(origin)?;
-            ensure!(Self::account_is_investor(&caller), Error::<T>::AccountNotAuthorized);
+            ensure!(Self::account_has_role(&caller, INVESTOR_ROLE_MASK, Some(&bond)), Error::<T>::AccountNotAuthorized);
             let now = Timestamp::<T>::get();
-            // prevent expired lots sales
-            ensure!(!lot.is_expired( now ), Error::<T>::LotObsolete);
-
-            ensure!(lot.new_bondholder == Default::default() || lot.new_bondholder == caller, Error::<T>::LotNotFound);
-            let balance = Self::balance_everusd(&caller);
-            // ensure caller has enough tokens on its balance
-            ensure!(lot.amount <= balance , Error::<T>::BalanceOverdraft);
 
             BondUnitPackageLot::<T>::try_mutate(&bond, &bondholder, |lots|->DispatchResult{
-                if let Some(index) = lots.iter().position(|item| item==&lot ){
-                     lots.remove( index );
-                     if !lots.is_empty() {
-                        // purge expired lots
-                        lots.retain( |item| !item.is_expired( now ) );
-                     }
-                     // @TODO optimize out access to balances
-                     BondRegistry::<T>::mutate(bond, |mut item|{
-                        Self::calc_and_store_bond_coupon_yield(&bond, &mut item, now);
-                        Self::request_coupon_yield(&bond, &mut item, &bondholder);
-                        Self::request_coupon_yield(&bond, &mut item, &caller);
-                     });
-
-                     let mut from_packages = BondUnitPackageRegistry::<T>::get(&bond, &bondholder);
-                     let mut to_packages = BondUnitPackageRegistry::<T>::get(&bond, &caller);
-                     // transfer lot.bond_units from bondholder to caller
-                     transfer_bond_units::<T>(&mut from_packages, &mut to_packages, lot.bond_units)?;
-                     // store new packages
-                     BondUnitPackageRegistry::<T>::insert(&bond, &bondholder, from_packages);
-                     BondUnitPackageRegistry::<T>::insert(&bond, &caller, to_packages);
-
-                     // pay off deal
-                     Self::balance_sub(&caller, lot.amount)?;
-                     Self::balance_add(&bondholder, lot.amount)?;
-                     Self::deposit_event(RawEvent::BondSaleLotSettle(caller, bondholder.clone(), bond, lot));
-                     Ok(())
-                }else{
-                    Err(Error::<T>::BondParamIncorrect.into())
+                let index = lots.iter().position(|item| item.id == lot).ok_or(Error::<T>::LotNotFound)?;
+                let item = &lots[index];
+                // prevent expired lots sales
+                ensure!(!item.is_expired( now ), Error::<T>::LotObsolete);
+                ensure!(item.new_bondholder == Default::default() || item.new_bondholder == caller, Error::<T>::LotNotFound);
+                ensure!(bond_units > 0 && bond_units <= item.bond_units, Error::<T>::LotParamIncorrect);
+
+                // sold part of the lot
+                let part = BondUnitSaleLotStruct {
+                    bond_units,
+                    amount: item.part_amount(bond_units),
+                    ..item.clone()
+                };
+                // ensure caller has enough tokens on its balance
+                ensure!(part.amount <= Self::balance_everusd(&caller), Error::<T>::BalanceOverdraft);
+
+                if bond_units == item.bond_units {
+                    lots.remove( index );
+                } else {
+                    lots[index].bond_units -= part.bond_units;
+                    lots[index].amount -= part.amount;
+                }
+                if !lots.is_empty() {
+                    // purge expired lots
+                    lots.retain( |item| !item.is_expired( now ) );
                 }
+                // transfer part.bond_units from bondholder to caller and pay off deal
+                Self::settle_bond_units_deal(&bond, &bondholder, &caller, part.bond_units, part.amount, now)?;
+                Self::balance_sub(&caller, part.amount)?;
+                Self::deposit_event(RawEvent::BondSaleLotSettle(caller, bondholder.clone(), bond, part));
+                Ok(())
             })
         }
+
+        /// <pre>
+        /// Method: bond_unit_transfer(origin, bond: BondId, to: AccountId, bond_units: BondUnitAmount)
+        /// Arguments: origin: AccountId - bondholder
+        ///            bond: BondId - bond identifier
+        ///            to: AccountId - receiver of Bond Units, account with Investor role
+        ///            bond_units: BondUnitAmount - amount of Bond Units to transfer
+        /// Access: any account, holding Bond Units
+        ///
+        /// Transfers Bond Units to another account without payment (custodial rebalancing,
+        /// inheritance, moves between fund and subfund). Bond Units, locked by sale lots and
+        /// sell orders, cannot be transferred. Like bond_unit_lot_settle(), function calculates and
+        /// stores coupon yield for sender and receiver before the transfer. Receiver must comply with
+        /// eligibility rules of the bond, lock-ups and trading halt are applied to sender.
+        /// Available only for ACTIVE bonds.
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_unit_transfer(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS)]
+        fn bond_unit_transfer(origin, bond: BondId, to: T::AccountId, #[compact] bond_units: BondUnitAmount) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(caller != to && bond_units > 0, Error::<T>::InvalidAction);
+            ensure!(Self::account_has_role(&to, INVESTOR_ROLE_MASK, Some(&bond)), Error::<T>::AccountNotAuthorized);
+            ensure!(BondRegistry::<T>::contains_key(&bond), Error::<T>::BondNotFound);
+            ensure!(BondRegistry::<T>::get(&bond).state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
+            let now = Timestamp::<T>::get();
+            ensure!(Self::bond_units_free(&bond, &caller, now) >= bond_units, Error::<T>::BalanceOverdraft);
+
+            Self::settle_bond_units_deal(&bond, &caller, &to, bond_units, 0, now)?;
+            Self::deposit_event(RawEvent::BondUnitTransfer(caller, to, bond, bond_units));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: bond_order_place(origin, bond: BondId, side: BondOrderSide, price: EverUSDBalance, bond_units: BondUnitAmount, deadline: Moment)
+        /// Arguments: origin: AccountId - order owner
+        ///            bond: BondId - bond identifier
+        ///            side: BondOrderSide - BUY or SELL
+        ///            price: EverUSDBalance - limit price of single bond unit
+        ///            bond_units: BondUnitAmount - amount of bond units to buy or sell
+        ///            deadline: Moment - moment, after that order is removed from order book
+        /// Access: any account with Investor role
+        ///
+        /// Places limit order in bond order book. Buy order locks price * bond_units EverUSD of the
+        /// owner, sell order locks bond units (locked units cannot be sold with sale lots).
+        /// Order is matched with orders of the opposite side by price-time priority: orders with
+        /// better price first, then older orders. Deal is made at the price of resting order, buyer
+        /// gets back the difference of locked EverUSD. Orders can be filled partially, not filled part
+        /// of the order stays in order book until it is filled, cancelled or expired.
+        /// Orders of the same owner are not matched with each other.
+        /// Each deal settles coupon yield of seller and buyer like bond_unit_lot_settle() does,
+        /// buyer must comply with eligibility rules of the bond. Orders cannot be placed and matched
+        /// while trading of the bond is halted, sell orders cannot be placed during lock-up.
+        /// Expired orders of the bond are purged and unlocked.
+        /// Available only for ACTIVE bonds.
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_order_place(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS, MAX_BOND_ORDERS as u32)]
+        fn bond_order_place(origin, bond: BondId, side: BondOrderSide, #[compact] price: EverUSDBalance, #[compact] bond_units: BondUnitAmount, deadline: T::Moment) -> DispatchResultWithPostInfo {
+            let caller = ensure_signed(origin)?;
+            ensure!(Self::account_has_role(&caller, INVESTOR_ROLE_MASK, Some(&bond)), Error::<T>::AccountNotAuthorized);
+            let now = Timestamp::<T>::get();
+            ensure!(price > 0 && bond_units > 0 && deadline >= now, Error::<T>::OrderParamIncorrect);
+            ensure!(price.checked_mul(bond_units as EverUSDBalance).is_some(), Error::<T>::OrderParamIncorrect);
+            ensure!(BondRegistry::<T>::contains_key(&bond), Error::<T>::BondNotFound);
+            let item = BondRegistry::<T>::get(&bond);
+            ensure!(item.state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
+            if side == BondOrderSide::BUY {
+                ensure!(!BondTradingHalt::get(&bond), Error::<T>::BondTradingHalted);
+                let holding = Self::bond_units_total(&bond, &caller).saturating_add(bond_units);
+                Self::ensure_bond_eligible(&bond, &item, &caller, holding, None, now)?;
+            } else {
+                Self::ensure_bond_units_transferable(&bond, &item, &caller, now)?;
+            }
+
+            let order = BondOrderStruct {
+                id: BondOrderCount::get(&bond),
+                owner: caller.clone(),
+                price,
+                bond_units,
+                deadline,
+            };
+            let matched = Self::order_place(&bond, side, order, now)?;
+            Ok(Some(<T as Config>::WeightInfo::bond_order_place(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS, matched)).into())
+        }
+
+        /// <pre>
+        /// Method: bond_order_cancel(origin, bond: BondId, order: BondOrderId)
+        /// Arguments: origin: AccountId - order owner
+        ///            bond: BondId - bond identifier
+        ///            order: BondOrderId - order identifier
+        /// Access: order owner
+        ///
+        /// Removes not filled part of the order from order book, unlocking EverUSD or bond units.
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_order_cancel()]
+        fn bond_order_cancel(origin, bond: BondId, #[compact] order: BondOrderId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            for &side in &[BondOrderSide::BUY, BondOrderSide::SELL] {
+                let mut book = BondOrderBook::<T>::get(&bond, side);
+                if let Some(index) = book.iter().position(|item| item.id == order) {
+                    ensure!(book[index].owner == caller, Error::<T>::BondAccessDenied);
+                    let item = book.remove(index);
+                    Self::order_unlock(side, &item)?;
+                    BondOrderBook::<T>::insert(&bond, side, book);
+
+                    Self::deposit_event(RawEvent::BondOrderCancelled(caller, bond, order));
+                    return Ok(());
+                }
+            }
+            Err(Error::<T>::OrderNotFound.into())
+        }
+
+        /// <pre>
+        /// Method: bond_swap_offer(origin, give_bond: BondId, give_units: BondUnitAmount, amount: EverUSDBalance,
+        ///             take_bond: BondId, take_units: BondUnitAmount, counterparty: AccountId, deadline: Moment)
+        /// Arguments: origin: AccountId - swap maker
+        ///            give_bond: BondId - bond, which Bond Units are given by maker
+        ///            give_units: BondUnitAmount - amount of given Bond Units
+        ///            amount: EverUSDBalance - EverUSD, paid by maker in addition to given Bond Units (can be 0)
+        ///            take_bond: BondId - bond, which Bond Units are taken from counterparty
+        ///            take_units: BondUnitAmount - amount of taken Bond Units
+        ///            counterparty: AccountId - (optional, can be empty) the only account, that can accept the swap
+        ///            deadline: Moment - moment, after that swap cannot be accepted
+        /// Access: any account with Investor role, holding Bond Units of give_bond
+        ///
+        /// Offers delivery-versus-payment swap of Bond Units between two ACTIVE bonds, f.e. to rotate
+        /// from one bond to another in a single deal. EverUSD amount is locked until the swap is settled
+        /// or cancelled. Given Bond Units are not locked, maker must have enough free Bond Units
+        /// (not offered in sale lots and sell orders) when the swap is offered and accepted.
+        /// Swap cannot be offered while trading of give_bond is halted or Bond Units of maker are locked up.
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_swap_offer()]
+        fn bond_swap_offer(
+            origin,
+            give_bond: BondId,
+            #[compact] give_units: BondUnitAmount,
+            #[compact] amount: EverUSDBalance,
+            take_bond: BondId,
+            #[compact] take_units: BondUnitAmount,
+            counterparty: T::AccountId,
+            deadline: T::Moment
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(Self::account_has_role(&caller, INVESTOR_ROLE_MASK, Some(&take_bond)), Error::<T>::AccountNotAuthorized);
+            let now = Timestamp::<T>::get();
+            ensure!(
+                give_bond != take_bond && give_units > 0 && take_units > 0 && caller != counterparty && deadline >= now,
+                Error::<T>::SwapParamIncorrect
+            );
+            for bond in &[give_bond, take_bond] {
+                ensure!(BondRegistry::<T>::contains_key(bond), Error::<T>::BondNotFound);
+                ensure!(BondRegistry::<T>::get(bond).state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
+            }
+            Self::ensure_bond_units_transferable(&give_bond, &BondRegistry::<T>::get(&give_bond), &caller, now)?;
+            ensure!(Self::bond_units_free(&give_bond, &caller, now) >= give_units, Error::<T>::BalanceOverdraft);
+            Self::balance_sub(&caller, amount)?;
+
+            let swap = BondSwapStruct {
+                id: BondSwapCount::get(),
+                maker: caller.clone(),
+                counterparty,
+                give_bond,
+                give_units,
+                amount,
+                take_bond,
+                take_units,
+                deadline,
+            };
+            BondSwapCount::put(swap.id + 1);
+            BondSwap::<T>::insert(swap.id, &swap);
+            Self::schedule_expiry(deadline, ExpiryItem::Swap(swap.id));
+            Self::deposit_event(RawEvent::BondSwapOffered(caller, swap.id));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: bond_swap_accept(origin, swap: BondSwapId)
+        /// Arguments: origin: AccountId - counterparty
+        ///            swap: BondSwapId - swap identifier
+        /// Access: any account with Investor role, holding Bond Units of take_bond.
+        ///         If "counterparty" field of the swap is set, only given account
+        ///
+        /// Accepts the swap, offered by bond_swap_offer(), and settles both legs at once:
+        /// give_units of give_bond are transferred from maker to caller, take_units of take_bond
+        /// and locked EverUSD are transferred from caller to maker and caller respectively.
+        /// Like bond_unit_lot_settle(), each leg calculates and stores coupon yield of both parties.
+        /// Receivers must comply with eligibility rules of the bonds, lock-ups and trading halts are
+        /// applied to senders. If any leg fails, none of them is settled.
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_swap_accept(WEIGHT_BONDHOLDERS, WEIGHT_PERIODS)]
+        fn bond_swap_accept(origin, #[compact] swap: BondSwapId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let item = BondSwap::<T>::get(swap).ok_or(Error::<T>::SwapNotFound)?;
+            ensure!(item.counterparty == Default::default() || item.counterparty == caller, Error::<T>::SwapNotFound);
+            ensure!(item.maker != caller, Error::<T>::InvalidAction);
+            ensure!(Self::account_has_role(&caller, INVESTOR_ROLE_MASK, Some(&item.give_bond)), Error::<T>::AccountNotAuthorized);
+            let now = Timestamp::<T>::get();
+            ensure!(!item.is_expired(now), Error::<T>::SwapObsolete);
+            for bond in &[item.give_bond, item.take_bond] {
+                ensure!(BondRegistry::<T>::get(bond).state == BondState::ACTIVE, Error::<T>::BondStateNotPermitAction);
+            }
+            ensure!(
+                Self::bond_units_free(&item.give_bond, &item.maker, now) >= item.give_units
+                    && Self::bond_units_free(&item.take_bond, &caller, now) >= item.take_units,
+                Error::<T>::BalanceOverdraft
+            );
+
+            Self::swap_settle(&item, &caller, now)?;
+            BondSwap::<T>::remove(swap);
+            Self::deposit_event(RawEvent::BondSwapSettled(caller, item.maker, swap));
+            Ok(())
+        }
+
+        /// <pre>
+        /// Method: bond_swap_cancel(origin, swap: BondSwapId)
+        /// Arguments: origin: AccountId - transaction caller
+        ///            swap: BondSwapId - swap identifier
+        /// Access: swap maker, any account for expired swap
+        ///
+        /// Removes the swap, returning locked EverUSD to maker
+        /// </pre>
+        #[weight = <T as Config>::WeightInfo::bond_swap_cancel()]
+        fn bond_swap_cancel(origin, #[compact] swap: BondSwapId) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            let item = BondSwap::<T>::get(swap).ok_or(Error::<T>::SwapNotFound)?;
+            ensure!(
+                item.maker == caller || item.is_expired(Timestamp::<T>::get()),
+                Error::<T>::BondAccessDenied
+            );
+            Self::balance_add(&item.maker, item.amount)?;
+            BondSwap::<T>::remove(swap);
+            Self::deposit_event(RawEvent::BondSwapCancelled(caller, swap));
+            Ok(())
+        }
     }
 }
 
@@ -1593,6 +3157,21 @@ impl<T: Config> Module<T> {
         T::OnAddAccount::on_add_account(account, &data);
     }
 
+    /// <pre>
+    /// Method: account_has_role(acc: &T::AccountId, role: RoleMask, bond: Option<&BondId>) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///            role: RoleMask - single role bit
+    ///            bond: Option<&BondId> - bond, where the role is checked
+    ///
+    /// Checks if the acc has the role, and role grant (if any) is not expired.
+    /// Role, granted in specific bonds, is valid only in these bonds
+    /// </pre>
+    pub fn account_has_role(acc: &T::AccountId, role: RoleMask, bond: Option<&BondId>) -> bool {
+        AccountRegistry::<T>::get(acc).roles & role != 0
+            && AccountRoleGrant::<T>::get(acc, role)
+                .map_or(true, |grant| grant.is_valid(Timestamp::<T>::get(), bond))
+    }
+
     /// <pre>
     /// Method: account_is_master(acc: &T::AccountId) -> bool
     /// Arguments: acc: AccountId - checked account id
@@ -1600,7 +3179,46 @@ impl<T: Config> Module<T> {
     /// Checks if the acc has global Master role
     /// </pre>
     pub fn account_is_master(acc: &T::AccountId) -> bool {
-        AccountRegistry::<T>::get(acc).roles & MASTER_ROLE_MASK != 0
+        Self::account_has_role(acc, MASTER_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: ensure_master(origin: T::Origin) -> Result<Option<T::AccountId>, DispatchError>
+    /// Arguments: origin: Origin - transaction origin
+    ///
+    /// Checks that origin is T::MasterOrigin or signed by account with Master role.
+    /// Returns the caller account, or None for T::MasterOrigin
+    /// </pre>
+    fn ensure_master(origin: T::Origin) -> Result<Option<T::AccountId>, DispatchError> {
+        Self::ensure_master_or(origin, MASTER_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: ensure_master_or(origin: T::Origin, role: RoleMask, bond: Option<&BondId>) -> Result<Option<T::AccountId>, DispatchError>
+    /// Arguments: origin: Origin - transaction origin
+    ///            role: RoleMask - single role, allowed besides Master
+    ///            bond: Option<&BondId> - bond, where the role is checked
+    ///
+    /// Checks that origin is T::MasterOrigin or signed by account with Master role or given role.
+    /// Returns the caller account, or None for T::MasterOrigin. T::MasterOrigin has no account,
+    /// so checks of the caller are skipped for it, and its events have None as the caller
+    /// </pre>
+    fn ensure_master_or(
+        origin: T::Origin,
+        role: RoleMask,
+        bond: Option<&BondId>,
+    ) -> Result<Option<T::AccountId>, DispatchError> {
+        match T::MasterOrigin::try_origin(origin) {
+            Ok(_) => Ok(None),
+            Err(origin) => {
+                let caller = ensure_signed(origin)?;
+                ensure!(
+                    Self::account_is_master(&caller) || Self::account_has_role(&caller, role, bond),
+                    Error::<T>::AccountNotAuthorized
+                );
+                Ok(Some(caller))
+            }
+        }
     }
 
     /// <pre>
@@ -1610,7 +3228,7 @@ impl<T: Config> Module<T> {
     /// Checks if the acc has global Custodian role
     /// </pre>
     pub fn account_is_custodian(acc: &T::AccountId) -> bool {
-        AccountRegistry::<T>::get(acc).roles & CUSTODIAN_ROLE_MASK != 0
+        Self::account_has_role(acc, CUSTODIAN_ROLE_MASK, None)
     }
 
     /// <pre>
@@ -1620,116 +3238,576 @@ impl<T: Config> Module<T> {
     /// Checks if the acc has global Issuer role
     /// </pre>
     pub fn account_is_issuer(acc: &T::AccountId) -> bool {
-        AccountRegistry::<T>::get(acc).roles & ISSUER_ROLE_MASK != 0
+        Self::account_has_role(acc, ISSUER_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: account_is_investor(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc has global Investor role
+    /// </pre>
+    pub fn account_is_investor(acc: &T::AccountId) -> bool {
+        Self::account_has_role(acc, INVESTOR_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: account_is_auditor(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc has global Auditor role
+    /// </pre>
+    pub fn account_is_auditor(acc: &T::AccountId) -> bool {
+        Self::account_has_role(acc, AUDITOR_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: account_is_manager(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc has global Manager role
+    /// </pre>
+    pub fn account_is_manager(acc: &T::AccountId) -> bool {
+        Self::account_has_role(acc, MANAGER_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: account_is_impact_reporter(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc has global Impact Reporter role
+    /// </pre>
+    pub fn account_is_impact_reporter(acc: &T::AccountId) -> bool {
+        Self::account_has_role(acc, IMPACT_REPORTER_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: account_is_registrar(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc has global Registrar role
+    /// </pre>
+    pub fn account_is_registrar(acc: &T::AccountId) -> bool {
+        Self::account_has_role(acc, REGISTRAR_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: account_is_paying_agent(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc has global Paying Agent role
+    /// </pre>
+    pub fn account_is_paying_agent(acc: &T::AccountId) -> bool {
+        Self::account_has_role(acc, PAYING_AGENT_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: account_is_rating_agent(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc has global Rating Agent role
+    /// </pre>
+    pub fn account_is_rating_agent(acc: &T::AccountId) -> bool {
+        Self::account_has_role(acc, RATING_AGENT_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: account_is_compliance_officer(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc has global Compliance Officer role
+    /// </pre>
+    pub fn account_is_compliance_officer(acc: &T::AccountId) -> bool {
+        Self::account_has_role(acc, COMPLIANCE_OFFICER_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: account_is_kyc_provider(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc has global KYC Provider role
+    /// </pre>
+    pub fn account_is_kyc_provider(acc: &T::AccountId) -> bool {
+        Self::account_has_role(acc, KYC_PROVIDER_ROLE_MASK, None)
+    }
+
+    /// <pre>
+    /// Method: account_is_kyc_valid(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc has KYC attestation, which is not expired
+    /// </pre>
+    pub fn account_is_kyc_valid(acc: &T::AccountId) -> bool {
+        AccountKyc::<T>::get(acc).map_or(false, |kyc| !kyc.is_expired(Timestamp::<T>::get()))
+    }
+
+    /// <pre>
+    /// Method: account_token_mint_burn_allowed(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc can create burn and mint tokens requests(INVESTOR or ISSUER)
+    /// </pre>
+    pub fn account_token_mint_burn_allowed(acc: &T::AccountId) -> bool {
+        Self::account_is_investor(acc) || Self::account_is_issuer(acc)
+    }
+
+    /// <pre>
+    /// Method: account_is_registered(acc: &T::AccountId) -> bool
+    /// Arguments: acc: AccountId - checked account id
+    ///
+    /// Checks if the acc is present in AccountRegistry and has at least one role
+    /// </pre>
+    pub fn account_is_registered(acc: &T::AccountId) -> bool {
+        AccountRegistry::<T>::contains_key(acc) && AccountRegistry::<T>::get(acc).roles != 0
+    }
+
+    /// <pre>
+    /// Method: balance_everusd(acc: &T::AccountId) -> EverUSDBalance
+    /// Arguments: acc: AccountId - account id
+    ///
+    /// Returns account's balance as the number of EverUSD tokens.
+    /// Amount is returned as unsigned integer in smallest units of EverUSD
+    ///  "1" EverUSD is presented as "1 000 000 000"(depends on EVERUSD_DECIMALS == 9)
+    /// </pre>
+    pub fn balance_everusd(acc: &T::AccountId) -> EverUSDBalance {
+        BalanceEverUSD::<T>::get(acc)
+    }
+
+    /// <pre>
+    /// Method: total_supply() -> EverUSDBalance
+    /// Arguments: none
+    ///
+    /// Returns the total number of EverUSD tokens, owned by all holders, that was
+    /// supplied by the custodian. Every mint() operation inreases total_supply, while
+    /// burn() - decreases it. Initial total_supply of EverUSD == 0
+    /// </pre>
+    #[cfg(test)]
+    pub fn total_supply() -> EverUSDBalance {
+        TotalSupplyEverUSD::get()
+    }
+
+    /// <pre>
+    /// Method: unattributed_supply_everusd() -> EverUSDBalance
+    /// Arguments: none
+    ///
+    /// Returns the part of EverUSD total supply, that isn't backed by any registered custodian
+    /// </pre>
+    pub fn unattributed_supply_everusd() -> EverUSDBalance {
+        let attributed: EverUSDBalance = CustodianRegistry::<T>::iter_values()
+            .map(|custodian| custodian.supply)
+            .sum();
+        TotalSupplyEverUSD::get().saturating_sub(attributed)
+    }
+
+    /// <pre>
+    /// Method: get_bond(bond: BondId) -> bond: BondId) -> BondStruct
+    /// Arguments: bond: BondId - bond unique identifier
+    ///
+    ///  Returns bond structure if it was found in BondRegistry by given BondId
+    /// </pre>
+    pub fn get_bond(bond: &BondId) -> BondStructOf<T> {
+        BondRegistry::<T>::get(bond)
+    }
+
+    #[cfg(test)]
+    pub fn bond_check_invariant(bond: &BondId) -> bool {
+        let (bond_units, coupon_yield) = BondUnitPackageRegistry::<T>::iter_prefix_values(bond)
+            .fold((0, 0), |acc, packages| {
+                packages.iter().fold(acc, |acc, package| {
+                    (acc.0 + package.bond_units, acc.1 + package.coupon_yield)
+                })
+            });
+        let bond = BondRegistry::<T>::get(bond);
+
+        bond.issued_amount == bond_units && bond.coupon_yield == coupon_yield
+    }
+
+    /// <pre>
+    /// Method: bond_holder_packages(bond: &BondId, bondholder: &T::AccountId) -> Vec<BondUnitPackage>
+    /// Arguments: bond: BondId - bond unique identifier
+    ///            bondholder: AccountId - bondholder account id
+    ///
+    /// Returns all BondUnitPackage-s of the bondholder for given bond
+    /// </pre>
+    pub fn bond_holder_packages(bond: &BondId, bondholder: &T::AccountId) -> Vec<BondUnitPackage> {
+        BondUnitPackageRegistry::<T>::get(bond, bondholder)
+    }
+
+    pub fn bond_impact_data(bond: &BondId) -> Vec<BondImpactReportStruct> {
+        BondImpactReport::get(bond)
+    }
+
+    /// <pre>
+    /// Method: bond_documents(bond: &BondId) -> Vec<(BondDocumentId, BondDocumentStruct)>
+    /// Arguments: bond: BondId - bond identifier
+    ///
+    /// Returns all documents of the bond, including amended versions, ordered by id
+    /// </pre>
+    pub fn bond_documents(bond: &BondId) -> Vec<(BondDocumentId, BondDocumentStructOf<T>)> {
+        let mut documents: Vec<_> = BondDocument::<T>::iter_prefix(bond).collect();
+        documents.sort_by_key(|(id, _)| *id);
+        documents
+    }
+
+    /// <pre>
+    /// Method: bond_units_in_orders(bond: &BondId, bondholder: &T::AccountId) -> BondUnitAmount
+    /// Arguments: bond: BondId - bond identifier
+    ///            bondholder: AccountId - bondholder account id
+    ///
+    /// Returns amount of bond units, locked by sell orders of the bondholder
+    /// </pre>
+    pub fn bond_units_in_orders(bond: &BondId, bondholder: &T::AccountId) -> BondUnitAmount {
+        BondOrderBook::<T>::get(bond, BondOrderSide::SELL)
+            .iter()
+            .filter(|order| order.owner == *bondholder)
+            .map(|order| order.bond_units)
+            .sum()
+    }
+
+    /// <pre>
+    /// Returns amount of bond units of the bondholder, not locked by sale lots and sell orders
+    /// </pre>
+    fn bond_units_free(bond: &BondId, bondholder: &T::AccountId, now: T::Moment) -> BondUnitAmount {
+        let total_bond_units = Self::bond_units_total(bond, bondholder);
+        let bond_units_inlot: BondUnitAmount = BondUnitPackageLot::<T>::get(bond, bondholder)
+            .iter()
+            .filter(|lot| !lot.is_expired(now))
+            .map(|lot| lot.bond_units)
+            .sum();
+        total_bond_units.saturating_sub(bond_units_inlot + Self::bond_units_in_orders(bond, bondholder))
+    }
+
+    /// <pre>
+    /// Checks that the investor complies with eligibility rules of the bond,
+    /// holding "holding" Bond Units after the deal. "leaving" is the bondholder,
+    /// who sells all Bond Units in the deal
+    /// </pre>
+    fn ensure_bond_eligible(
+        bond: &BondId,
+        item: &BondStructOf<T>,
+        investor: &T::AccountId,
+        holding: BondUnitAmount,
+        leaving: Option<&T::AccountId>,
+        now: T::Moment,
+    ) -> DispatchResult {
+        let rules = &item.inner.eligibility;
+        ensure!(
+            rules.is_kyc_eligible(AccountKyc::<T>::get(investor).as_ref(), now),
+            Error::<T>::KycNotValid
+        );
+        ensure!(
+            !rules.allow_list_only || BondAllowList::<T>::get(bond, investor),
+            Error::<T>::BondInvestorNotAllowed
+        );
+        ensure!(rules.is_holding_eligible(holding), Error::<T>::BondHoldingOutOfLimits);
+        // new bondholder
+        if Self::bond_units_total(bond, investor) == 0 {
+            let holders = BondHolderCount::get(bond).saturating_sub(leaving.is_some() as u32);
+            let max_holders = rules.max_holders.map_or(MAX_BOND_HOLDERS, |max| min(max, MAX_BOND_HOLDERS));
+            ensure!(holders < max_holders, Error::<T>::BondHoldersLimitExceeded);
+        }
+        Ok(())
+    }
+
+    /// <pre>
+    /// Checks that the bondholder can sell Bond Units: trading of the bond is not halted,
+    /// lock-up period of the bond is passed and the bondholder is not locked up
+    /// </pre>
+    fn ensure_bond_units_transferable(
+        bond: &BondId,
+        item: &BondStructOf<T>,
+        bondholder: &T::AccountId,
+        now: T::Moment,
+    ) -> DispatchResult {
+        ensure!(!BondTradingHalt::get(bond), Error::<T>::BondTradingHalted);
+        ensure!(item.is_lockup_passed(now), Error::<T>::BondUnitsLocked);
+        ensure!(
+            BondUnitLockup::<T>::get(bond, bondholder).map_or(true, |until| until <= now),
+            Error::<T>::BondUnitsLocked
+        );
+        Ok(())
     }
 
     /// <pre>
-    /// Method: account_is_investor(acc: &T::AccountId) -> bool
-    /// Arguments: acc: AccountId - checked account id
-    ///
-    /// Checks if the acc has global Investor role
+    /// Stores Bond Unit packages of the bondholder and keeps the amount of bondholders
+    /// of the bond. Bondholder without packages is removed from the registry
     /// </pre>
-    pub fn account_is_investor(acc: &T::AccountId) -> bool {
-        AccountRegistry::<T>::get(acc).roles & INVESTOR_ROLE_MASK != 0
+    fn set_bond_unit_packages(bond: &BondId, bondholder: &T::AccountId, packages: Vec<BondUnitPackage>) {
+        let was_holder = BondUnitPackageRegistry::<T>::decode_len(bond, bondholder).unwrap_or(0) > 0;
+        let is_holder = !packages.is_empty();
+        if is_holder {
+            BondUnitPackageRegistry::<T>::insert(bond, bondholder, packages);
+        } else {
+            BondUnitPackageRegistry::<T>::remove(bond, bondholder);
+        }
+        if is_holder != was_holder {
+            BondHolderCount::mutate(bond, |count| {
+                *count = if is_holder { count.saturating_add(1) } else { count.saturating_sub(1) }
+            });
+        }
     }
 
     /// <pre>
-    /// Method: account_is_auditor(acc: &T::AccountId) -> bool
-    /// Arguments: acc: AccountId - checked account id
-    ///
-    /// Checks if the acc has global Auditor role
+    /// Returns amount of bond units of the bondholder
     /// </pre>
-    pub fn account_is_auditor(acc: &T::AccountId) -> bool {
-        AccountRegistry::<T>::get(acc).roles & AUDITOR_ROLE_MASK != 0
+    fn bond_units_total(bond: &BondId, bondholder: &T::AccountId) -> BondUnitAmount {
+        BondUnitPackageRegistry::<T>::get(bond, bondholder)
+            .iter()
+            .map(|package| package.bond_units)
+            .sum()
     }
 
     /// <pre>
-    /// Method: account_is_manager(acc: &T::AccountId) -> bool
-    /// Arguments: acc: AccountId - checked account id
-    ///
-    /// Checks if the acc has global Manager role
+    /// Transfers bond units from seller to buyer, and pays "amount" EverUSD to seller.
+    /// Before transfer, calculates and stores coupon yield of the bond and
+    /// requests coupon yield for seller and buyer, because the deal changes their
+    /// BondUnitsPackages. Buyer's EverUSD should be taken by caller.
+    /// All changes are reverted if the deal fails
     /// </pre>
-    pub fn account_is_manager(acc: &T::AccountId) -> bool {
-        AccountRegistry::<T>::get(acc).roles & MANAGER_ROLE_MASK != 0
+    #[transactional]
+    fn settle_bond_units_deal(
+        bond: &BondId,
+        seller: &T::AccountId,
+        buyer: &T::AccountId,
+        bond_units: BondUnitAmount,
+        amount: EverUSDBalance,
+        now: T::Moment,
+    ) -> DispatchResult {
+        ensure!(!Self::is_restructuring_open(bond, now), Error::<T>::RestructuringInProgress);
+        let item = BondRegistry::<T>::get(bond);
+        Self::ensure_bond_units_transferable(bond, &item, seller, now)?;
+        let seller_units = Self::bond_units_total(bond, seller).saturating_sub(bond_units);
+        ensure!(
+            item.inner.eligibility.is_remainder_eligible(seller_units),
+            Error::<T>::BondHoldingOutOfLimits
+        );
+        Self::ensure_bond_eligible(
+            bond,
+            &item,
+            buyer,
+            Self::bond_units_total(bond, buyer).saturating_add(bond_units),
+            if seller_units == 0 { Some(seller) } else { None },
+            now,
+        )?;
+        // @TODO optimize out access to balances
+        BondRegistry::<T>::mutate(bond, |mut item| {
+            Self::calc_and_store_bond_coupon_yield(bond, &mut item, now);
+            Self::request_coupon_yield(bond, &mut item, seller);
+            Self::request_coupon_yield(bond, &mut item, buyer);
+        });
+
+        let mut from_packages = BondUnitPackageRegistry::<T>::get(bond, seller);
+        let mut to_packages = BondUnitPackageRegistry::<T>::get(bond, buyer);
+        transfer_bond_units::<T>(&mut from_packages, &mut to_packages, bond_units)?;
+        // store new packages
+        Self::set_bond_unit_packages(bond, seller, from_packages);
+        Self::set_bond_unit_packages(bond, buyer, to_packages);
+
+        Self::balance_add(seller, amount)
     }
 
     /// <pre>
-    /// Method: account_is_impact_reporter(acc: &T::AccountId) -> bool
-    /// Arguments: acc: AccountId - checked account id
-    ///
-    /// Checks if the acc has global Impact Reporter role
+    /// Settles both legs of the swap: give_units of give_bond are moved from maker
+    /// to counterparty, take_units of take_bond are moved from counterparty to maker,
+    /// counterparty receives locked EverUSD of the swap.
+    /// All changes are reverted if any leg fails
     /// </pre>
-    pub fn account_is_impact_reporter(acc: &T::AccountId) -> bool {
-        AccountRegistry::<T>::get(acc).roles & IMPACT_REPORTER_ROLE_MASK != 0
+    #[transactional]
+    fn swap_settle(swap: &BondSwapStructOf<T>, counterparty: &T::AccountId, now: T::Moment) -> DispatchResult {
+        Self::settle_bond_units_deal(&swap.give_bond, &swap.maker, counterparty, swap.give_units, 0, now)?;
+        Self::settle_bond_units_deal(&swap.take_bond, counterparty, &swap.maker, swap.take_units, swap.amount, now)
     }
 
     /// <pre>
-    /// Method: account_token_mint_burn_allowed(acc: &T::AccountId) -> bool
-    /// Arguments: acc: AccountId - checked account id
-    ///
-    /// Checks if the acc can create burn and mint tokens requests(INVESTOR or ISSUER)
+    /// Locks EverUSD of buy order or checks, that owner of sell order has enough
+    /// free bond units (not locked by sale lots and other sell orders)
     /// </pre>
-    pub fn account_token_mint_burn_allowed(acc: &T::AccountId) -> bool {
-        const ALLOWED_ROLES_MASK: u8 = INVESTOR_ROLE_MASK | ISSUER_ROLE_MASK;
-        AccountRegistry::<T>::get(acc).roles & ALLOWED_ROLES_MASK != 0
+    fn order_lock(
+        bond: &BondId,
+        side: BondOrderSide,
+        order: &BondOrderStructOf<T>,
+        now: T::Moment,
+    ) -> DispatchResult {
+        match side {
+            BondOrderSide::BUY => Self::balance_sub(&order.owner, order.locked_amount()),
+            BondOrderSide::SELL => {
+                ensure!(
+                    Self::bond_units_free(bond, &order.owner, now) >= order.bond_units,
+                    Error::<T>::BalanceOverdraft
+                );
+                Ok(())
+            }
+        }
     }
 
     /// <pre>
-    /// Method: balance_everusd(acc: &T::AccountId) -> EverUSDBalance
-    /// Arguments: acc: AccountId - account id
-    ///
-    /// Returns account's balance as the number of EverUSD tokens.
-    /// Amount is returned as unsigned integer in smallest units of EverUSD
-    ///  "1" EverUSD is presented as "1 000 000 000"(depends on EVERUSD_DECIMALS == 9)
+    /// Unlocks EverUSD of not filled part of buy order
     /// </pre>
-    pub fn balance_everusd(acc: &T::AccountId) -> EverUSDBalance {
-        BalanceEverUSD::<T>::get(acc)
+    fn order_unlock(side: BondOrderSide, order: &BondOrderStructOf<T>) -> DispatchResult {
+        match side {
+            BondOrderSide::BUY => Self::balance_add(&order.owner, order.locked_amount()),
+            BondOrderSide::SELL => Ok(()),
+        }
     }
 
     /// <pre>
-    /// Method: total_supply() -> EverUSDBalance
-    /// Arguments: none
-    ///
-    /// Returns the total number of EverUSD tokens, owned by all holders, that was
-    /// supplied by the custodian. Every mint() operation inreases total_supply, while
-    /// burn() - decreases it. Initial total_supply of EverUSD == 0
+    /// Removes at most "limit" expired orders from the side of order book,
+    /// unlocking EverUSD of buy orders. Returns the number of removed orders
     /// </pre>
-    #[cfg(test)]
-    pub fn total_supply() -> EverUSDBalance {
-        TotalSupplyEverUSD::get()
+    fn order_book_purge(
+        side: BondOrderSide,
+        book: &mut Vec<BondOrderStructOf<T>>,
+        now: T::Moment,
+        limit: usize,
+    ) -> usize {
+        let mut purged = 0;
+        book.retain(|order| {
+            if purged < limit && order.is_expired(now) {
+                // unlocking adds EverUSD, taken from the owner's balance, it can't overflow
+                let _ = Self::order_unlock(side, order);
+                purged += 1;
+                false
+            } else {
+                true
+            }
+        });
+        purged
     }
 
     /// <pre>
-    /// Method: get_bond(bond: BondId) -> bond: BondId) -> BondStruct
-    /// Arguments: bond: BondId - bond unique identifier
+    /// Method: order_place(bond, side, order, now) -> Result<u32, DispatchError>
     ///
-    ///  Returns bond structure if it was found in BondRegistry by given BondId
+    /// Locks funds of the order, matches it with orders of the opposite side and puts
+    /// not filled part of the order into order book. Returns the number of deals.
+    /// Matching stops, when holding of the order owner would break eligibility rules.
+    /// Resting order, which deal fails, is removed from order book and unlocked.
+    /// All changes are reverted if the order itself can't be settled
     /// </pre>
-    #[cfg(test)]
-    pub fn get_bond(bond: &BondId) -> BondStructOf<T> {
-        BondRegistry::<T>::get(bond)
-    }
-
-    #[cfg(test)]
-    pub fn bond_check_invariant(bond: &BondId) -> bool {
-        let (bond_units, coupon_yield) = BondUnitPackageRegistry::<T>::iter_prefix_values(bond)
-            .fold((0, 0), |acc, packages| {
-                packages.iter().fold(acc, |acc, package| {
-                    (acc.0 + package.bond_units, acc.1 + package.coupon_yield)
-                })
-            });
-        let bond = BondRegistry::<T>::get(bond);
+    #[transactional]
+    fn order_place(
+        bond: &BondId,
+        side: BondOrderSide,
+        mut order: BondOrderStructOf<T>,
+        now: T::Moment,
+    ) -> Result<u32, DispatchError> {
+        // unlock expired orders before they can be matched
+        let mut book = BondOrderBook::<T>::get(bond, side);
+        let mut opposite = BondOrderBook::<T>::get(bond, side.opposite());
+        Self::order_book_purge(side, &mut book, now, MAX_BOND_ORDERS);
+        Self::order_book_purge(side.opposite(), &mut opposite, now, MAX_BOND_ORDERS);
+        ensure!(book.len() < MAX_BOND_ORDERS, Error::<T>::OrderBookFull);
+        // sell order locks units, which are not in order book yet
+        BondOrderBook::<T>::insert(bond, side.opposite(), &opposite);
+        BondOrderBook::<T>::insert(bond, side, &book);
+        Self::order_lock(bond, side, &order, now)?;
+        // deals can't be settled because of the order, not because of resting orders
+        let item = BondRegistry::<T>::get(bond);
+        let crossing = opposite.first().map_or(false, |resting| {
+            side.is_crossing(order.price, resting.price)
+        });
+        if crossing {
+            ensure!(
+                !Self::is_restructuring_open(bond, now),
+                Error::<T>::RestructuringInProgress
+            );
+            let owner = &order.owner;
+            match side {
+                BondOrderSide::BUY => {
+                    let holding =
+                        Self::bond_units_total(bond, owner).saturating_add(order.bond_units);
+                    Self::ensure_bond_eligible(bond, &item, owner, holding, None, now)?
+                }
+                BondOrderSide::SELL => {
+                    Self::ensure_bond_units_transferable(bond, &item, owner, now)?
+                }
+            }
+        }
 
-        bond.issued_amount == bond_units && bond.coupon_yield == coupon_yield
-    }
+        let mut matched: u32 = 0;
+        let mut index = 0;
+        while order.bond_units > 0 && index < opposite.len() {
+            let resting = &mut opposite[index];
+            if !side.is_crossing(order.price, resting.price) {
+                break;
+            }
+            if resting.owner == order.owner {
+                index += 1;
+                continue;
+            }
+            let bond_units = min(order.bond_units, resting.bond_units);
+            // partial fill can leave the order owner out of holding limits
+            let holding = Self::bond_units_total(bond, &order.owner);
+            let rules = &item.inner.eligibility;
+            let eligible = match side {
+                BondOrderSide::BUY => rules.is_holding_eligible(holding.saturating_add(bond_units)),
+                BondOrderSide::SELL => {
+                    rules.is_remainder_eligible(holding.saturating_sub(bond_units))
+                }
+            };
+            if !eligible {
+                break;
+            }
+            let amount = resting.price.saturating_mul(bond_units as EverUSDBalance);
+            let (seller, buyer) = match side {
+                BondOrderSide::BUY => (&resting.owner, &order.owner),
+                BondOrderSide::SELL => (&order.owner, &resting.owner),
+            };
+            if Self::settle_bond_units_deal(bond, seller, buyer, bond_units, amount, now).is_err() {
+                // f.e. owner of resting order has lost eligibility or bond units
+                let resting = opposite.remove(index);
+                Self::order_unlock(side.opposite(), &resting)?;
+                Self::deposit_event(RawEvent::BondOrderDropped(resting.owner, *bond, resting.id));
+                continue;
+            }
+            Self::deposit_event(RawEvent::BondOrderMatched(
+                seller.clone(),
+                buyer.clone(),
+                *bond,
+                bond_units,
+                amount,
+            ));
+            if side == BondOrderSide::BUY {
+                // buyer locked EverUSD at his own price
+                Self::balance_add(
+                    &order.owner,
+                    (order.price - resting.price).saturating_mul(bond_units as EverUSDBalance),
+                )?;
+            }
 
-    #[cfg(test)]
-    pub fn bond_holder_packages(bond: &BondId, bondholder: &T::AccountId) -> Vec<BondUnitPackage> {
-        BondUnitPackageRegistry::<T>::get(bond, bondholder)
+            resting.bond_units -= bond_units;
+            order.bond_units -= bond_units;
+            matched += 1;
+            if resting.bond_units == 0 {
+                opposite.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        BondOrderBook::<T>::insert(bond, side.opposite(), opposite);
+
+        BondOrderCount::insert(bond, order.id + 1);
+        let (owner, id) = (order.owner.clone(), order.id);
+        if order.bond_units > 0 {
+            Self::schedule_expiry(order.deadline, ExpiryItem::Orders(*bond, side));
+            order_book_insert(side, &mut book, order);
+            BondOrderBook::<T>::insert(bond, side, book);
+        }
+        Self::deposit_event(RawEvent::BondOrderPlaced(owner, *bond, id));
+        Ok(matched)
     }
 
-    pub fn bond_impact_data(bond: &BondId) -> Vec<BondImpactReportStruct> {
-        BondImpactReport::get(bond)
+    /// Stores new document of the bond, returns document id
+    fn document_insert(
+        bond: &BondId,
+        document: BondDocumentStructOf<T>,
+    ) -> Result<BondDocumentId, DispatchError> {
+        BondDocumentCount::try_mutate(bond, |count| {
+            ensure!(*count < MAX_BOND_DOCUMENTS, Error::<T>::DocumentParamIncorrect);
+            let id = *count;
+            BondDocument::<T>::insert(bond, id, document);
+            *count += 1;
+            Ok(id)
+        })
     }
 
     #[cfg(test)]
@@ -1782,38 +3860,273 @@ impl<T: Config> Module<T> {
     }
 
     /// <pre>
-    /// Deletes expired burn requests.
-    /// Process less or equal than MAX_PURGE_REQUESTS expired requests
+    /// Moves `amount` EverUSD between two registered accounts.
+    /// With `keep_alive` set, sender's balance must stay non-zero and
+    /// cover the amount of sender's pending burn requests
+    /// </pre>
+    fn transfer_everusd_from(
+        from: T::AccountId,
+        to: T::AccountId,
+        amount: EverUSDBalance,
+        keep_alive: bool,
+    ) -> DispatchResult {
+        ensure!(Self::account_is_registered(&from), Error::<T>::AccountNotAuthorized);
+        ensure!(Self::account_is_registered(&to), Error::<T>::AccountNotAuthorized);
+        ensure!(from != to && amount > 0, Error::<T>::InvalidAction);
+        if TransferKycRequired::get() {
+            ensure!(
+                Self::account_is_kyc_valid(&from) && Self::account_is_kyc_valid(&to),
+                Error::<T>::KycNotValid
+            );
+        }
+
+        let remaining = BalanceEverUSD::<T>::get(&from)
+            .checked_sub(amount)
+            .ok_or(Error::<T>::BalanceOverdraft)?;
+        if keep_alive {
+            ensure!(remaining > 0, Error::<T>::BalanceOverdraft);
+            let now = Timestamp::<T>::get();
+            let burn_amount: EverUSDBalance = BurnRequestEverUSD::<T>::iter_prefix_values(&from)
+                .filter(|request| !request.is_expired(now))
+                .map(|request| request.amount)
+                .sum();
+            ensure!(remaining >= burn_amount, Error::<T>::BalanceOverdraft);
+        }
+
+        Self::balance_sub(&from, amount)?;
+        Self::balance_add(&to, amount)?;
+
+        Self::deposit_event(RawEvent::TransferEverUSD(from, to, amount));
+        Ok(())
+    }
+
+    /// <pre>
+    /// Appends the item to the slot of its moment.
+    /// Item with moment before the cursor is put into the slot of the cursor
     /// </pre>
-    fn purge_expired_burn_requests(before: T::Moment) {
-        let to_purge: Vec<_> = BurnRequestEverUSD::<T>::iter()
-            .filter(|(_, request)| request.is_expired(before))
-            .map(|(acc, _)| acc)
-            .take(MAX_PURGE_REQUESTS)
-            .collect();
+    fn enqueue<Q, L, C, I>(moment: T::Moment, item: I)
+    where
+        Q: StorageDoubleMap<QueueSlot, QueueIndex, (T::Moment, I)>,
+        L: StorageMap<QueueSlot, QueueIndex, Query = QueueIndex>,
+        C: StorageValue<(QueueSlot, QueueIndex), Query = Option<(QueueSlot, QueueIndex)>>,
+        I: FullCodec,
+    {
+        let slot = queue_slot(moment, T::TimeStep::get());
+        let slot = match C::get() {
+            Some((cursor, _)) => max(slot, cursor),
+            None => {
+                let now = queue_slot(Timestamp::<T>::get(), T::TimeStep::get());
+                C::put((min(slot, now), 0));
+                slot
+            }
+        };
+        let index = L::get(slot);
+        L::insert(slot, index + 1);
+        Q::insert(slot, index, (moment, item));
+    }
+
+    /// <pre>
+    /// Takes items with moment before "until" from the queue, starting from the cursor,
+    /// and moves the cursor to the first item, which is not taken. Items of the slot
+    /// are not sorted, so the slot of "until" is scanned up to the last item.
+    /// Every visited slot and every visited item uses one of "limit" units.
+    /// Returns taken items and the number of used units
+    /// </pre>
+    fn dequeue<Q, L, C, I>(until: T::Moment, limit: usize) -> (Vec<(T::Moment, I)>, usize)
+    where
+        Q: StorageDoubleMap<QueueSlot, QueueIndex, (T::Moment, I), Query = Option<(T::Moment, I)>>,
+        L: StorageMap<QueueSlot, QueueIndex, Query = QueueIndex>,
+        C: StorageValue<(QueueSlot, QueueIndex), Query = Option<(QueueSlot, QueueIndex)>>,
+        I: FullCodec,
+    {
+        let mut taken = Vec::new();
+        let (mut slot, mut head) = match C::get() {
+            Some(cursor) => cursor,
+            None => return (taken, 0),
+        };
+        let last = queue_slot(until, T::TimeStep::get());
+        let mut used: usize = 0;
+        while slot <= last && used < limit {
+            used += 1;
+            let len = L::get(slot);
+            // items before the index are taken, except items after "until"
+            let mut index = head;
+            while index < len && used < limit {
+                used += 1;
+                let is_taken = match Q::get(slot, index) {
+                    Some((moment, item)) if moment < until => {
+                        Q::remove(slot, index);
+                        taken.push((moment, item));
+                        true
+                    }
+                    Some(_) => false,
+                    None => true,
+                };
+                index += 1;
+                if is_taken && head == index - 1 {
+                    head = index;
+                }
+            }
+            // slot of "until" can get new items
+            if head < len || slot == last {
+                break;
+            }
+            L::remove(slot);
+            slot += 1;
+            head = 0;
+        }
+        if used > 0 {
+            C::put((slot, head));
+        }
+        (taken, used)
+    }
+
+    /// <pre>
+    /// Queues the bond for coupon yield accrual at the moment. Previously queued moment
+    /// of the bond becomes obsolete
+    /// </pre>
+    fn schedule_accrual(bond: BondId, moment: T::Moment) {
+        BondAccrualMoment::<T>::insert(&bond, moment);
+        Self::enqueue::<BondAccrualQueue<T>, BondAccrualQueueLen, BondAccrualCursor, _>(
+            moment, bond,
+        );
+    }
+
+    /// <pre>
+    /// Queues the item for removal after the deadline
+    /// </pre>
+    fn schedule_expiry(deadline: T::Moment, item: ExpiryItemOf<T>) {
+        Self::enqueue::<ExpiryQueue<T>, ExpiryQueueLen, ExpiryCursor, _>(deadline, item);
+    }
+
+    /// <pre>
+    /// Removes the burn request, releasing its amount from pending burns of the custodian
+    /// </pre>
+    fn burn_request_take(
+        acc: &T::AccountId,
+        id: TokenRequestId,
+    ) -> Option<TokenBurnRequestStructOf<T>> {
+        let request = BurnRequestEverUSD::<T>::take(acc, id)?;
+        CustodianPendingBurn::<T>::mutate(&request.custodian, |pending| {
+            *pending = pending.saturating_sub(request.amount)
+        });
+        Some(request)
+    }
 
-        for acc in to_purge {
-            BurnRequestEverUSD::<T>::remove(acc);
+    /// <pre>
+    /// Removes expired item from storage, unlocking EverUSD of buy orders and swaps
+    /// </pre>
+    fn expire(item: ExpiryItemOf<T>, now: T::Moment) {
+        match item {
+            ExpiryItem::MintRequest(acc, id) => {
+                if MintRequestEverUSD::<T>::get(&acc, id)
+                    .map_or(false, |request| request.is_expired(now))
+                {
+                    MintRequestEverUSD::<T>::remove(&acc, id);
+                }
+            }
+            ExpiryItem::BurnRequest(acc, id) => {
+                if BurnRequestEverUSD::<T>::get(&acc, id)
+                    .map_or(false, |request| request.is_expired(now))
+                {
+                    Self::burn_request_take(&acc, id);
+                }
+            }
+            ExpiryItem::SaleLots(bond, bondholder) => {
+                let mut lots = BondUnitPackageLot::<T>::get(&bond, &bondholder);
+                let count = lots.len();
+                lots.retain(|lot| !lot.is_expired(now));
+                if lots.is_empty() {
+                    BondUnitPackageLot::<T>::remove(&bond, &bondholder);
+                } else if lots.len() < count {
+                    BondUnitPackageLot::<T>::insert(&bond, &bondholder, lots);
+                }
+            }
+            ExpiryItem::Orders(bond, side) => {
+                let mut book = BondOrderBook::<T>::get(&bond, side);
+                if Self::order_book_purge(side, &mut book, now, MAX_BOND_ORDERS) > 0 {
+                    BondOrderBook::<T>::insert(&bond, side, book);
+                }
+            }
+            ExpiryItem::Swap(id) => {
+                if let Some(swap) = BondSwap::<T>::get(id).filter(|swap| swap.is_expired(now)) {
+                    let _ = Self::balance_add(&swap.maker, swap.amount);
+                    BondSwap::<T>::remove(id);
+                }
+            }
         }
     }
 
     /// <pre>
-    /// Deletes expired mint requests from the queue.
-    /// Process less or equal than MAX_PURGE_REQUESTS expired requests
+    /// Method: process_coupon_accruals(limit: Weight) -> Weight
+    /// Arguments: limit: Weight - weight budget
+    ///
+    /// Takes bonds from BondAccrualQueue, which payment_period has ended,
+    /// and calculates their coupon yield (see calc_and_store_bond_coupon_yield()).
+    /// Each processed bond is queued again with the end moment of its next period,
+    /// or dropped if all periods are accrued or the bond is not ACTIVE or BANKRUPT.
+    /// When budget remains, removes up to MAX_PURGE_REQUESTS expired mint/burn requests,
+    /// sale lots, orders and swaps from ExpiryQueue.
+    /// Returns consumed weight, which never exceeds "limit" plus cursor reads
     /// </pre>
-    fn purge_expired_mint_requests(before: T::Moment) {
-        let to_purge: Vec<_> = MintRequestEverUSD::<T>::iter()
-            .filter(|(_, request)| request.is_expired(before))
-            .map(|(acc, _)| acc)
-            .take(MAX_PURGE_REQUESTS)
-            .collect();
+    fn process_coupon_accruals(limit: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        // every processed bond has single payment_period to accrue,
+        // reading of queue slot is estimated the same way
+        let accrual_weight =
+            <T as Config>::WeightInfo::bond_accrue_coupon_yield(WEIGHT_BONDHOLDERS, 1);
+        let purge_weight = db_weight.reads_writes(
+            6 * MAX_PURGE_REQUESTS as Weight,
+            6 * MAX_PURGE_REQUESTS as Weight,
+        );
+        let now = Timestamp::<T>::get();
+        let mut weight = db_weight.reads(3);
+
+        let units = (limit.saturating_sub(weight) / accrual_weight.max(1)) as usize;
+        let (due, used) =
+            Self::dequeue::<BondAccrualQueue<T>, BondAccrualQueueLen, BondAccrualCursor, _>(
+                now + 1_u32.into(),
+                units,
+            );
+        weight = weight.saturating_add(accrual_weight.saturating_mul(used as Weight));
+        for (moment, id) in due {
+            if BondAccrualMoment::<T>::get(&id) != Some(moment) {
+                // the bond was queued again
+                continue;
+            }
+            BondAccrualMoment::<T>::remove(&id);
+            let _ = Self::with_bond(&id, |item| -> DispatchResult {
+                ensure!(
+                    matches!(item.state, BondState::ACTIVE | BondState::BANKRUPT),
+                    Error::<T>::BondStateNotPermitAction
+                );
+                Self::calc_and_store_bond_coupon_yield(&id, item, now);
+                let accrued = BondCouponYield::get(&id).len() as BondPeriodNumber;
+                if let Some(next) = item.coupon_accrual_moment(accrued) {
+                    Self::schedule_accrual(id, next);
+                }
+                Ok(())
+            });
+        }
 
-        for acc in to_purge {
-            MintRequestEverUSD::<T>::remove(acc);
+        if weight.saturating_add(purge_weight) <= limit {
+            let (expired, _) = Self::dequeue::<ExpiryQueue<T>, ExpiryQueueLen, ExpiryCursor, _>(
+                now,
+                MAX_PURGE_REQUESTS,
+            );
+            for (_, item) in expired {
+                Self::expire(item, now);
+            }
+            weight = weight.saturating_add(purge_weight);
         }
+
+        weight
     }
 
-    #[cfg(test)]
+    /// <pre>
+    /// Returns accrued coupon yields and effective interest rates
+    /// for each passed period of given BondId
+    /// </pre>
     pub fn get_coupon_yields(bond: &BondId) -> Vec<PeriodYield> {
         BondCouponYield::get(bond)
     }
@@ -1836,14 +4149,47 @@ impl<T: Config> Module<T> {
             .collect()
     }
 
+    /// <pre>
+    /// Returns coupon yield and principal installment of the package for payment period "period".
+    /// Coupon yield is calculated on outstanding principal of Bond Unit at the beginning
+    /// of the period using day count convention of the bond, principal installment
+    /// is repaid if the package was held during the period.
+    /// This is the only place where coupon yield of the package is calculated
+    /// </pre>
+    fn package_period_yield(
+        bond: &BondStructOf<T>,
+        package: &BondUnitPackage,
+        period: BondPeriodNumber,
+        period_desc: &PeriodDescr,
+        interest_rate: BondInterest,
+    ) -> (EverUSDBalance, EverUSDBalance) {
+        let time_step = T::TimeStep::get();
+        let duration = period_desc.duration(package.acquisition);
+        let coupon_yield = calc_coupon_yield(
+            bond.unit_principal(period),
+            package.bond_units,
+            interest_rate,
+            duration / time_step,
+            (period_desc.payment_period - period_desc.start_period) / time_step,
+            bond.inner.day_count_convention,
+        );
+        let principal = if duration > 0 {
+            bond.unit_principal_installment(period) * package.bond_units as EverUSDBalance
+        } else {
+            0
+        };
+        (coupon_yield, principal)
+    }
+
     /// <pre>
     /// Calculates bond coupon yield and store accrued values in BondCouponYield map.
     /// Calculated values are stored in array, holding data for each payment_period.
     /// Function also updats bond's "bond_credit" value to the currently accrued coupon yield.
+    /// Principal installments of amortizing bond are accrued along with coupon yield.
     /// Returns the number of processed periods.
     /// Common function complexity is O(N), where N is the number of issued bond unit packages.
     /// For each BondUnitsPackage of bond (containing payment_period when it was bought),
-    /// the "package_yield" is calculated (yield per one bond unit), and then used to summarise
+    /// the "package_period_yield" is calculated, and then used to summarise
     /// yields of all bond's BondUnitsPackages.
     /// </pre>
     fn calc_and_store_bond_coupon_yield(
@@ -1868,8 +4214,6 @@ impl<T: Config> Module<T> {
             bond.bond_credit = total_yield;
             return 0;
         }
-        let time_step = T::TimeStep::get();
-
         let reports = BondImpactReport::get(id);
         assert!(reports.len() + 1 >= period);
 
@@ -1887,10 +4231,7 @@ impl<T: Config> Module<T> {
                 } else if reports[index - 1].signed {
                     // There is confirmed impact_data about this period
                     // Calculate interest rate, based on impact_data and baseline,min,max parameters of bond
-                    bond.calc_effective_interest_rate(
-                        bond.inner.impact_data_baseline[index - 1].unwrap_or(0),
-                        reports[index - 1].impact_data,
-                    )
+                    bond.calc_effective_interest_rate(index - 1, &reports[index - 1].impact_data)
                 } else {
                     // Report is missed, apply penalty for missed report(but not more than interest_rate_margin_cap)
                     min(
@@ -1901,10 +4242,6 @@ impl<T: Config> Module<T> {
                 }
             };
 
-            let package_yield = bond.inner.bond_units_base_price / 1000
-                * interest_rate as EverUSDBalance
-                / INTEREST_RATE_YEAR;
-
             // calculate yield for period equal to bond_yields.len()
             let period_coupon_yield: EverUSDBalance = match bond
                 .period_desc(index as BondPeriodNumber)
@@ -1918,12 +4255,14 @@ impl<T: Config> Module<T> {
                             packages
                                 .iter()
                                 .map(|package| {
-                                    // @TODO use checked arithmetics
-                                    package_yield
-                                        * package.bond_units as EverUSDBalance
-                                        * (period_desc.duration(package.acquisition) / time_step)
-                                            as EverUSDBalance
-                                        / 100
+                                    let (coupon_yield, principal) = Self::package_period_yield(
+                                        bond,
+                                        package,
+                                        index as BondPeriodNumber,
+                                        &period_desc,
+                                        interest_rate,
+                                    );
+                                    coupon_yield + principal
                                 })
                                 .sum::<EverUSDBalance>()
                         })
@@ -1958,8 +4297,8 @@ impl<T: Config> Module<T> {
     /// Function summarizes data from all passed periods,
     /// calculates coupon yield for each BondUnitsPackage, owned by bondholder.
     /// For each BondUnitsPackage, owned by "bondholder"(containing "payment_period"
-    /// when it was bought), the "package_yield" value is calculated (yield per one bond unit).
-    /// This "package_yield" then used to summarize yields of all bond's BondUnitsPackages.
+    /// when it was bought), the "package_period_yield" value is calculated.
+    /// This "package_period_yield" then used to summarize yields of all bond's BondUnitsPackages.
     /// </pre>
     pub fn redeem_bond_units(
         id: &BondId,
@@ -1967,7 +4306,9 @@ impl<T: Config> Module<T> {
         bondholder: &T::AccountId,
     ) -> EverUSDBalance {
         let packages = BondUnitPackageRegistry::<T>::take(id, &bondholder);
-        let time_step = T::TimeStep::get();
+        if !packages.is_empty() {
+            BondHolderCount::mutate(id, |count| *count = count.saturating_sub(1));
+        }
         let bond_yields = BondCouponYield::get(id);
         assert!(!bond_yields.is_empty());
         // calc coupon yield
@@ -1976,17 +4317,17 @@ impl<T: Config> Module<T> {
             .enumerate()
             .map(|(i, bond_yield)| {
                 let period_desc = bond.period_desc(i as BondPeriodNumber).unwrap();
-                let package_yield = bond.inner.bond_units_base_price / 1000
-                    * bond_yield.interest_rate as EverUSDBalance
-                    / INTEREST_RATE_YEAR;
                 packages
                     .iter()
                     .map(|package| {
-                        package_yield
-                            * package.bond_units as EverUSDBalance
-                            * (period_desc.duration(package.acquisition) / time_step)
-                                as EverUSDBalance
-                            / 100
+                        let (coupon_yield, principal) = Self::package_period_yield(
+                            bond,
+                            package,
+                            i as BondPeriodNumber,
+                            &period_desc,
+                            bond_yield.interest_rate,
+                        );
+                        coupon_yield + principal
                     })
                     .sum::<EverUSDBalance>()
             })
@@ -1997,9 +4338,9 @@ impl<T: Config> Module<T> {
                 (acc.0 + package.bond_units, acc.1 + package.coupon_yield)
             });
         // substrate paid coupon
-        payable -= paid_yield;
-        // add principal value
-        payable += bond.par_value(bond_units);
+        payable = payable.saturating_sub(paid_yield);
+        // add outstanding principal value (with call premium, if the bond was called)
+        payable += bond.redemption_value(bond_units, bond_yields.len() as BondPeriodNumber);
         bond.coupon_yield += payable;
 
         Self::balance_add(bondholder, payable).unwrap();
@@ -2007,6 +4348,126 @@ impl<T: Config> Module<T> {
         payable
     }
 
+    /// <pre>
+    /// Accrues coupon yield of passed periods on current terms, applies new terms
+    /// of approved restructuring proposal and moves the bond to ACTIVE state.
+    /// Haircut reduces outstanding principal of Bond Units from the current period, coupon yield
+    /// of the next periods is calculated on new terms. Impact report storage is extended
+    /// to new bond duration, and the bond is put back into accrual queue
+    /// </pre>
+    fn restructure_bond(
+        id: &BondId,
+        bond: &mut BondStructOf<T>,
+        terms: &BondRestructuringTermsStruct,
+        now: <T as pallet_timestamp::Config>::Moment,
+    ) {
+        Self::calc_and_store_bond_coupon_yield(id, bond, now);
+        terms.apply(&mut bond.inner);
+
+        let accrued = BondCouponYield::decode_len(id).unwrap_or(0) as BondPeriodNumber;
+        if terms.haircut_bps > 0 {
+            // accrued periods keep original par value
+            bond.principal_haircuts.push((accrued, terms.haircut_bps));
+        }
+        BondImpactReport::mutate(id, |reports| {
+            reports.resize(bond.inner.bond_duration as usize, Default::default())
+        });
+
+        bond.state = BondState::ACTIVE;
+        bond.nonce += 1;
+
+        // the bond leaves accrual queue when all periods are accrued
+        match bond.coupon_accrual_moment(accrued) {
+            Some(moment) => Self::schedule_accrual(*id, moment),
+            None => BondAccrualMoment::<T>::remove(id),
+        }
+    }
+
+    /// Checks if bondholders vote for the restructuring proposal of the bond
+    fn is_restructuring_open(bond: &BondId, now: <T as pallet_timestamp::Config>::Moment) -> bool {
+        BondRestructuring::<T>::get(bond).map_or(false, |proposal| !proposal.is_expired(now))
+    }
+
+    /// Checks that the action of bondholder meeting proposal can be performed
+    fn ensure_bond_proposal_action(
+        id: &BondId,
+        bond: &BondStructOf<T>,
+        action: &BondProposalAction<T::AccountId, T::Hash>,
+    ) -> DispatchResult {
+        match action {
+            BondProposalAction::Resolution(_) => {}
+            BondProposalAction::SetAuditor(acc) => {
+                ensure!(
+                    Self::account_has_role(acc, AUDITOR_ROLE_MASK, Some(id)),
+                    Error::<T>::AccountRoleParamIncorrect
+                );
+                ensure!(!bond.auditors.contains(acc), Error::<T>::ProposalParamIncorrect);
+            }
+            BondProposalAction::SetImpactReporter(acc) => {
+                ensure!(
+                    Self::account_has_role(acc, IMPACT_REPORTER_ROLE_MASK, Some(id)),
+                    Error::<T>::AccountRoleParamIncorrect
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Performs the action of passed bondholder meeting proposal.
+    /// Roles of assigned accounts are checked again, as they could be changed during the voting
+    fn execute_bond_proposal(
+        id: &BondId,
+        bond: &mut BondStructOf<T>,
+        action: &BondProposalAction<T::AccountId, T::Hash>,
+    ) -> DispatchResult {
+        Self::ensure_bond_proposal_action(id, bond, action)?;
+        match action {
+            BondProposalAction::Resolution(_) => return Ok(()),
+            BondProposalAction::SetAuditor(acc) => bond.auditor = acc.clone(),
+            BondProposalAction::SetImpactReporter(acc) => bond.impact_reporter = acc.clone(),
+        }
+        bond.nonce += 1;
+        Ok(())
+    }
+
+    /// Removes bondholder meeting proposal with its snapshot and votes
+    fn remove_bond_proposal(bond: &BondId, proposal: BondProposalId) {
+        BondProposal::<T>::remove(bond, proposal);
+        BondProposalSnapshot::<T>::remove_prefix((*bond, proposal));
+        BondProposalVote::<T>::remove_prefix((*bond, proposal));
+    }
+
+    /// <pre>
+    /// Accrues coupon yield of the bond and takes from (or returns to) Issuer's balance
+    /// the difference between bond fund and bond debt: accrued coupon yield and redemption value
+    /// of issued Bond Units. Moves bond to FINISHED state. Returns accrued coupon yield
+    /// </pre>
+    fn redeem_bond(
+        id: &BondId,
+        bond: &mut BondStructOf<T>,
+        now: <T as pallet_timestamp::Config>::Moment,
+    ) -> Result<EverUSDBalance, DispatchError> {
+        Self::calc_and_store_bond_coupon_yield(id, bond, now);
+        // now bond_credit has YTM ( yield to mature )
+        let periods = BondCouponYield::decode_len(id).unwrap_or(0) as BondPeriodNumber;
+        let amount = bond.bond_credit + bond.redemption_value(bond.issued_amount, periods);
+        if amount <= bond.bond_debit {
+            // withdraw free balance
+            Self::balance_add(&bond.issuer, bond.bond_debit - amount)?;
+        } else {
+            let transfer = amount - bond.bond_debit;
+            // pay off debt
+            Self::balance_sub(&bond.issuer, transfer)?;
+        }
+        let ytm = bond.bond_credit;
+        bond.bond_credit = amount;
+        //bond.coupon_yield = amount;
+        bond.bond_debit = amount;
+        bond.state = BondState::FINISHED;
+        bond.nonce += 1;
+        Ok(ytm)
+    }
+
     /// <pre>
     /// Transfer accrued coupon yield into bondholder balance
 
@@ -2015,8 +4476,8 @@ impl<T: Config> Module<T> {
     /// EverUSD to bondholder and stores information about "aready-transfered"
     /// amount of EverUSD for given bondholder.
     /// For each BondUnitsPackage, owned by "bondholder"(containing "payment_period"
-    /// when it was bought), the "package_yield" value is calculated (yield per one bond unit).
-    /// This "package_yield" then used to summarize yields of all bond's BondUnitsPackages
+    /// when it was bought), the "package_period_yield" value is calculated.
+    /// This "package_period_yield" then used to summarize yields of all bond's BondUnitsPackages
     /// and transfers this amount to bondholder from bond's balance.
     /// </pre>
     pub fn request_coupon_yield(
@@ -2048,7 +4509,6 @@ impl<T: Config> Module<T> {
             return 0;
         }
 
-        let time_step = T::TimeStep::get();
         let mut payable = 0;
 
         let mut prev_total_yield = if last_bondholder_coupon_yield.period_num == 0 {
@@ -2100,18 +4560,18 @@ impl<T: Config> Module<T> {
 
             debug_assert!(installment <= accrued_yield);
 
-            let package_yield = bond.inner.bond_units_base_price / 1000
-                * bond_yield.interest_rate as EverUSDBalance
-                / INTEREST_RATE_YEAR;
-
             let period_desc = bond.period_desc(i as BondPeriodNumber).unwrap();
 
             BondUnitPackageRegistry::<T>::mutate(id, &bondholder, |packages| {
                 for package in packages.iter_mut() {
-                    let accrued = package_yield
-                        * package.bond_units as EverUSDBalance
-                        * (period_desc.duration(package.acquisition) / time_step) as EverUSDBalance
-                        / 100;
+                    let (coupon_yield, principal) = Self::package_period_yield(
+                        bond,
+                        package,
+                        i as BondPeriodNumber,
+                        &period_desc,
+                        bond_yield.interest_rate,
+                    );
+                    let accrued = coupon_yield + principal;
 
                     let package_coupon_yield = if installment == accrued_yield {
                         accrued
@@ -2153,13 +4613,9 @@ impl<T: Config> Module<T> {
             let mut missed_periods = 0;
             let mut interest: bond::BondInterest = bond.inner.interest_rate_start_period_value.unwrap_or(0);
 
-            for (report, baseline) in reports[0..period]
-                .iter()
-                .zip(bond.inner.impact_data_baseline[0..period].iter())
-                .rev()
-            {
+            for (index, report) in reports[0..period].iter().enumerate().rev() {
                 if report.signed {
-                    interest = bond.calc_effective_interest_rate(baseline.unwrap_or(0), report.impact_data);
+                    interest = bond.calc_effective_interest_rate(index, &report.impact_data);
                     break;
                 }
                 missed_periods += 1;
@@ -2223,7 +4679,7 @@ impl<T: Config> Module<T> {
             let index = period as usize;
 
             reports[index].signed = true;
-            reports[index].impact_data = impact_data;
+            reports[index].impact_data = vec![impact_data];
             reports[index].create_period = 1; //dirty hack. test require nonzero value
 
             Ok(())
@@ -2236,11 +4692,18 @@ impl<T: Config> Module<T> {
         let bond_fund: EverUSDBalance = BondRegistry::<T>::iter_values()
             .map(|bond| bond.bond_debit - bond.coupon_yield)
             .sum();
+        let order_fund: EverUSDBalance = BondOrderBook::<T>::iter()
+            .filter(|(_, side, _)| *side == BondOrderSide::BUY)
+            .flat_map(|(_, _, book)| book.into_iter().map(|order| order.locked_amount()))
+            .sum();
+        let swap_fund: EverUSDBalance = BondSwap::<T>::iter_values().map(|swap| swap.amount).sum();
 
         ledger::EvercityBalance {
             supply: TotalSupplyEverUSD::get(),
             account,
             bond_fund,
+            order_fund,
+            swap_fund,
         }
     }
 }
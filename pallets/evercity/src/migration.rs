@@ -0,0 +1,484 @@
+//! Storage migrations of pallet-evercity.
+//! StorageVersion keeps the version of storage layout, migrations are applied
+//! in on_runtime_upgrade() one by one
+
+use crate::account::{
+    AccountRoleGrantStructOf, EvercityAccountStructT, RoleMask, TokenBurnRequestStruct,
+    TokenMintRequestStruct, CUSTODIAN_ROLE_MASK,
+};
+use crate::bond::{
+    BondImpactReportStruct, BondImpactType, BondInnerStruct, BondInterest, BondPeriod,
+    BondPeriodNumber, BondState, BondStruct, BondUnitAmount, BondUnitSaleLotStruct,
+};
+use crate::custodian::CustodianStruct;
+use crate::queue::ExpiryItem;
+use crate::{
+    AccountRegistry, AccountRoleGrant, BondAccrualQueue, BondCouponYield, BondHolderCount,
+    BondImpactReport, BondOrderBook, BondRegistry, BondSwap, BondUnitPackageLot,
+    BondUnitPackageRegistry, BondUnitSaleLotCount, BurnRequestCount, BurnRequestEverUSD, Config,
+    CustodianPendingBurn, CustodianRegistry, EverUSDBalance, MintRequestCount, MintRequestEverUSD,
+    Module, StorageVersion,
+};
+use frame_support::{
+    codec::{Decode, Encode},
+    dispatch::Vec,
+    storage::{
+        unhashed, IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap,
+        StoragePrefixedMap, StorageValue,
+    },
+    traits::Get,
+    weights::Weight,
+};
+
+/// Version of storage layout, written to genesis of new chains
+pub const STORAGE_VERSION: u16 = 7;
+
+/// Account data before roles were widened from u8 to RoleMask
+#[derive(Decode)]
+struct EvercityAccountStructV0<Moment> {
+    roles: u8,
+    #[codec(compact)]
+    identity: u64,
+    #[codec(compact)]
+    create_time: Moment,
+}
+
+/// Mint or burn request before requests were keyed by request id
+#[derive(Decode)]
+struct TokenRequestStructV0<Moment> {
+    #[codec(compact)]
+    amount: EverUSDBalance,
+    #[codec(compact)]
+    deadline: Moment,
+}
+
+/// Burn request before requests were routed to custodians
+#[derive(Encode, Decode)]
+struct TokenBurnRequestStructV2<Moment> {
+    #[codec(compact)]
+    amount: EverUSDBalance,
+    #[codec(compact)]
+    deadline: Moment,
+    reference: Vec<u8>,
+}
+
+/// Bond terms before impact KPIs, day count convention, option schedules, amortization,
+/// eligibility and lock-up were added
+#[derive(Decode)]
+struct BondInnerStructV3<Moment, Hash> {
+    docs_pack_root_hash_main: Hash,
+    docs_pack_root_hash_legal: Hash,
+    docs_pack_root_hash_finance: Hash,
+    docs_pack_root_hash_tech: Hash,
+    impact_data_type: BondImpactType,
+    impact_data_baseline: Vec<Option<u64>>,
+    impact_data_max_deviation_cap: Option<u64>,
+    impact_data_max_deviation_floor: Option<u64>,
+    #[codec(compact)]
+    impact_data_send_period: BondPeriod,
+    interest_rate_penalty_for_missed_report: Option<BondInterest>,
+    #[codec(compact)]
+    interest_rate_base_value: BondInterest,
+    interest_rate_margin_cap: Option<BondInterest>,
+    interest_rate_margin_floor: Option<BondInterest>,
+    interest_rate_start_period_value: Option<BondInterest>,
+    interest_pay_period: Option<BondPeriod>,
+    start_period: Option<BondPeriod>,
+    payment_period: BondPeriod,
+    #[codec(compact)]
+    bond_duration: BondPeriodNumber,
+    #[codec(compact)]
+    bond_finishing_period: BondPeriod,
+    #[codec(compact)]
+    mincap_deadline: Moment,
+    #[codec(compact)]
+    bond_units_mincap_amount: BondUnitAmount,
+    #[codec(compact)]
+    bond_units_maxcap_amount: BondUnitAmount,
+    #[codec(compact)]
+    bond_units_base_price: EverUSDBalance,
+}
+
+/// Bond before additional auditors, call premium and haircuts were added
+#[derive(Decode)]
+struct BondStructV3<AccountId, Moment, Hash> {
+    inner: BondInnerStructV3<Moment, Hash>,
+    issuer: AccountId,
+    manager: AccountId,
+    auditor: AccountId,
+    impact_reporter: AccountId,
+    #[codec(compact)]
+    issued_amount: BondUnitAmount,
+    #[codec(compact)]
+    creation_date: Moment,
+    #[codec(compact)]
+    booking_start_date: Moment,
+    #[codec(compact)]
+    active_start_date: Moment,
+    state: BondState,
+    #[codec(compact)]
+    bond_debit: EverUSDBalance,
+    #[codec(compact)]
+    bond_credit: EverUSDBalance,
+    #[codec(compact)]
+    coupon_yield: EverUSDBalance,
+    #[codec(compact)]
+    nonce: u64,
+}
+
+/// Impact report with the value of single KPI
+#[derive(Decode)]
+struct BondImpactReportStructV3 {
+    #[codec(compact)]
+    create_period: BondPeriod,
+    #[codec(compact)]
+    impact_data: u64,
+    signed: bool,
+}
+
+/// Sale lot before lots got identifiers
+#[derive(Decode)]
+struct BondUnitSaleLotStructV3<AccountId, Moment> {
+    #[codec(compact)]
+    deadline: Moment,
+    new_bondholder: AccountId,
+    #[codec(compact)]
+    bond_units: BondUnitAmount,
+    #[codec(compact)]
+    amount: EverUSDBalance,
+}
+
+pub fn on_runtime_upgrade<T: Config>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+    if StorageVersion::get() < 1 {
+        weight = weight.saturating_add(migrate_to_v1::<T>());
+        StorageVersion::put(1);
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+    }
+    if StorageVersion::get() < 2 {
+        weight = weight.saturating_add(migrate_to_v2::<T>());
+        StorageVersion::put(2);
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+    }
+    if StorageVersion::get() < 3 {
+        weight = weight.saturating_add(migrate_to_v3::<T>());
+        StorageVersion::put(3);
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+    }
+    if StorageVersion::get() < 4 {
+        weight = weight.saturating_add(migrate_to_v4::<T>());
+        StorageVersion::put(4);
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+    }
+    if StorageVersion::get() < 5 {
+        weight = weight.saturating_add(migrate_to_v5::<T>());
+        StorageVersion::put(5);
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+    }
+    if StorageVersion::get() < 6 {
+        weight = weight.saturating_add(migrate_to_v6::<T>());
+        StorageVersion::put(6);
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+    }
+    if StorageVersion::get() < 7 {
+        weight = weight.saturating_add(migrate_to_v7::<T>());
+        StorageVersion::put(7);
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+    }
+    weight
+}
+
+/// Widens account roles from u8 to RoleMask. Role grants are keyed by role,
+/// so they are moved to the keys with new role encoding
+fn migrate_to_v1<T: Config>() -> Weight {
+    let mut accounts: Vec<T::AccountId> = Vec::new();
+    AccountRegistry::<T>::translate::<EvercityAccountStructV0<T::Moment>, _>(|acc, old| {
+        accounts.push(acc);
+        Some(EvercityAccountStructT {
+            roles: old.roles.into(),
+            identity: old.identity,
+            create_time: old.create_time,
+        })
+    });
+
+    let mut grants: u64 = 0;
+    for acc in accounts.iter() {
+        for bit in 0..8 {
+            let role: RoleMask = 1 << bit;
+            let key = AccountRoleGrant::<T>::hashed_key_for(acc, role);
+            // second key is not hashed: old key ends with one byte of u8 role instead of four
+            let old_key = &key[..key.len() - 3];
+            if let Some(grant) = unhashed::take::<AccountRoleGrantStructOf<T>>(old_key) {
+                unhashed::put(&key, &grant);
+                grants += 1;
+            }
+        }
+    }
+
+    let accounts = accounts.len() as Weight;
+    T::DbWeight::get().reads_writes(accounts * 9, accounts + grants * 2)
+}
+
+/// Moves mint and burn requests, keyed by account, to the keys with request id.
+/// Requests get ids in order of accounts and empty reference
+fn migrate_to_v2<T: Config>() -> Weight {
+    let mut requests: u64 = 0;
+    let mut accounts: u64 = 0;
+    for (acc, _) in AccountRegistry::<T>::iter() {
+        accounts += 1;
+        let id = MintRequestCount::get();
+        let key = MintRequestEverUSD::<T>::hashed_key_for(&acc, id);
+        // old key ends with account, new key has request id after it
+        let old_key = &key[..key.len() - 8];
+        if let Some(old) = unhashed::take::<TokenRequestStructV0<T::Moment>>(old_key) {
+            let request = TokenMintRequestStruct {
+                amount: old.amount,
+                deadline: old.deadline,
+                reference: Vec::new(),
+            };
+            MintRequestEverUSD::<T>::insert(&acc, id, request);
+            MintRequestCount::put(id + 1);
+            requests += 1;
+        }
+
+        let id = BurnRequestCount::get();
+        let key = BurnRequestEverUSD::<T>::hashed_key_for(&acc, id);
+        let old_key = &key[..key.len() - 8];
+        if let Some(old) = unhashed::take::<TokenRequestStructV0<T::Moment>>(old_key) {
+            let request = TokenBurnRequestStructV2 {
+                amount: old.amount,
+                deadline: old.deadline,
+                reference: Vec::new(),
+            };
+            unhashed::put(&key, &request);
+            BurnRequestCount::put(id + 1);
+            requests += 1;
+        }
+    }
+
+    T::DbWeight::get().reads_writes(accounts * 5, requests * 3)
+}
+
+/// Registers accounts with Custodian role in CustodianRegistry, limiting every single mint
+/// by MaxMintAmount. EverUSD supply stays unattributed until Master assigns it to custodians.
+/// Burn requests are routed to the custodian, if the platform has only one. Otherwise they are
+/// declined, and accounts create them again, choosing the custodian
+fn migrate_to_v3<T: Config>() -> Weight {
+    let mut accounts: u64 = 0;
+    let mut custodians: Vec<T::AccountId> = Vec::new();
+    for (acc, account) in AccountRegistry::<T>::iter() {
+        accounts += 1;
+        if account.roles & CUSTODIAN_ROLE_MASK != 0 {
+            if !CustodianRegistry::<T>::contains_key(&acc) {
+                let custodian = CustodianStruct {
+                    mint_cap: T::MaxMintAmount::get(),
+                    ..Default::default()
+                };
+                CustodianRegistry::<T>::insert(&acc, custodian);
+            }
+            custodians.push(acc);
+        }
+    }
+
+    let route = if custodians.len() == 1 {
+        custodians.pop()
+    } else {
+        None
+    };
+    let mut requests: u64 = 0;
+    BurnRequestEverUSD::<T>::translate::<TokenBurnRequestStructV2<T::Moment>, _>(|_, _, old| {
+        requests += 1;
+        route.clone().map(|custodian| TokenBurnRequestStruct {
+            amount: old.amount,
+            deadline: old.deadline,
+            reference: old.reference,
+            custodian,
+        })
+    });
+
+    T::DbWeight::get().reads_writes(requests + accounts * 2, requests + accounts)
+}
+
+/// Rewrites bonds, impact reports and sale lots to current layout. New bond terms get values,
+/// which keep the behaviour of existing bonds: no additional KPIs and auditors, ACT/365 day count,
+/// no call, put and amortization schedules, no eligibility restrictions and lock-up.
+/// Impact data of sent reports becomes the value of main KPI, sale lots get ids in storage order
+fn migrate_to_v4<T: Config>() -> Weight {
+    let mut bonds: u64 = 0;
+    BondRegistry::<T>::translate::<BondStructV3<T::AccountId, T::Moment, T::Hash>, _>(|_, old| {
+        bonds += 1;
+        let inner = old.inner;
+        Some(BondStruct {
+            inner: BondInnerStruct {
+                docs_pack_root_hash_main: inner.docs_pack_root_hash_main,
+                docs_pack_root_hash_legal: inner.docs_pack_root_hash_legal,
+                docs_pack_root_hash_finance: inner.docs_pack_root_hash_finance,
+                docs_pack_root_hash_tech: inner.docs_pack_root_hash_tech,
+                impact_data_type: inner.impact_data_type,
+                impact_data_baseline: inner.impact_data_baseline,
+                impact_data_max_deviation_cap: inner.impact_data_max_deviation_cap,
+                impact_data_max_deviation_floor: inner.impact_data_max_deviation_floor,
+                impact_data_kpis: Vec::new(),
+                impact_data_send_period: inner.impact_data_send_period,
+                interest_rate_penalty_for_missed_report: inner
+                    .interest_rate_penalty_for_missed_report,
+                interest_rate_base_value: inner.interest_rate_base_value,
+                interest_rate_margin_cap: inner.interest_rate_margin_cap,
+                interest_rate_margin_floor: inner.interest_rate_margin_floor,
+                interest_rate_start_period_value: inner.interest_rate_start_period_value,
+                day_count_convention: Default::default(),
+                interest_pay_period: inner.interest_pay_period,
+                start_period: inner.start_period,
+                payment_period: inner.payment_period,
+                bond_duration: inner.bond_duration,
+                bond_finishing_period: inner.bond_finishing_period,
+                call_schedule: None,
+                put_schedule: None,
+                amortization_schedule: Vec::new(),
+                eligibility: Default::default(),
+                lockup_period: 0,
+                mincap_deadline: inner.mincap_deadline,
+                bond_units_mincap_amount: inner.bond_units_mincap_amount,
+                bond_units_maxcap_amount: inner.bond_units_maxcap_amount,
+                bond_units_base_price: inner.bond_units_base_price,
+            },
+            issuer: old.issuer,
+            manager: old.manager,
+            auditor: old.auditor,
+            auditors: Vec::new(),
+            auditor_quorum: 0,
+            impact_reporter: old.impact_reporter,
+            issued_amount: old.issued_amount,
+            creation_date: old.creation_date,
+            booking_start_date: old.booking_start_date,
+            active_start_date: old.active_start_date,
+            state: old.state,
+            call_premium_bps: 0,
+            principal_haircuts: Vec::new(),
+            bond_debit: old.bond_debit,
+            bond_credit: old.bond_credit,
+            coupon_yield: old.coupon_yield,
+            nonce: old.nonce,
+        })
+    });
+
+    let mut reports: u64 = 0;
+    BondImpactReport::translate::<Vec<BondImpactReportStructV3>, _>(|_, old| {
+        reports += 1;
+        Some(
+            old.into_iter()
+                .map(|report| BondImpactReportStruct {
+                    create_period: report.create_period,
+                    // unsent report has no impact data
+                    impact_data: if report.create_period > 0 {
+                        [report.impact_data].to_vec()
+                    } else {
+                        Vec::new()
+                    },
+                    signed: report.signed,
+                })
+                .collect(),
+        )
+    });
+
+    let mut lots: u64 = 0;
+    BondUnitPackageLot::<T>::translate::<Vec<BondUnitSaleLotStructV3<T::AccountId, T::Moment>>, _>(
+        |bond, _, old| {
+            lots += 1;
+            let id = BondUnitSaleLotCount::get(&bond);
+            BondUnitSaleLotCount::insert(&bond, id + old.len() as u64);
+            Some(
+                old.into_iter()
+                    .zip(id..)
+                    .map(|(lot, id)| BondUnitSaleLotStruct {
+                        id,
+                        deadline: lot.deadline,
+                        new_bondholder: lot.new_bondholder,
+                        bond_units: lot.bond_units,
+                        amount: lot.amount,
+                    })
+                    .collect(),
+            )
+        },
+    );
+
+    T::DbWeight::get().reads_writes(bonds + reports + lots * 2, bonds + reports + lots * 2)
+}
+
+/// Counts bondholders of every bond, holding non-empty Bond Unit packages
+fn migrate_to_v5<T: Config>() -> Weight {
+    let mut packages: u64 = 0;
+    let mut holders: u64 = 0;
+    for (bond, _, bond_units) in BondUnitPackageRegistry::<T>::iter() {
+        packages += 1;
+        if !bond_units.is_empty() {
+            BondHolderCount::mutate(&bond, |count| *count += 1);
+            holders += 1;
+        }
+    }
+
+    T::DbWeight::get().reads_writes(packages + holders, holders)
+}
+
+/// Replaces accrual queue, stored as single list, with queue slots and queues every ACTIVE
+/// or BANKRUPT bond, including bonds activated before the queue was added.
+/// Queues mint and burn requests, sale lots, orders and swaps for removal after deadline
+fn migrate_to_v6<T: Config>() -> Weight {
+    // single list was stored at the prefix of the map
+    unhashed::kill(&BondAccrualQueue::<T>::final_prefix());
+
+    let mut bonds: u64 = 0;
+    let mut queued: u64 = 0;
+    for (id, bond) in BondRegistry::<T>::iter() {
+        bonds += 1;
+        if matches!(bond.state, BondState::ACTIVE | BondState::BANKRUPT) {
+            let accrued = BondCouponYield::decode_len(&id).unwrap_or(0) as BondPeriodNumber;
+            if let Some(moment) = bond.coupon_accrual_moment(accrued) {
+                Module::<T>::schedule_accrual(id, moment);
+                queued += 1;
+            }
+        }
+    }
+
+    let mut items: u64 = 0;
+    for (acc, id, request) in MintRequestEverUSD::<T>::iter() {
+        Module::<T>::schedule_expiry(request.deadline, ExpiryItem::MintRequest(acc, id));
+        items += 1;
+    }
+    for (acc, id, request) in BurnRequestEverUSD::<T>::iter() {
+        Module::<T>::schedule_expiry(request.deadline, ExpiryItem::BurnRequest(acc, id));
+        items += 1;
+    }
+    for (bond, bondholder, lots) in BondUnitPackageLot::<T>::iter() {
+        for lot in lots {
+            let item = ExpiryItem::SaleLots(bond, bondholder.clone());
+            Module::<T>::schedule_expiry(lot.deadline, item);
+            items += 1;
+        }
+    }
+    for (bond, side, book) in BondOrderBook::<T>::iter() {
+        for order in book {
+            Module::<T>::schedule_expiry(order.deadline, ExpiryItem::Orders(bond, side));
+            items += 1;
+        }
+    }
+    for (id, swap) in BondSwap::<T>::iter() {
+        Module::<T>::schedule_expiry(swap.deadline, ExpiryItem::Swap(id));
+        items += 1;
+    }
+
+    T::DbWeight::get().reads_writes(bonds * 2 + items * 2, 1 + queued * 2 + items)
+}
+
+/// Sums pending burn requests, routed to every custodian, into CustodianPendingBurn
+fn migrate_to_v7<T: Config>() -> Weight {
+    let mut requests: u64 = 0;
+    for (_, _, request) in BurnRequestEverUSD::<T>::iter() {
+        CustodianPendingBurn::<T>::mutate(&request.custodian, |pending| {
+            *pending = pending.saturating_add(request.amount)
+        });
+        requests += 1;
+    }
+
+    T::DbWeight::get().reads_writes(requests * 2, requests)
+}
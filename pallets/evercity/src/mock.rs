@@ -2,7 +2,7 @@
 #![allow(clippy::large_enum_variant)]
 
 use crate::{self as pallet_evercity, *};
-use frame_support::parameter_types;
+use frame_support::{parameter_types, weights::Weight};
 use frame_support::sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
@@ -65,6 +65,7 @@ parameter_types! {
     pub const MintRequestTtl: u32 = DEFAULT_DAY_DURATION as u32 * 7 * 1000;
     pub const MaxMintAmount: EverUSDBalance = EVERUSD_MAX_MINT_AMOUNT;
     pub const TimeStep: BondPeriod = DEFAULT_DAY_DURATION;
+    pub const MaxAccrualWeight: Weight = 1_000_000_000_000;
 }
 
 impl Config for TestRuntime {
@@ -73,6 +74,8 @@ impl Config for TestRuntime {
     type MintRequestTtl = MintRequestTtl;
     type MaxMintAmount = MaxMintAmount;
     type TimeStep = TimeStep;
+    type MasterOrigin = frame_system::EnsureRoot<u64>;
+    type MaxAccrualWeight = MaxAccrualWeight;
     type WeightInfo = ();
     type OnAddAccount = ();
     type OnAddBond = ();
@@ -105,7 +108,7 @@ impl pallet_balances::Config for TestRuntime {
     type MaxLocks = MaxLocks;
 }
 // (AccountId, role)
-static ROLES: [(u64, u8); 8] = [
+static ROLES: [(u64, RoleMask); 8] = [
     (1_u64, MASTER_ROLE_MASK),
     (2_u64, CUSTODIAN_ROLE_MASK),
     (3_u64, ISSUER_ROLE_MASK),
@@ -166,12 +169,14 @@ pub fn get_test_bond() -> BondStruct {
             impact_data_baseline: vec![Some(20000_u64); PERIODS],
             impact_data_max_deviation_cap: Some(30000_u64),
             impact_data_max_deviation_floor: Some(14000_u64),
+            impact_data_kpis: Vec::new(),
             interest_rate_penalty_for_missed_report: Some(400), // +0.4%
 
             interest_rate_base_value: 2000,   // 2.0%
             interest_rate_margin_cap: Some(4000),   // 4.0%
             interest_rate_margin_floor: Some(1000), // 1.0%
             interest_rate_start_period_value: Some(1900),
+            day_count_convention: Default::default(),
             start_period: Some(120 * DEFAULT_DAY_DURATION),
             payment_period: 30 * DEFAULT_DAY_DURATION, // every month (30 days)
             interest_pay_period: Some(7 * DEFAULT_DAY_DURATION), // up to 7 days after  new period started
@@ -179,6 +184,11 @@ pub fn get_test_bond() -> BondStruct {
             impact_data_send_period: 10 * DEFAULT_DAY_DURATION, // 10 days before next period
             bond_duration: PERIODS as BondPeriodNumber,         // PERIODS periods for 30 days
             bond_finishing_period: 14 * DEFAULT_DAY_DURATION,   // 14 days after mature date
+            call_schedule: None,
+            put_schedule: None,
+            amortization_schedule: Vec::new(),
+            eligibility: Default::default(),
+            lockup_period: 0,
 
             bond_units_mincap_amount: 1000,
             bond_units_maxcap_amount: 1800,
@@ -188,6 +198,8 @@ pub fn get_test_bond() -> BondStruct {
         issuer: 0,
         manager: 0,
         auditor: 0,
+        auditors: Vec::new(),
+        auditor_quorum: 0,
         impact_reporter: 0,
 
         issued_amount: 0,
@@ -195,6 +207,8 @@ pub fn get_test_bond() -> BondStruct {
         active_start_date: Default::default(),
         creation_date: Default::default(),
         state: Default::default(),
+        call_premium_bps: 0,
+        principal_haircuts: Vec::new(),
 
         bond_debit: 0,
         bond_credit: 0,
@@ -216,12 +230,14 @@ pub fn get_test_bond_stable() -> BondStruct {
             impact_data_baseline: vec![None; PERIODS],
             impact_data_max_deviation_cap: None,
             impact_data_max_deviation_floor: None,
+            impact_data_kpis: Vec::new(),
             interest_rate_penalty_for_missed_report: None,
 
             interest_rate_base_value: 2000,   // 2.0%
             interest_rate_margin_cap: None,
             interest_rate_margin_floor: None,
             interest_rate_start_period_value: None,
+            day_count_convention: Default::default(),
             start_period: None,
             payment_period: bond::MIN_PAYMENT_PERIOD*DEFAULT_DAY_DURATION,
             interest_pay_period: None,
@@ -229,6 +245,11 @@ pub fn get_test_bond_stable() -> BondStruct {
             impact_data_send_period: 0,
             bond_duration: PERIODS as BondPeriodNumber,         // PERIODS periods for 30 days
             bond_finishing_period: 14 * DEFAULT_DAY_DURATION,   // 14 days after mature date
+            call_schedule: None,
+            put_schedule: None,
+            amortization_schedule: Vec::new(),
+            eligibility: Default::default(),
+            lockup_period: 0,
 
             bond_units_mincap_amount: 1000,
             bond_units_maxcap_amount: 1800,
@@ -238,6 +259,8 @@ pub fn get_test_bond_stable() -> BondStruct {
         issuer: 0,
         manager: 0,
         auditor: 0,
+        auditors: Vec::new(),
+        auditor_quorum: 0,
         impact_reporter: 0,
 
         issued_amount: 0,
@@ -245,6 +268,8 @@ pub fn get_test_bond_stable() -> BondStruct {
         active_start_date: Default::default(),
         creation_date: Default::default(),
         state: Default::default(),
+        call_premium_bps: 0,
+        principal_haircuts: Vec::new(),
 
         bond_debit: 0,
         bond_credit: 0,
@@ -0,0 +1,115 @@
+use crate::bond::BondUnitAmount;
+use crate::{EverUSDBalance, Expired};
+use frame_support::{
+    codec::{Decode, Encode},
+    dispatch::Vec,
+    sp_runtime::RuntimeDebug,
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Maximal amount of orders on each side of bond order book
+pub const MAX_BOND_ORDERS: usize = 100;
+
+pub type BondOrderId = u64;
+
+/// Side of bond order book
+#[allow(clippy::upper_case_acronyms)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq)]
+pub enum BondOrderSide {
+    /// Buy order, locks price * bond_units EverUSD of the owner
+    BUY,
+    /// Sell order, locks bond_units Bond Units of the owner
+    SELL,
+}
+
+impl Default for BondOrderSide {
+    fn default() -> Self {
+        BondOrderSide::BUY
+    }
+}
+
+impl BondOrderSide {
+    pub fn opposite(self) -> Self {
+        match self {
+            BondOrderSide::BUY => BondOrderSide::SELL,
+            BondOrderSide::SELL => BondOrderSide::BUY,
+        }
+    }
+
+    /// Checks if incoming order with given price can be matched with resting order
+    /// of the opposite side
+    pub fn is_crossing(self, price: EverUSDBalance, resting_price: EverUSDBalance) -> bool {
+        match self {
+            BondOrderSide::BUY => price >= resting_price,
+            BondOrderSide::SELL => price <= resting_price,
+        }
+    }
+
+    /// Checks if the price is better than other price for the side:
+    /// higher for buy orders and lower for sell orders
+    pub fn is_better(self, price: EverUSDBalance, other_price: EverUSDBalance) -> bool {
+        match self {
+            BondOrderSide::BUY => price > other_price,
+            BondOrderSide::SELL => price < other_price,
+        }
+    }
+}
+
+/// Order of bond order book
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct BondOrderStruct<AccountId, Moment> {
+    #[codec(compact)]
+    pub id: BondOrderId,
+    pub owner: AccountId,
+    /// Price of single bond unit
+    #[codec(compact)]
+    pub price: EverUSDBalance,
+    /// Amount of bond units, not filled yet
+    #[codec(compact)]
+    pub bond_units: BondUnitAmount,
+    /// Order is removed from order book after this deadline
+    #[codec(compact)]
+    pub deadline: Moment,
+}
+
+pub type BondOrderStructOf<T> = BondOrderStruct<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+>;
+
+impl<AccountId, Moment: core::cmp::PartialOrd> Expired<Moment> for BondOrderStruct<AccountId, Moment> {
+    fn is_expired(&self, now: Moment) -> bool {
+        self.deadline < now
+    }
+}
+
+impl<AccountId, Moment> BondOrderStruct<AccountId, Moment> {
+    /// Amount of EverUSD, locked by not filled part of buy order
+    pub fn locked_amount(&self) -> EverUSDBalance {
+        self.price.saturating_mul(self.bond_units as EverUSDBalance)
+    }
+}
+
+/// <pre>
+/// Method: order_book_insert(side, book, order)
+/// Arguments: side: BondOrderSide - side of the order book
+///            book: &mut Vec<BondOrderStruct> - orders of the side
+///            order: BondOrderStruct - new order
+///
+/// Inserts the order into the side of order book, keeping price-time priority:
+/// orders with better price come first, orders with the same price are sorted by id
+/// </pre>
+pub fn order_book_insert<AccountId, Moment>(
+    side: BondOrderSide,
+    book: &mut Vec<BondOrderStruct<AccountId, Moment>>,
+    order: BondOrderStruct<AccountId, Moment>,
+) {
+    let index = book
+        .iter()
+        .position(|item| side.is_better(order.price, item.price))
+        .unwrap_or_else(|| book.len());
+    book.insert(index, order);
+}
@@ -38,6 +38,7 @@ impl PeriodDescr {
 }
 
 /// Struct, storing per-period coupon_yield and effective interest_rate for given bond
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
 pub struct PeriodYield {
     /// bond cumulative accrued yield for this period
@@ -56,7 +57,7 @@ pub struct PeriodYield {
 pub struct PeriodDataStruct {
     pub interest_rate: BondInterest,
     pub create_period: BondPeriod,
-    pub impact_data: u64,
+    pub impact_data: Vec<u64>,
     pub signed: bool,
 }
 
@@ -0,0 +1,42 @@
+use crate::account::TokenRequestId;
+use crate::bond::{BondId, BondPeriod};
+use crate::order::BondOrderSide;
+use crate::swap::BondSwapId;
+use frame_support::{
+    codec::{Decode, Encode},
+    sp_runtime::{
+        traits::{SaturatedConversion, UniqueSaturatedInto},
+        RuntimeDebug,
+    },
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Slot of the queue, processed in on_initialize().
+/// Queued items are grouped by slots of TimeStep duration
+pub type QueueSlot = u64;
+/// Index of the item in the queue slot
+pub type QueueIndex = u32;
+
+/// Item, which is removed from storage after its deadline
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub enum ExpiryItem<AccountId> {
+    MintRequest(AccountId, TokenRequestId),
+    BurnRequest(AccountId, TokenRequestId),
+    /// Sale lots of the bondholder
+    SaleLots(BondId, AccountId),
+    /// Order book side of the bond
+    Orders(BondId, BondOrderSide),
+    Swap(BondSwapId),
+}
+
+pub type ExpiryItemOf<T> = ExpiryItem<<T as frame_system::Config>::AccountId>;
+
+/// Returns the slot of the moment. Slot duration is time_step seconds
+pub fn queue_slot<Moment: UniqueSaturatedInto<u64>>(
+    moment: Moment,
+    time_step: BondPeriod,
+) -> QueueSlot {
+    moment.saturated_into::<u64>() / (time_step as u64 * 1000).max(1)
+}
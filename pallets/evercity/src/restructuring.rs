@@ -0,0 +1,99 @@
+use crate::bond::{BondInnerStruct, BondInterest, BondPeriodNumber, BondUnitAmount};
+use crate::Expired;
+use frame_support::{
+    codec::{Decode, Encode},
+    sp_runtime::RuntimeDebug,
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// New terms of BANKRUPT bond, proposed by Issuer or Manager
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct BondRestructuringTermsStruct {
+    /// Amount of payment periods, added to bond duration (extends maturity date)
+    #[codec(compact)]
+    pub duration_extension: BondPeriodNumber,
+    /// New base coupon interest rate
+    #[codec(compact)]
+    pub interest_rate_base_value: BondInterest,
+    /// New upper margin of interest rate
+    pub interest_rate_margin_cap: Option<BondInterest>,
+    /// New lower margin of interest rate
+    pub interest_rate_margin_floor: Option<BondInterest>,
+    /// Reduction of Bond Unit par value, basis points
+    #[codec(compact)]
+    pub haircut_bps: u32,
+}
+
+impl BondRestructuringTermsStruct {
+    /// Rewrites bond terms. Impact data baselines and amortization schedule
+    /// are extended to new bond duration: baselines repeat the value of the last period,
+    /// nothing is amortized in new periods. Haircut is not applied here, because it
+    /// reduces principal from the restructuring period only. Validity of new terms
+    /// should be checked by caller
+    pub fn apply<Moment, Hash>(&self, inner: &mut BondInnerStruct<Moment, Hash>) {
+        let periods = inner.get_periods().saturating_add(self.duration_extension);
+        inner.bond_duration = inner.bond_duration.saturating_add(self.duration_extension);
+        let duration = inner.bond_duration as usize;
+
+        let baseline = inner.impact_data_baseline.last().copied().flatten();
+        inner.impact_data_baseline.resize(duration, baseline);
+        for kpi in inner.impact_data_kpis.iter_mut() {
+            let baseline = kpi.impact_data_baseline.last().copied().unwrap_or(0);
+            kpi.impact_data_baseline.resize(duration, baseline);
+        }
+        if !inner.amortization_schedule.is_empty() {
+            inner.amortization_schedule.resize(periods as usize, 0);
+        }
+
+        inner.interest_rate_base_value = self.interest_rate_base_value;
+        inner.interest_rate_margin_cap = self.interest_rate_margin_cap;
+        inner.interest_rate_margin_floor = self.interest_rate_margin_floor;
+    }
+}
+
+/// Restructuring proposal of BANKRUPT bond. Bondholders vote with their Bond Units.
+/// Proposal is approved when more than half of issued Bond Units vote for it,
+/// and rejected when at least half of issued Bond Units vote against it
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct BondRestructuringStruct<AccountId, Moment> {
+    /// Issuer or Manager, who proposed new terms
+    pub proposer: AccountId,
+    pub terms: BondRestructuringTermsStruct,
+    /// Voting is closed after this deadline
+    #[codec(compact)]
+    pub deadline: Moment,
+    /// Bond Units, voted for the proposal
+    #[codec(compact)]
+    pub votes_for: BondUnitAmount,
+    /// Bond Units, voted against the proposal
+    #[codec(compact)]
+    pub votes_against: BondUnitAmount,
+}
+
+pub type BondRestructuringStructOf<T> = BondRestructuringStruct<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+>;
+
+impl<AccountId, Moment: core::cmp::PartialOrd> Expired<Moment>
+    for BondRestructuringStruct<AccountId, Moment>
+{
+    fn is_expired(&self, now: Moment) -> bool {
+        self.deadline < now
+    }
+}
+
+impl<AccountId, Moment> BondRestructuringStruct<AccountId, Moment> {
+    /// Checks if more than half of issued Bond Units voted for the proposal
+    pub fn is_approved(&self, issued_amount: BondUnitAmount) -> bool {
+        self.votes_for as u64 * 2 > issued_amount as u64
+    }
+
+    /// Checks if the proposal cannot be approved anymore
+    pub fn is_rejected(&self, issued_amount: BondUnitAmount) -> bool {
+        self.votes_against as u64 * 2 >= issued_amount as u64
+    }
+}
@@ -1,8 +1,26 @@
-use frame_support::dispatch::Vec;
+use crate::account::EvercityAccountStructT;
+use crate::bond::{BondId, BondStruct, BondUnitPackage};
+use crate::period::{PeriodDataStruct, PeriodYield};
+use crate::EverUSDBalance;
+use frame_support::{codec::Codec, dispatch::Vec};
 
 sp_api::decl_runtime_apis! {
-    pub trait BondApi {
+    pub trait BondApi<AccountId, Moment, Hash> where
+        AccountId: Codec,
+        Moment: Codec,
+        Hash: Codec,
+    {
         /// delegate call to the pallet get_impact_reports()
-        fn get_impact_reports(bond: crate::BondId)->Vec<crate::PeriodDataStruct>;
+        fn get_impact_reports(bond: BondId)->Vec<PeriodDataStruct>;
+        /// delegate call to the pallet get_bond()
+        fn get_bond(bond: BondId)->BondStruct<AccountId, Moment, Hash>;
+        /// delegate call to the pallet get_coupon_yields()
+        fn get_bond_yields(bond: BondId)->Vec<PeriodYield>;
+        /// delegate call to the pallet bond_holder_packages()
+        fn get_bondholder_packages(bond: BondId, bondholder: AccountId)->Vec<BondUnitPackage>;
+        /// delegate call to the pallet balance_everusd()
+        fn get_everusd_balance(account: AccountId)->EverUSDBalance;
+        /// delegate call to the pallet account_registry()
+        fn get_account(account: AccountId)->EvercityAccountStructT<Moment>;
     }
 }
@@ -0,0 +1,50 @@
+use crate::bond::{BondId, BondUnitAmount};
+use crate::{EverUSDBalance, Expired};
+use frame_support::{
+    codec::{Decode, Encode},
+    sp_runtime::RuntimeDebug,
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+pub type BondSwapId = u64;
+
+/// Delivery-versus-payment swap: maker offers Bond Units of one bond and, optionally,
+/// EverUSD for Bond Units of another bond. Both legs are settled at once, when
+/// the counterparty accepts the swap
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct BondSwapStruct<AccountId, Moment> {
+    /// Swap identifier. Assigned by bond_swap_offer()
+    #[codec(compact)]
+    pub id: BondSwapId,
+    pub maker: AccountId,
+    /// If set (can be empty) - then only counterparty can accept the swap
+    pub counterparty: AccountId,
+    /// Bond, which Bond Units are given by maker
+    pub give_bond: BondId,
+    #[codec(compact)]
+    pub give_units: BondUnitAmount,
+    /// EverUSD, paid by maker in addition to Bond Units.
+    /// Locked on the offer until the swap is settled or cancelled
+    #[codec(compact)]
+    pub amount: EverUSDBalance,
+    /// Bond, which Bond Units are taken from counterparty
+    pub take_bond: BondId,
+    #[codec(compact)]
+    pub take_units: BondUnitAmount,
+    /// Swap can be accepted only before this deadline
+    #[codec(compact)]
+    pub deadline: Moment,
+}
+
+pub type BondSwapStructOf<T> = BondSwapStruct<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_timestamp::Config>::Moment,
+>;
+
+impl<AccountId, Moment: core::cmp::PartialOrd> Expired<Moment> for BondSwapStruct<AccountId, Moment> {
+    fn is_expired(&self, now: Moment) -> bool {
+        self.deadline < now
+    }
+}
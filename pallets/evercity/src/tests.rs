@@ -1,15 +1,37 @@
 #![allow(clippy::from_over_into)]
 use frame_support::{
-    assert_noop, assert_ok, dispatch::DispatchResult, sp_io, sp_std::ops::RangeInclusive,
+    assert_err, assert_noop, assert_ok,
+    codec::{Compact, Encode},
+    dispatch::{DispatchError, DispatchResult},
+    sp_io,
+    sp_runtime::traits::{BlakeTwo256, Hash},
+    sp_std::ops::RangeInclusive,
+    storage::{unhashed, StorageDoubleMap, StorageMap, StoragePrefixedMap, StorageValue},
+    traits::{Get, OnInitialize},
     Blake2_256, StorageHasher,
 };
 
-use crate::bond::transfer_bond_units;
+use crate::account::{
+    AccountRoleGrantStruct, MAX_ACCOUNT_TOKEN_REQUESTS, MAX_TOKEN_REQUEST_REFERENCE_LEN,
+};
+use crate::bond::{
+    calc_coupon_yield, transfer_bond_units, BondCallScheduleStruct, BondDayCountConvention,
+    BondImpactKpiStruct, BondImpactType, BondPutScheduleStruct, MAX_BOND_HOLDERS, MAX_IMPACT_KPIS,
+};
+use crate::document::BondDocumentType;
+use crate::governance::BondProposalAction;
+use crate::migration;
 use crate::mock::*;
+use crate::order::BondOrderSide;
+use crate::queue::{queue_slot, ExpiryItem};
+use crate::restructuring::BondRestructuringTermsStruct;
 use crate::{
     BondId, BondImpactReportStruct, BondInnerStructOf, BondPeriodNumber, BondState, BondStructOf,
     BondUnitAmount, BondUnitPackage, BondUnitSaleLotStructOf, Error, EverUSDBalance, Module,
-    AUDITOR_ROLE_MASK, DEFAULT_DAY_DURATION, ISSUER_ROLE_MASK, MASTER_ROLE_MASK,
+    AUDITOR_ROLE_MASK, COMPLIANCE_OFFICER_ROLE_MASK, CUSTODIAN_ROLE_MASK, DEFAULT_DAY_DURATION,
+    ISSUER_ROLE_MASK,
+    KYC_PROVIDER_ROLE_MASK, MASTER_ROLE_MASK, PAYING_AGENT_ROLE_MASK, RATING_AGENT_ROLE_MASK,
+    REGISTRAR_ROLE_MASK,
 };
 
 type Evercity = Module<TestRuntime>;
@@ -39,8 +61,9 @@ fn bond_current_period(bond: &BondStruct, now: Moment) -> u32 {
 
 /// Auxiliary function that replenish account balance
 fn add_token(id: AccountId, amount: EverUSDBalance) -> DispatchResult {
-    Evercity::token_mint_request_create_everusd(Origin::signed(id), amount)?;
-    Evercity::token_mint_request_confirm_everusd(Origin::signed(CUSTODIAN_ID), id, amount)
+    let request = Evercity::mint_request_count();
+    Evercity::token_mint_request_create_everusd(Origin::signed(id), amount, Vec::new())?;
+    Evercity::token_mint_request_confirm_everusd(Origin::signed(CUSTODIAN_ID), id, request, amount)
 }
 
 /// Converts days into milliseconds
@@ -195,6 +218,560 @@ fn it_denies_add_and_set_roles_for_non_master() {
     });
 }
 
+#[test]
+fn it_restricts_roles_by_grant() {
+    const MASTER: u64 = 1;
+    const ISSUER: u64 = 3;
+    const AUDITOR: u64 = 5;
+    let bondid: BondId = "BOND".into();
+    let other_bondid: BondId = "BOND1".into();
+
+    new_test_ext().execute_with(|| {
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(10_000);
+        let grant = AccountRoleGrantStruct {
+            expiry: Some(20_000),
+            bonds: vec![other_bondid],
+        };
+        assert_noop!(
+            Evercity::account_set_role_grant(
+                Origin::signed(MASTER),
+                AUDITOR,
+                ISSUER_ROLE_MASK,
+                Some(grant.clone())
+            ),
+            RuntimeError::AccountRoleParamIncorrect
+        );
+        assert_noop!(
+            Evercity::account_set_role_grant(
+                Origin::signed(MASTER),
+                AUDITOR,
+                AUDITOR_ROLE_MASK | ISSUER_ROLE_MASK,
+                Some(grant.clone())
+            ),
+            RuntimeError::AccountRoleParamIncorrect
+        );
+        assert_noop!(
+            Evercity::account_set_role_grant(
+                Origin::signed(AUDITOR),
+                AUDITOR,
+                AUDITOR_ROLE_MASK,
+                Some(grant.clone())
+            ),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_ok!(Evercity::account_set_role_grant(
+            Origin::signed(MASTER),
+            AUDITOR,
+            AUDITOR_ROLE_MASK,
+            Some(grant)
+        ));
+
+        // scoped role is valid only in listed bonds
+        assert!(!Evercity::account_is_auditor(&AUDITOR));
+        assert!(Evercity::account_has_role(&AUDITOR, AUDITOR_ROLE_MASK, Some(&other_bondid)));
+        assert!(!Evercity::account_has_role(&AUDITOR, AUDITOR_ROLE_MASK, Some(&bondid)));
+        assert_ok!(Evercity::bond_add_new(
+            Origin::signed(ISSUER),
+            bondid,
+            get_test_bond().inner
+        ));
+        assert_noop!(
+            Evercity::bond_set_auditor(Origin::signed(MASTER), bondid, AUDITOR),
+            RuntimeError::AccountRoleParamIncorrect
+        );
+
+        // expired role is not valid anywhere
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(20_000);
+        assert!(!Evercity::account_has_role(&AUDITOR, AUDITOR_ROLE_MASK, Some(&other_bondid)));
+
+        // removed restriction makes the role permanent and global again
+        assert_ok!(Evercity::account_set_role_grant(
+            Origin::signed(MASTER),
+            AUDITOR,
+            AUDITOR_ROLE_MASK,
+            None
+        ));
+        assert!(Evercity::account_is_auditor(&AUDITOR));
+        assert_ok!(Evercity::bond_set_auditor(Origin::signed(MASTER), bondid, AUDITOR));
+    });
+}
+
+#[test]
+fn it_checks_permissions_of_agent_roles() {
+    const MASTER: u64 = 1;
+    const ISSUER: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const REGISTRAR: u64 = 101;
+    const PAYING_AGENT: u64 = 102;
+    const RATING_AGENT: u64 = 103;
+    const COMPLIANCE_OFFICER: u64 = 104;
+    let bondid: BondId = "BOND".into();
+    let prepared_bondid: BondId = "BOND1".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ISSUER, get_test_bond().inner);
+
+        assert_noop!(
+            Evercity::account_add_with_role_and_data(
+                Origin::signed(MASTER),
+                REGISTRAR,
+                KYC_PROVIDER_ROLE_MASK << 1,
+                0
+            ),
+            RuntimeError::AccountRoleParamIncorrect
+        );
+        for (acc, role) in [
+            (REGISTRAR, REGISTRAR_ROLE_MASK),
+            (PAYING_AGENT, PAYING_AGENT_ROLE_MASK),
+            (RATING_AGENT, RATING_AGENT_ROLE_MASK),
+            (COMPLIANCE_OFFICER, COMPLIANCE_OFFICER_ROLE_MASK),
+        ]
+        .iter()
+        {
+            assert_ok!(Evercity::account_add_with_role_and_data(
+                Origin::signed(MASTER),
+                *acc,
+                *role,
+                0
+            ));
+        }
+        assert!(Evercity::account_is_registrar(&REGISTRAR));
+        assert!(Evercity::account_is_paying_agent(&PAYING_AGENT));
+        assert!(Evercity::account_is_rating_agent(&RATING_AGENT));
+        assert!(Evercity::account_is_compliance_officer(&COMPLIANCE_OFFICER));
+
+        // Registrar keeps documentation of any bond
+        assert_ok!(Evercity::bond_add_new(
+            Origin::signed(ISSUER),
+            prepared_bondid,
+            get_test_bond().inner
+        ));
+        assert_ok!(Evercity::bond_document_add(
+            Origin::signed(REGISTRAR),
+            prepared_bondid,
+            BondDocumentType::LEGAL,
+            BlakeTwo256::hash(b"prospectus"),
+            b"ipfs://prospectus".to_vec()
+        ));
+        assert_eq!(Evercity::bond_documents(&prepared_bondid).len(), 1);
+
+        // Paying Agent pays to bond fund on behalf of Issuer
+        assert_ok!(add_token(INVESTOR1, 1000));
+        assert_ok!(Evercity::transfer_everusd(
+            Origin::signed(INVESTOR1),
+            PAYING_AGENT,
+            1000
+        ));
+        let bond_debit = Evercity::get_bond(&bondid).bond_debit;
+        assert_noop!(
+            Evercity::bond_deposit_everusd(Origin::signed(INVESTOR1), bondid, 1000),
+            RuntimeError::BondAccessDenied
+        );
+        assert_ok!(Evercity::bond_deposit_everusd(
+            Origin::signed(PAYING_AGENT),
+            bondid,
+            1000
+        ));
+        assert_eq!(Evercity::get_bond(&bondid).bond_debit, bond_debit + 1000);
+
+        // Rating Agent publishes credit rating
+        assert_noop!(
+            Evercity::bond_credit_rating_set(
+                Origin::signed(REGISTRAR),
+                bondid,
+                b"AA+".to_vec(),
+                BlakeTwo256::hash(b"report")
+            ),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_noop!(
+            Evercity::bond_credit_rating_set(
+                Origin::signed(RATING_AGENT),
+                bondid,
+                b"TOO LONG RATING".to_vec(),
+                BlakeTwo256::hash(b"report")
+            ),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_ok!(Evercity::bond_credit_rating_set(
+            Origin::signed(RATING_AGENT),
+            bondid,
+            b"AA+".to_vec(),
+            BlakeTwo256::hash(b"report")
+        ));
+        let rating = Evercity::bond_credit_rating(&bondid).unwrap();
+        assert_eq!(rating.agent, RATING_AGENT);
+        assert_eq!(rating.rating, b"AA+".to_vec());
+
+        // Compliance Officer disables accounts, except Master
+        assert_noop!(
+            Evercity::account_disable(Origin::signed(COMPLIANCE_OFFICER), MASTER),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_noop!(
+            Evercity::account_disable(Origin::signed(RATING_AGENT), INVESTOR2),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_ok!(Evercity::account_disable(
+            Origin::signed(COMPLIANCE_OFFICER),
+            INVESTOR2
+        ));
+        assert!(!Evercity::account_is_registered(&INVESTOR2));
+    });
+}
+
+#[test]
+fn it_migrates_account_roles_to_role_mask() {
+    const AUDITOR: u64 = 101;
+
+    new_test_ext().execute_with(|| {
+        // account and role grant in storage layout with u8 roles
+        let key = crate::AccountRegistry::<TestRuntime>::hashed_key_for(&AUDITOR);
+        unhashed::put(
+            &key,
+            &(
+                (AUDITOR_ROLE_MASK | ISSUER_ROLE_MASK) as u8,
+                Compact(50_u64),
+                Compact(1000_u64),
+            ),
+        );
+        let grant = AccountRoleGrantStruct {
+            expiry: Some(20_000_u64),
+            bonds: Vec::new(),
+        };
+        let grant_key =
+            crate::AccountRoleGrant::<TestRuntime>::hashed_key_for(&AUDITOR, AUDITOR_ROLE_MASK);
+        unhashed::put(&grant_key[..grant_key.len() - 3], &grant);
+        crate::StorageVersion::put(0);
+
+        migration::on_runtime_upgrade::<TestRuntime>();
+
+        assert_eq!(Evercity::storage_version(), migration::STORAGE_VERSION);
+        let account = Evercity::account_registry(&AUDITOR);
+        assert_eq!(account.roles, AUDITOR_ROLE_MASK | ISSUER_ROLE_MASK);
+        assert_eq!(account.identity, 50);
+        assert_eq!(account.create_time, 1000);
+        assert_eq!(
+            Evercity::account_role_grant(&AUDITOR, AUDITOR_ROLE_MASK),
+            Some(grant)
+        );
+        assert!(Evercity::account_is_issuer(&3));
+
+        // migration is applied once
+        let weight = migration::on_runtime_upgrade::<TestRuntime>();
+        assert_eq!(weight, <TestRuntime as frame_system::Config>::DbWeight::get().reads(1));
+    });
+}
+
+#[test]
+fn it_migrates_token_requests_to_request_ids() {
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+
+    new_test_ext().execute_with(|| {
+        // requests in storage layout, keyed by account
+        let key = crate::MintRequestEverUSD::<TestRuntime>::hashed_key_for(&INVESTOR1, 0);
+        unhashed::put(
+            &key[..key.len() - 8],
+            &(Compact(1000_u64), Compact(20_000_u64)),
+        );
+        let key = crate::BurnRequestEverUSD::<TestRuntime>::hashed_key_for(&INVESTOR2, 0);
+        unhashed::put(
+            &key[..key.len() - 8],
+            &(Compact(500_u64), Compact(30_000_u64)),
+        );
+        crate::StorageVersion::put(1);
+
+        migration::on_runtime_upgrade::<TestRuntime>();
+
+        assert_eq!(Evercity::storage_version(), migration::STORAGE_VERSION);
+        let request = Evercity::mint_request_everusd(&INVESTOR1, 0).unwrap();
+        assert_eq!((request.amount, request.deadline), (1000, 20_000));
+        assert!(request.reference.is_empty());
+        let request = Evercity::burn_request_everusd(&INVESTOR2, 0).unwrap();
+        assert_eq!((request.amount, request.deadline), (500, 30_000));
+        // the only custodian of the platform confirms the request
+        assert_eq!(request.custodian, CUSTODIAN_ID);
+        assert_eq!(Evercity::custodian_pending_burn(&CUSTODIAN_ID), 500);
+        assert_eq!(Evercity::mint_request_count(), 1);
+        assert_eq!(Evercity::burn_request_count(), 1);
+    });
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Evercity::account_add_with_role_and_data(
+            Origin::signed(1),
+            101,
+            CUSTODIAN_ROLE_MASK,
+            0
+        ));
+        let key = crate::BurnRequestEverUSD::<TestRuntime>::hashed_key_for(&INVESTOR2, 0);
+        unhashed::put(
+            &key[..key.len() - 8],
+            &(Compact(500_u64), Compact(30_000_u64)),
+        );
+        crate::StorageVersion::put(1);
+
+        migration::on_runtime_upgrade::<TestRuntime>();
+
+        // request is declined, when several custodians can pay out USD
+        assert!(Evercity::burn_request_everusd(&INVESTOR2, 0).is_none());
+        assert!(Evercity::custodian_registry(&101).is_some());
+    });
+}
+
+/// Encodes the bond in storage layout of version 3, before bond terms were extended
+fn encode_bond_v3(bond: &BondStruct) -> Vec<u8> {
+    let inner = &bond.inner;
+    let mut data = (
+        inner.docs_pack_root_hash_main,
+        inner.docs_pack_root_hash_legal,
+        inner.docs_pack_root_hash_finance,
+        inner.docs_pack_root_hash_tech,
+        &inner.impact_data_type,
+        &inner.impact_data_baseline,
+        inner.impact_data_max_deviation_cap,
+        inner.impact_data_max_deviation_floor,
+        Compact(inner.impact_data_send_period),
+        inner.interest_rate_penalty_for_missed_report,
+        Compact(inner.interest_rate_base_value),
+        inner.interest_rate_margin_cap,
+        inner.interest_rate_margin_floor,
+        inner.interest_rate_start_period_value,
+        inner.interest_pay_period,
+        inner.start_period,
+        inner.payment_period,
+        Compact(inner.bond_duration),
+    )
+        .encode();
+    data.extend(
+        (
+            Compact(inner.bond_finishing_period),
+            Compact(inner.mincap_deadline),
+            Compact(inner.bond_units_mincap_amount),
+            Compact(inner.bond_units_maxcap_amount),
+            Compact(inner.bond_units_base_price),
+        )
+            .encode(),
+    );
+    data.extend(
+        (
+            bond.issuer,
+            bond.manager,
+            bond.auditor,
+            bond.impact_reporter,
+            Compact(bond.issued_amount),
+            Compact(bond.creation_date),
+            Compact(bond.booking_start_date),
+            Compact(bond.active_start_date),
+            &bond.state,
+            Compact(bond.bond_debit),
+            Compact(bond.bond_credit),
+            Compact(bond.coupon_yield),
+            Compact(bond.nonce),
+        )
+            .encode(),
+    );
+    data
+}
+
+#[test]
+fn it_migrates_bonds_to_current_layout() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let chain_bond_item = Evercity::get_bond(&bondid);
+
+        // bond, impact reports and sale lot in storage layout of version 3
+        let key = crate::BondRegistry::<TestRuntime>::hashed_key_for(&bondid);
+        unhashed::put_raw(&key, &encode_bond_v3(&chain_bond_item));
+        let reports = vec![
+            (Compact(1_u32), Compact(25_000_u64), true),
+            (Compact(0_u32), Compact(0_u64), false),
+        ];
+        unhashed::put(&crate::BondImpactReport::hashed_key_for(&bondid), &reports);
+        // lots without buyer restriction and for INVESTOR2
+        let lot1 = (
+            Compact(100_000_u64),
+            0_u64,
+            Compact(5_u32),
+            Compact(1000_u64),
+        );
+        let lot2 = (
+            Compact(200_000_u64),
+            6_u64,
+            Compact(3_u32),
+            Compact(700_u64),
+        );
+        unhashed::put(
+            &crate::BondUnitPackageLot::<TestRuntime>::hashed_key_for(&bondid, &INVESTOR1),
+            &vec![lot1, lot2],
+        );
+        crate::StorageVersion::put(3);
+
+        migration::on_runtime_upgrade::<TestRuntime>();
+
+        assert_eq!(Evercity::storage_version(), migration::STORAGE_VERSION);
+        assert_eq!(Evercity::get_bond(&bondid), chain_bond_item);
+        let reports = Evercity::impact_reports(&bondid);
+        assert_eq!(reports[0].impact_data, vec![25_000]);
+        assert!(reports[0].signed);
+        assert_eq!(reports[1], BondImpactReportStruct::default());
+        let lots = Evercity::bond_unit_lots(&bondid, &INVESTOR1);
+        assert_eq!((lots[0].id, lots[1].id), (0, 1));
+        assert_eq!(
+            (lots[1].new_bondholder, lots[1].bond_units, lots[1].amount),
+            (6, 3, 700)
+        );
+        assert_eq!(Evercity::bond_unit_lot_count(&bondid), 2);
+    });
+}
+
+#[test]
+fn it_counts_bondholders_on_upgrade() {
+    const ACCOUNT: u64 = 3;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        crate::BondHolderCount::remove(&bondid);
+        crate::StorageVersion::put(4);
+
+        migration::on_runtime_upgrade::<TestRuntime>();
+
+        assert_eq!(Evercity::storage_version(), migration::STORAGE_VERSION);
+        assert_eq!(Evercity::bond_holder_count(&bondid), 2);
+    });
+}
+
+#[test]
+fn it_queues_bonds_and_requests_on_upgrade() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(INVESTOR1),
+            1000,
+            Vec::new()
+        ));
+        let request = Evercity::mint_request_count() - 1;
+        let start_period_end = Evercity::bond_accrual_moment(&bondid).unwrap();
+        let accrual_slot = queue_slot(start_period_end, DEFAULT_DAY_DURATION);
+        let deadline = Evercity::mint_request_everusd(&INVESTOR1, request)
+            .unwrap()
+            .deadline;
+        let expiry_slot = queue_slot(deadline, DEFAULT_DAY_DURATION);
+
+        // queues in storage layout of version 5
+        crate::BondAccrualQueue::<TestRuntime>::remove_all();
+        crate::BondAccrualQueueLen::remove_all();
+        crate::BondAccrualMoment::<TestRuntime>::remove(&bondid);
+        crate::BondAccrualCursor::kill();
+        crate::ExpiryQueue::<TestRuntime>::remove_all();
+        crate::ExpiryQueueLen::remove_all();
+        crate::ExpiryCursor::kill();
+        let old_queue = vec![(start_period_end, bondid)];
+        let key = crate::BondAccrualQueue::<TestRuntime>::final_prefix();
+        unhashed::put(&key, &old_queue);
+        crate::StorageVersion::put(5);
+
+        migration::on_runtime_upgrade::<TestRuntime>();
+
+        assert_eq!(Evercity::storage_version(), migration::STORAGE_VERSION);
+        assert!(!unhashed::exists(&key));
+        assert_eq!(
+            Evercity::bond_accrual_queue(accrual_slot, 0),
+            Some((start_period_end, bondid))
+        );
+        assert_eq!(
+            Evercity::bond_accrual_moment(&bondid),
+            Some(start_period_end)
+        );
+        assert_eq!(
+            Evercity::expiry_queue(expiry_slot, 0),
+            Some((deadline, ExpiryItem::MintRequest(INVESTOR1, request)))
+        );
+        assert_eq!(Evercity::expiry_queue_len(expiry_slot), 1);
+
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(start_period_end + 1);
+        Evercity::on_initialize(1);
+        assert_eq!(Evercity::get_coupon_yields(&bondid).len(), 1);
+    });
+}
+
+#[test]
+fn it_allows_master_actions_from_master_origin() {
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(12345);
+        assert_ok!(Evercity::account_add_with_role_and_data(
+            Origin::root(),
+            101,
+            ISSUER_ROLE_MASK,
+            88u64
+        ));
+        assert!(Evercity::account_is_issuer(&101));
+        assert_ok!(Evercity::account_set_with_role_and_data(
+            Origin::root(),
+            101,
+            AUDITOR_ROLE_MASK,
+            88u64
+        ));
+        assert!(Evercity::account_is_auditor(&101));
+        assert_ok!(Evercity::account_disable(Origin::root(), 101));
+        // MasterOrigin has no caller account to name in events
+        assert!(System::events().iter().any(|record| record.event
+            == Event::pallet_evercity(crate::RawEvent::AccountDisable(None, 101))));
+
+        // MasterOrigin has no caller account, so the default account can be managed too
+        assert_ok!(Evercity::account_add_with_role_and_data(
+            Origin::root(),
+            0,
+            AUDITOR_ROLE_MASK,
+            0
+        ));
+        assert_ok!(Evercity::account_set_with_role_and_data(
+            Origin::root(),
+            0,
+            ISSUER_ROLE_MASK,
+            0
+        ));
+        assert_ok!(Evercity::account_set_role_grant(
+            Origin::root(),
+            0,
+            AUDITOR_ROLE_MASK,
+            None
+        ));
+        assert_ok!(Evercity::account_disable(Origin::root(), 0));
+        assert!(!Evercity::account_is_auditor(&0));
+
+        let mut bond = get_test_bond().inner;
+        bond.mincap_deadline = 50000;
+        assert_ok!(Evercity::bond_add_new(Origin::signed(3), bondid, bond));
+        assert_ok!(Evercity::bond_release(Origin::root(), bondid, 0));
+        assert_eq!(Evercity::get_bond(&bondid).state, BondState::BOOKING);
+        assert!(System::events().iter().any(|record| record.event
+            == Event::pallet_evercity(crate::RawEvent::BondReleased(None, bondid))));
+
+        assert_noop!(
+            Evercity::account_disable(Origin::none(), 3),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
 // mint tokens
 
 #[test]
@@ -203,13 +780,19 @@ fn it_token_mint_create_with_confirm() {
     new_test_ext().execute_with(|| {
         assert_ok!(Evercity::token_mint_request_create_everusd(
             Origin::signed(ACCOUNT),
-            100000
+            100000,
+            b"UETR 0001".to_vec()
         ));
         assert_eq!(Evercity::total_supply(), 0);
+        assert_eq!(
+            Evercity::mint_request_everusd(&ACCOUNT, 0).map(|request| request.reference),
+            Some(b"UETR 0001".to_vec())
+        );
 
         assert_ok!(Evercity::token_mint_request_confirm_everusd(
             Origin::signed(CUSTODIAN_ID),
             ACCOUNT,
+            0,
             100000
         ));
         assert_eq!(Evercity::total_supply(), 100000);
@@ -222,17 +805,20 @@ fn it_token_mint_create_with_revoke() {
     new_test_ext().execute_with(|| {
         assert_ok!(Evercity::token_mint_request_create_everusd(
             Origin::signed(ACCOUNT), // INVESTOR
-            100000
+            100000,
+            Vec::new()
         ));
 
-        assert_ok!(Evercity::token_mint_request_revoke_everusd(Origin::signed(
-            ACCOUNT
-        ),));
+        assert_ok!(Evercity::token_mint_request_revoke_everusd(
+            Origin::signed(ACCOUNT),
+            0
+        ));
 
         assert_noop!(
             Evercity::token_mint_request_confirm_everusd(
                 Origin::signed(CUSTODIAN_ID),
                 ACCOUNT,
+                0,
                 100000
             ),
             RuntimeError::MintRequestDoesntExist
@@ -246,16 +832,18 @@ fn it_token_mint_create_with_decline() {
     new_test_ext().execute_with(|| {
         assert_ok!(Evercity::token_mint_request_create_everusd(
             Origin::signed(ACCOUNT),
-            100000
+            100000,
+            Vec::new()
         ));
 
         assert_ok!(Evercity::token_mint_request_decline_everusd(
             Origin::signed(CUSTODIAN_ID),
-            ACCOUNT
+            ACCOUNT,
+            0
         ));
 
         assert_noop!(
-            Evercity::token_mint_request_revoke_everusd(Origin::signed(ACCOUNT)),
+            Evercity::token_mint_request_revoke_everusd(Origin::signed(ACCOUNT), 0),
             RuntimeError::MintRequestDoesntExist
         );
     });
@@ -266,35 +854,77 @@ fn it_token_mint_create_denied() {
     const ACCOUNT: u64 = 5; // AUDITOR
     new_test_ext().execute_with(|| {
         assert_noop!(
-            Evercity::token_mint_request_create_everusd(Origin::signed(ACCOUNT), 100000),
+            Evercity::token_mint_request_create_everusd(
+                Origin::signed(ACCOUNT),
+                100000,
+                Vec::new()
+            ),
             RuntimeError::AccountNotAuthorized
         );
     });
 }
 
 #[test]
-fn it_token_mint_create_hasty() {
+fn it_token_mint_create_several() {
     const ACCOUNT: u64 = 4; // INVESTOR
     new_test_ext().execute_with(|| {
-        assert_ok!(Evercity::token_mint_request_create_everusd(
-            Origin::signed(ACCOUNT),
-            100000
-        ));
-
+        for index in 0..MAX_ACCOUNT_TOKEN_REQUESTS {
+            assert_ok!(Evercity::token_mint_request_create_everusd(
+                Origin::signed(ACCOUNT),
+                1000 * (index as EverUSDBalance + 1),
+                Vec::new()
+            ));
+        }
         assert_noop!(
-            Evercity::token_mint_request_create_everusd(Origin::signed(ACCOUNT), 10),
-            RuntimeError::MintRequestAlreadyExist
+            Evercity::token_mint_request_create_everusd(Origin::signed(ACCOUNT), 10, Vec::new()),
+            RuntimeError::MintRequestLimitExceeded
         );
 
-        // make amend
-        let ttl: u32 = <TestRuntime as crate::Config>::MintRequestTtl::get();
-        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(ttl.into());
-
-        assert_ok!(Evercity::token_mint_request_create_everusd(
-            Origin::signed(ACCOUNT),
-            10
-        ));
-    });
+        // requests are confirmed and declined individually
+        assert_noop!(
+            Evercity::token_mint_request_confirm_everusd(
+                Origin::signed(CUSTODIAN_ID),
+                ACCOUNT,
+                1,
+                1000
+            ),
+            RuntimeError::MintRequestParamIncorrect
+        );
+        assert_ok!(Evercity::token_mint_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            ACCOUNT,
+            1,
+            2000
+        ));
+        assert_ok!(Evercity::token_mint_request_decline_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            ACCOUNT,
+            2
+        ));
+        assert_eq!(Evercity::balance_everusd(&ACCOUNT), 2000);
+        assert!(Evercity::mint_request_everusd(&ACCOUNT, 0).is_some());
+        assert!(Evercity::mint_request_everusd(&ACCOUNT, 1).is_none());
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(ACCOUNT),
+            10,
+            Vec::new()
+        ));
+
+        // make amend
+        let ttl: u32 = <TestRuntime as crate::Config>::MintRequestTtl::get();
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(ttl.into());
+
+        // expired requests of the account are purged
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(ACCOUNT),
+            10,
+            Vec::new()
+        ));
+        assert_eq!(
+            crate::MintRequestEverUSD::<TestRuntime>::iter_prefix(&ACCOUNT).count(),
+            1
+        );
+    });
 }
 
 #[test]
@@ -304,7 +934,16 @@ fn it_token_mint_create_toolarge() {
         assert_noop!(
             Evercity::token_mint_request_create_everusd(
                 Origin::signed(ACCOUNT), // INVESTOR
-                EVERUSD_MAX_MINT_AMOUNT + 1
+                EVERUSD_MAX_MINT_AMOUNT + 1,
+                Vec::new()
+            ),
+            RuntimeError::MintRequestParamIncorrect
+        );
+        assert_noop!(
+            Evercity::token_mint_request_create_everusd(
+                Origin::signed(ACCOUNT),
+                1000,
+                vec![0; MAX_TOKEN_REQUEST_REFERENCE_LEN + 1]
             ),
             RuntimeError::MintRequestParamIncorrect
         );
@@ -317,18 +956,22 @@ fn it_token_burn_mint_overflow() {
     new_test_ext().execute_with(|| {
         assert_ok!(Evercity::token_mint_request_create_everusd(
             Origin::signed(ACCOUNT),
-            1000
+            1000,
+            Vec::new()
         ));
 
         assert_ok!(Evercity::token_mint_request_confirm_everusd(
             Origin::signed(CUSTODIAN_ID),
             ACCOUNT,
+            0,
             1000
         ));
         assert_noop!(
             Evercity::token_burn_request_create_everusd(
                 Origin::signed(ACCOUNT),
-                EverUSDBalance::MAX - 1000
+                EverUSDBalance::MAX - 1000,
+                Vec::new(),
+                CUSTODIAN_ID
             ),
             RuntimeError::BalanceOverdraft
         );
@@ -349,13 +992,15 @@ fn it_token_mint_try_confirm_expired() {
     new_test_ext().execute_with(|| {
         assert_ok!(Evercity::token_mint_request_create_everusd(
             Origin::signed(ACCOUNT), // INVESTOR
-            1000
+            1000,
+            Vec::new()
         ));
         <pallet_timestamp::Module<TestRuntime>>::set_timestamp(days2timestamp(10));
         assert_noop!(
             Evercity::token_mint_request_confirm_everusd(
                 Origin::signed(CUSTODIAN_ID),
                 ACCOUNT,
+                0,
                 1000
             ),
             RuntimeError::MintRequestObsolete
@@ -374,7 +1019,9 @@ fn it_token_burn_create_with_confirm() {
 
         assert_ok!(Evercity::token_burn_request_create_everusd(
             Origin::signed(ACCOUNT),
-            10000
+            10000,
+            b"IBAN DE00 0000".to_vec(),
+            CUSTODIAN_ID
         ));
 
         assert_eq!(Evercity::total_supply(), 10000);
@@ -382,6 +1029,7 @@ fn it_token_burn_create_with_confirm() {
         assert_ok!(Evercity::token_burn_request_confirm_everusd(
             Origin::signed(CUSTODIAN_ID),
             ACCOUNT,
+            0,
             10000
         ));
 
@@ -391,6 +1039,7 @@ fn it_token_burn_create_with_confirm() {
             Evercity::token_burn_request_confirm_everusd(
                 Origin::signed(CUSTODIAN_ID),
                 ACCOUNT,
+                0,
                 10000
             ),
             RuntimeError::BurnRequestDoesntExist
@@ -407,7 +1056,12 @@ fn it_token_burn_create_overrun() {
         assert_ok!(add_token(ACCOUNT, BALANCE));
 
         assert_noop!(
-            Evercity::token_burn_request_create_everusd(Origin::signed(ACCOUNT), BALANCE + 1),
+            Evercity::token_burn_request_create_everusd(
+                Origin::signed(ACCOUNT),
+                BALANCE + 1,
+                Vec::new(),
+                CUSTODIAN_ID
+            ),
             RuntimeError::BalanceOverdraft
         );
     });
@@ -422,17 +1076,21 @@ fn it_token_burn_create_with_revoke() {
 
         assert_ok!(Evercity::token_burn_request_create_everusd(
             Origin::signed(ACCOUNT),
-            10000
+            10000,
+            Vec::new(),
+            CUSTODIAN_ID
         ));
 
-        assert_ok!(Evercity::token_burn_request_revoke_everusd(Origin::signed(
-            ACCOUNT
-        ),));
+        assert_ok!(Evercity::token_burn_request_revoke_everusd(
+            Origin::signed(ACCOUNT),
+            0
+        ));
 
         assert_noop!(
             Evercity::token_burn_request_confirm_everusd(
                 Origin::signed(CUSTODIAN_ID),
                 ACCOUNT,
+                0,
                 10000
             ),
             RuntimeError::BurnRequestDoesntExist
@@ -447,13 +1105,16 @@ fn it_token_burn_try_confirm_expired() {
         assert_ok!(add_token(ACCOUNT, 10000));
         assert_ok!(Evercity::token_burn_request_create_everusd(
             Origin::signed(ACCOUNT), // INVESTOR
-            1000
+            1000,
+            Vec::new(),
+            CUSTODIAN_ID
         ));
         <pallet_timestamp::Module<TestRuntime>>::set_timestamp(days2timestamp(10));
         assert_noop!(
             Evercity::token_burn_request_confirm_everusd(
                 Origin::signed(CUSTODIAN_ID),
                 ACCOUNT,
+                0,
                 1000
             ),
             RuntimeError::BurnRequestObsolete
@@ -462,7 +1123,7 @@ fn it_token_burn_try_confirm_expired() {
 }
 
 #[test]
-fn it_token_burn_hasty() {
+fn it_token_burn_several() {
     const ACCOUNT: u64 = 4; // INVESTOR
 
     new_test_ext().execute_with(|| {
@@ -470,12 +1131,34 @@ fn it_token_burn_hasty() {
 
         assert_ok!(Evercity::token_burn_request_create_everusd(
             Origin::signed(ACCOUNT),
-            5000
+            5000,
+            Vec::new(),
+            CUSTODIAN_ID
+        ));
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(ACCOUNT),
+            4000,
+            Vec::new(),
+            CUSTODIAN_ID
         ));
+        // pending requests should be covered by the balance
         assert_noop!(
-            Evercity::token_burn_request_create_everusd(Origin::signed(ACCOUNT), 10000),
-            RuntimeError::BurnRequestAlreadyExist
+            Evercity::token_burn_request_create_everusd(
+                Origin::signed(ACCOUNT),
+                1001,
+                Vec::new(),
+                CUSTODIAN_ID
+            ),
+            RuntimeError::BalanceOverdraft
         );
+        assert_ok!(Evercity::token_burn_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            ACCOUNT,
+            1,
+            4000
+        ));
+        assert_eq!(Evercity::balance_everusd(&ACCOUNT), 6000);
+        assert!(Evercity::burn_request_everusd(&ACCOUNT, 0).is_some());
 
         // make amend
         let ttl: u32 = <TestRuntime as crate::Config>::BurnRequestTtl::get();
@@ -483,112 +1166,544 @@ fn it_token_burn_hasty() {
 
         assert_ok!(Evercity::token_burn_request_create_everusd(
             Origin::signed(ACCOUNT),
-            10000
+            6000,
+            Vec::new(),
+            CUSTODIAN_ID
         ));
+        assert!(Evercity::burn_request_everusd(&ACCOUNT, 0).is_none());
     })
 }
-// fuse
 
 #[test]
-fn fuse_is_blone() {
-    new_test_ext().execute_with(|| {
-        let fuse = Evercity::fuse();
-        assert_eq!(fuse, true);
+fn it_token_mint_burn_by_custodians() {
+    const CUSTODIAN2: u64 = 101;
+    const ACCOUNT: u64 = 4; // INVESTOR
+    const AUDITOR: u64 = 5;
 
+    new_test_ext().execute_with(|| {
+        assert_ok!(Evercity::account_add_with_role_and_data(
+            Origin::signed(1),
+            CUSTODIAN2,
+            CUSTODIAN_ROLE_MASK,
+            0
+        ));
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(ACCOUNT),
+            3000,
+            Vec::new()
+        ));
         assert_noop!(
-            Evercity::set_master(Origin::signed(2),),
-            RuntimeError::InvalidAction
+            Evercity::token_mint_request_confirm_everusd(
+                Origin::signed(CUSTODIAN2),
+                ACCOUNT,
+                0,
+                3000
+            ),
+            RuntimeError::CustodianNotRegistered
         );
-    })
-}
-
-#[test]
-fn fuse_is_intact_on_bare_storage() {
-    let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default()
-        .build_storage::<TestRuntime>()
-        .unwrap()
-        .into();
+        assert_noop!(
+            Evercity::custodian_set(Origin::signed(1), ACCOUNT, 5000, 1000),
+            RuntimeError::AccountRoleParamIncorrect
+        );
+        assert_ok!(Evercity::custodian_set(
+            Origin::signed(1),
+            CUSTODIAN2,
+            5000,
+            1000
+        ));
 
-    ext.execute_with(|| {
-        assert_eq!(Evercity::fuse(), false);
+        // mint cap is shared by all mints within the window
+        assert_ok!(Evercity::token_mint_request_confirm_everusd(
+            Origin::signed(CUSTODIAN2),
+            ACCOUNT,
+            0,
+            3000
+        ));
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(ACCOUNT),
+            3000,
+            Vec::new()
+        ));
+        assert_noop!(
+            Evercity::token_mint_request_confirm_everusd(
+                Origin::signed(CUSTODIAN2),
+                ACCOUNT,
+                1,
+                3000
+            ),
+            RuntimeError::CustodianMintCapExceeded
+        );
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(1000);
+        assert_ok!(Evercity::token_mint_request_confirm_everusd(
+            Origin::signed(CUSTODIAN2),
+            ACCOUNT,
+            1,
+            3000
+        ));
+        assert_eq!(
+            Evercity::custodian_registry(&CUSTODIAN2).unwrap().supply,
+            6000
+        );
+        assert_eq!(Evercity::unattributed_supply_everusd(), 0);
 
+        // auditor attests reserves of custodian
+        let hash = BlakeTwo256::hash(b"report");
         assert_noop!(
-            Evercity::account_add_with_role_and_data(Origin::signed(1), 101, MASTER_ROLE_MASK, 0),
+            Evercity::custodian_reserves_attest(Origin::signed(ACCOUNT), CUSTODIAN2, 6000, hash),
             RuntimeError::AccountNotAuthorized
         );
-        assert_ok!(Evercity::set_master(Origin::signed(1),));
-        // make amend
-        assert_ok!(Evercity::account_add_with_role_and_data(
-            Origin::signed(1),
-            101,
-            MASTER_ROLE_MASK,
-            0
+        assert_ok!(Evercity::custodian_reserves_attest(
+            Origin::signed(AUDITOR),
+            CUSTODIAN2,
+            6000,
+            hash
         ));
+        let custodian = Evercity::custodian_registry(&CUSTODIAN2).unwrap();
+        assert_eq!(custodian.reserves.as_ref().unwrap().attestation_hash, hash);
+        assert!(custodian.is_backed());
 
-        assert_eq!(Evercity::fuse(), true);
+        // custodian can't mint more than its attested reserves
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(ACCOUNT),
+            1000,
+            Vec::new()
+        ));
         assert_noop!(
-            Evercity::set_master(Origin::signed(2),),
-            RuntimeError::InvalidAction
+            Evercity::token_mint_request_confirm_everusd(
+                Origin::signed(CUSTODIAN2),
+                ACCOUNT,
+                2,
+                1000
+            ),
+            RuntimeError::CustodianReservesShortage
         );
-    });
-}
-// bonds
 
-fn create_bond_unit_package(amount: Vec<BondUnitAmount>) -> Vec<BondUnitPackage> {
-    amount
-        .into_iter()
-        .map(|bond_units| BondUnitPackage {
-            bond_units,
-            acquisition: 0,
-            coupon_yield: 0,
-        })
-        .collect()
-}
+        // burn is routed to the custodian, holding the reserves
+        assert_noop!(
+            Evercity::token_burn_request_create_everusd(
+                Origin::signed(ACCOUNT),
+                2000,
+                Vec::new(),
+                CUSTODIAN_ID
+            ),
+            RuntimeError::CustodianSupplyShortage
+        );
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(ACCOUNT),
+            2000,
+            Vec::new(),
+            CUSTODIAN2
+        ));
+        assert_noop!(
+            Evercity::token_burn_request_confirm_everusd(
+                Origin::signed(CUSTODIAN_ID),
+                ACCOUNT,
+                0,
+                2000
+            ),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_ok!(Evercity::token_burn_request_confirm_everusd(
+            Origin::signed(CUSTODIAN2),
+            ACCOUNT,
+            0,
+            2000
+        ));
+        assert_eq!(
+            Evercity::custodian_registry(&CUSTODIAN2).unwrap().supply,
+            4000
+        );
+        assert_eq!(Evercity::total_supply(), 4000);
 
-fn bond_unit_package_amount(package: Vec<BondUnitPackage>) -> Vec<BondUnitAmount> {
-    package.into_iter().map(|item| item.bond_units).collect()
+        // supply, minted before custodians were registered, is attributed by Master
+        crate::TotalSupplyEverUSD::mutate(|total| *total += 1000);
+        assert_eq!(Evercity::unattributed_supply_everusd(), 1000);
+        assert_noop!(
+            Evercity::custodian_supply_assign(Origin::signed(1), CUSTODIAN_ID, 1001),
+            RuntimeError::BalanceOverdraft
+        );
+        assert_ok!(Evercity::custodian_supply_assign(
+            Origin::signed(1),
+            CUSTODIAN_ID,
+            1000
+        ));
+        assert_eq!(
+            Evercity::custodian_registry(&CUSTODIAN_ID).unwrap().supply,
+            1000
+        );
+    })
 }
 
 #[test]
-fn bond_transfer_units() {
+fn it_token_burn_requests_are_covered_by_custodian_supply() {
+    const ACCOUNT1: u64 = 4; // INVESTOR
+    const ACCOUNT2: u64 = 6; // INVESTOR
+
     new_test_ext().execute_with(|| {
-        let mut from_package = create_bond_unit_package(vec![5, 2, 10, 1]);
-        let mut to_package = create_bond_unit_package(vec![]);
-        assert_ok!(transfer_bond_units::<TestRuntime>(
-            &mut from_package,
-            &mut to_package,
-            3
-        ));
+        assert_ok!(add_token(ACCOUNT1, 3000));
+        // balance, which isn't backed by the custodian
+        crate::BalanceEverUSD::<TestRuntime>::insert(ACCOUNT2, 3000);
+        crate::TotalSupplyEverUSD::mutate(|total| *total += 3000);
 
-        assert_eq!(bond_unit_package_amount(from_package), vec![10, 5]);
-        assert_eq!(bond_unit_package_amount(to_package), vec![1, 2]);
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(ACCOUNT1),
+            2000,
+            Vec::new(),
+            CUSTODIAN_ID
+        ));
+        assert_eq!(Evercity::custodian_pending_burn(&CUSTODIAN_ID), 2000);
+        assert_noop!(
+            Evercity::token_burn_request_create_everusd(
+                Origin::signed(ACCOUNT2),
+                2000,
+                Vec::new(),
+                CUSTODIAN_ID
+            ),
+            RuntimeError::CustodianSupplyShortage
+        );
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(ACCOUNT2),
+            1000,
+            Vec::new(),
+            CUSTODIAN_ID
+        ));
+        assert_eq!(Evercity::custodian_pending_burn(&CUSTODIAN_ID), 3000);
 
-        let mut from_package = create_bond_unit_package(vec![5, 2, 10, 1]);
-        let mut to_package = create_bond_unit_package(vec![]);
+        // revoked, declined and confirmed requests are not pending
+        assert_ok!(Evercity::token_burn_request_revoke_everusd(
+            Origin::signed(ACCOUNT1),
+            0
+        ));
+        assert_eq!(Evercity::custodian_pending_burn(&CUSTODIAN_ID), 1000);
+        assert_ok!(Evercity::token_burn_request_decline_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            ACCOUNT2,
+            1
+        ));
+        assert_eq!(Evercity::custodian_pending_burn(&CUSTODIAN_ID), 0);
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(ACCOUNT2),
+            3000,
+            Vec::new(),
+            CUSTODIAN_ID
+        ));
+        assert_ok!(Evercity::token_burn_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            ACCOUNT2,
+            2,
+            3000
+        ));
+        assert_eq!(Evercity::custodian_pending_burn(&CUSTODIAN_ID), 0);
+        assert_eq!(
+            Evercity::custodian_registry(&CUSTODIAN_ID).unwrap().supply,
+            0
+        );
 
-        assert_ok!(transfer_bond_units::<TestRuntime>(
-            &mut from_package,
-            &mut to_package,
-            10
+        // expired request is purged
+        assert_ok!(add_token(ACCOUNT1, 1000));
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(ACCOUNT1),
+            1000,
+            Vec::new(),
+            CUSTODIAN_ID
         ));
+        assert_eq!(Evercity::custodian_pending_burn(&CUSTODIAN_ID), 1000);
+        let ttl: u32 = <TestRuntime as crate::Config>::BurnRequestTtl::get();
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(ttl as u64 + 1);
+        Evercity::on_initialize(1);
+        assert!(Evercity::burn_request_everusd(&ACCOUNT1, 3).is_none());
+        assert_eq!(Evercity::custodian_pending_burn(&CUSTODIAN_ID), 0);
+    })
+}
 
-        assert_eq!(bond_unit_package_amount(from_package), vec![8]);
-        assert_eq!(bond_unit_package_amount(to_package), vec![1, 2, 5, 2]);
+// transfer tokens
 
-        let mut from_package = create_bond_unit_package(vec![5, 2, 10, 1]);
-        let mut to_package = create_bond_unit_package(vec![]);
+#[test]
+fn it_token_transfer_everusd() {
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
 
-        assert_ok!(transfer_bond_units::<TestRuntime>(
-            &mut from_package,
-            &mut to_package,
-            2
-        ));
+    new_test_ext().execute_with(|| {
+        assert_ok!(add_token(INVESTOR1, 10000));
 
-        assert_eq!(bond_unit_package_amount(from_package), vec![10, 5, 1]);
-        assert_eq!(bond_unit_package_amount(to_package), vec![1, 1]);
+        assert_ok!(Evercity::transfer_everusd(
+            Origin::signed(INVESTOR1),
+            INVESTOR2,
+            4000
+        ));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), 6000);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), 4000);
+        assert_eq!(Evercity::total_supply(), 10000);
+        assert!(Evercity::evercity_balance().is_ok());
 
-        let mut from_package = create_bond_unit_package(vec![5, 2, 10, 1]);
-        let mut to_package = create_bond_unit_package(vec![]);
+        // whole balance can be sent
+        assert_ok!(Evercity::transfer_everusd(
+            Origin::signed(INVESTOR1),
+            INVESTOR2,
+            6000
+        ));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), 0);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), 10000);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn it_token_transfer_everusd_try_unregistered() {
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(add_token(INVESTOR1, 10000));
+
+        assert_noop!(
+            Evercity::transfer_everusd(Origin::signed(INVESTOR1), 101, 1000),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_noop!(
+            Evercity::transfer_everusd(Origin::signed(INVESTOR1), INVESTOR1, 1000),
+            RuntimeError::InvalidAction
+        );
+        assert_noop!(
+            Evercity::transfer_everusd(Origin::signed(INVESTOR1), INVESTOR2, 10001),
+            RuntimeError::BalanceOverdraft
+        );
+        // disabled accounts can neither send nor receive tokens
+        assert_ok!(Evercity::account_disable(Origin::signed(1), INVESTOR2));
+        assert_noop!(
+            Evercity::transfer_everusd(Origin::signed(INVESTOR1), INVESTOR2, 1000),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_ok!(Evercity::account_disable(Origin::signed(1), INVESTOR1));
+        assert_noop!(
+            Evercity::transfer_everusd(Origin::signed(INVESTOR1), 7, 1000),
+            RuntimeError::AccountNotAuthorized
+        );
+    });
+}
+
+#[test]
+fn it_token_transfer_everusd_keep_alive() {
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(add_token(INVESTOR1, 10000));
+
+        assert_noop!(
+            Evercity::transfer_everusd_keep_alive(Origin::signed(INVESTOR1), INVESTOR2, 10000),
+            RuntimeError::BalanceOverdraft
+        );
+        // pending burn requests should remain covered
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(INVESTOR1),
+            3000,
+            Vec::new(),
+            CUSTODIAN_ID
+        ));
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(INVESTOR1),
+            2000,
+            Vec::new(),
+            CUSTODIAN_ID
+        ));
+        assert_noop!(
+            Evercity::transfer_everusd_keep_alive(Origin::signed(INVESTOR1), INVESTOR2, 5001),
+            RuntimeError::BalanceOverdraft
+        );
+        assert_ok!(Evercity::transfer_everusd_keep_alive(
+            Origin::signed(INVESTOR1),
+            INVESTOR2,
+            5000
+        ));
+        assert_ok!(Evercity::token_burn_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            INVESTOR1,
+            0,
+            3000
+        ));
+        assert_ok!(Evercity::token_burn_request_confirm_everusd(
+            Origin::signed(CUSTODIAN_ID),
+            INVESTOR1,
+            1,
+            2000
+        ));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), 0);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn it_token_transfer_everusd_requires_kyc() {
+    const MASTER: u64 = 1;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const KYC_PROVIDER: u64 = 101;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(add_token(INVESTOR1, 10000));
+        assert_ok!(Evercity::account_add_with_role_and_data(
+            Origin::signed(MASTER),
+            KYC_PROVIDER,
+            KYC_PROVIDER_ROLE_MASK,
+            0
+        ));
+        assert_noop!(
+            Evercity::set_transfer_kyc_required(Origin::signed(KYC_PROVIDER), true),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_ok!(Evercity::set_transfer_kyc_required(
+            Origin::signed(MASTER),
+            true
+        ));
+
+        assert_ok!(Evercity::account_kyc_set(
+            Origin::signed(KYC_PROVIDER),
+            INVESTOR1,
+            BlakeTwo256::hash(b"passport"),
+            *b"DE",
+            0,
+            20_000
+        ));
+        assert_noop!(
+            Evercity::transfer_everusd(Origin::signed(INVESTOR1), INVESTOR2, 1000),
+            RuntimeError::KycNotValid
+        );
+        assert_ok!(Evercity::account_kyc_set(
+            Origin::signed(KYC_PROVIDER),
+            INVESTOR2,
+            BlakeTwo256::hash(b"passport"),
+            *b"FR",
+            0,
+            20_000
+        ));
+        assert_ok!(Evercity::transfer_everusd(
+            Origin::signed(INVESTOR1),
+            INVESTOR2,
+            1000
+        ));
+
+        // attestation is expired
+        Timestamp::set_timestamp(20_000);
+        assert_noop!(
+            Evercity::transfer_everusd(Origin::signed(INVESTOR1), INVESTOR2, 1000),
+            RuntimeError::KycNotValid
+        );
+        assert_ok!(Evercity::set_transfer_kyc_required(
+            Origin::signed(MASTER),
+            false
+        ));
+        assert_ok!(Evercity::transfer_everusd(
+            Origin::signed(INVESTOR1),
+            INVESTOR2,
+            1000
+        ));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), 2000);
+    });
+}
+
+// fuse
+
+#[test]
+fn fuse_is_blone() {
+    new_test_ext().execute_with(|| {
+        let fuse = Evercity::fuse();
+        assert_eq!(fuse, true);
+
+        assert_noop!(
+            Evercity::set_master(Origin::signed(2),),
+            RuntimeError::InvalidAction
+        );
+    })
+}
+
+#[test]
+fn fuse_is_intact_on_bare_storage() {
+    let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default()
+        .build_storage::<TestRuntime>()
+        .unwrap()
+        .into();
+
+    ext.execute_with(|| {
+        assert_eq!(Evercity::fuse(), false);
+
+        assert_noop!(
+            Evercity::account_add_with_role_and_data(Origin::signed(1), 101, MASTER_ROLE_MASK, 0),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_ok!(Evercity::set_master(Origin::signed(1),));
+        // make amend
+        assert_ok!(Evercity::account_add_with_role_and_data(
+            Origin::signed(1),
+            101,
+            MASTER_ROLE_MASK,
+            0
+        ));
+
+        assert_eq!(Evercity::fuse(), true);
+        assert_noop!(
+            Evercity::set_master(Origin::signed(2),),
+            RuntimeError::InvalidAction
+        );
+    });
+}
+// bonds
+
+fn create_bond_unit_package(amount: Vec<BondUnitAmount>) -> Vec<BondUnitPackage> {
+    amount
+        .into_iter()
+        .map(|bond_units| BondUnitPackage {
+            bond_units,
+            acquisition: 0,
+            coupon_yield: 0,
+        })
+        .collect()
+}
+
+fn bond_unit_package_amount(package: Vec<BondUnitPackage>) -> Vec<BondUnitAmount> {
+    package.into_iter().map(|item| item.bond_units).collect()
+}
+
+#[test]
+fn bond_transfer_units() {
+    new_test_ext().execute_with(|| {
+        let mut from_package = create_bond_unit_package(vec![5, 2, 10, 1]);
+        let mut to_package = create_bond_unit_package(vec![]);
+        assert_ok!(transfer_bond_units::<TestRuntime>(
+            &mut from_package,
+            &mut to_package,
+            3
+        ));
+
+        assert_eq!(bond_unit_package_amount(from_package), vec![10, 5]);
+        assert_eq!(bond_unit_package_amount(to_package), vec![1, 2]);
+
+        let mut from_package = create_bond_unit_package(vec![5, 2, 10, 1]);
+        let mut to_package = create_bond_unit_package(vec![]);
+
+        assert_ok!(transfer_bond_units::<TestRuntime>(
+            &mut from_package,
+            &mut to_package,
+            10
+        ));
+
+        assert_eq!(bond_unit_package_amount(from_package), vec![8]);
+        assert_eq!(bond_unit_package_amount(to_package), vec![1, 2, 5, 2]);
+
+        let mut from_package = create_bond_unit_package(vec![5, 2, 10, 1]);
+        let mut to_package = create_bond_unit_package(vec![]);
+
+        assert_ok!(transfer_bond_units::<TestRuntime>(
+            &mut from_package,
+            &mut to_package,
+            2
+        ));
+
+        assert_eq!(bond_unit_package_amount(from_package), vec![10, 5, 1]);
+        assert_eq!(bond_unit_package_amount(to_package), vec![1, 1]);
+
+        let mut from_package = create_bond_unit_package(vec![5, 2, 10, 1]);
+        let mut to_package = create_bond_unit_package(vec![]);
 
         assert_noop!(
             transfer_bond_units::<TestRuntime>(&mut from_package, &mut to_package, 20),
@@ -624,6 +1739,65 @@ fn incorrect_bond_validation() {
 }
 
 
+fn get_test_bond_kpis() -> BondStruct {
+    let mut bond = get_test_bond();
+    let periods = bond.inner.bond_duration as usize;
+    bond.inner.impact_data_kpis = vec![
+        BondImpactKpiStruct {
+            impact_data_type: BondImpactType::CO2_EMISSIONS_REDUCTION,
+            impact_data_baseline: vec![1000_u64; periods],
+            impact_data_max_deviation_cap: 2000_u64,
+            impact_data_max_deviation_floor: 500_u64,
+            weight: 250_000,
+        },
+        BondImpactKpiStruct {
+            impact_data_type: BondImpactType::WATER_SAVED,
+            impact_data_baseline: vec![100_u64; periods],
+            impact_data_max_deviation_cap: 200_u64,
+            impact_data_max_deviation_floor: 50_u64,
+            weight: 250_000,
+        },
+    ];
+    bond
+}
+
+#[test]
+fn bond_with_kpis_validation() {
+    let bond = get_test_bond_kpis().inner;
+    assert!(bond.is_valid(DEFAULT_DAY_DURATION));
+    assert_eq!(bond.impact_data_len(), 3);
+    assert_eq!(bond.impact_data_weight(), 500_000);
+
+    let mut bond1 = bond.clone();
+    bond1.impact_data_kpis[0].weight = 0;
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+
+    let mut bond1 = bond.clone();
+    bond1.impact_data_kpis[0].weight = 800_000;
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+
+    let mut bond1 = bond.clone();
+    bond1.impact_data_kpis[1].impact_data_baseline.pop();
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+
+    let mut bond1 = bond.clone();
+    bond1.impact_data_kpis[1].impact_data_baseline[0] = 201;
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+
+    let mut bond1 = bond.clone();
+    bond1.impact_data_kpis = vec![bond.impact_data_kpis[0].clone(); MAX_IMPACT_KPIS + 1];
+    for kpi in bond1.impact_data_kpis.iter_mut() {
+        kpi.weight = 1;
+    }
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+
+    let mut bond1 = get_test_bond_stable().inner;
+    bond1.impact_data_kpis = bond.impact_data_kpis.clone();
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+
+    assert!(!bond.is_financial_options_eq(&get_test_bond().inner));
+}
+
 #[test]
 fn bond_check_equation() {
     new_test_ext().execute_with(|| {
@@ -651,59 +1825,65 @@ fn bond_interest_min_max() {
         let impact_base_value = bond.inner.impact_data_baseline[0];
         // full amplitude
         assert_eq!(
-            bond.calc_effective_interest_rate(impact_base_value.unwrap_or(0), impact_base_value.unwrap_or(0)),
+            bond.calc_effective_interest_rate(0, &[impact_base_value.unwrap_or(0)]),
             bond.inner.interest_rate_base_value
         );
         assert_eq!(
-            bond.calc_effective_interest_rate(
-                impact_base_value.unwrap_or(0),
-                bond.inner.impact_data_max_deviation_cap.unwrap_or(0),
-            ),
+            bond.calc_effective_interest_rate(0, &[bond.inner.impact_data_max_deviation_cap.unwrap_or(0)]),
             bond.inner.interest_rate_margin_floor.unwrap_or(0)
         );
         assert_eq!(
-            bond.calc_effective_interest_rate(
-                impact_base_value.unwrap_or(0),
-                bond.inner.impact_data_max_deviation_cap.unwrap_or(0) + 1,
-            ),
+            bond.calc_effective_interest_rate(0, &[bond.inner.impact_data_max_deviation_cap.unwrap_or(0) + 1]),
             bond.inner.interest_rate_margin_floor.unwrap_or(0)
         );
         assert_eq!(
-            bond.calc_effective_interest_rate(
-                impact_base_value.unwrap_or(0),
-                bond.inner.impact_data_max_deviation_floor.unwrap_or(0),
-            ),
+            bond.calc_effective_interest_rate(0, &[bond.inner.impact_data_max_deviation_floor.unwrap_or(0)]),
             bond.inner.interest_rate_margin_cap.unwrap_or(0)
         );
         assert_eq!(
-            bond.calc_effective_interest_rate(
-                impact_base_value.unwrap_or(0),
-                bond.inner.impact_data_max_deviation_floor.unwrap_or(0) - 1,
-            ),
+            bond.calc_effective_interest_rate(0, &[bond.inner.impact_data_max_deviation_floor.unwrap_or(0) - 1]),
             bond.inner.interest_rate_margin_cap.unwrap_or(0)
         );
 
         // partial amplitude
         assert_eq!(
-            bond.calc_effective_interest_rate(impact_base_value.unwrap_or(0), 25000_u64),
+            bond.calc_effective_interest_rate(0, &[25000_u64]),
             1500
         );
         assert_eq!(
-            bond.calc_effective_interest_rate(impact_base_value.unwrap_or(0), 29000_u64),
+            bond.calc_effective_interest_rate(0, &[29000_u64]),
             1100
         );
 
         assert_eq!(
-            bond.calc_effective_interest_rate(impact_base_value.unwrap_or(0), 17000_u64),
+            bond.calc_effective_interest_rate(0, &[17000_u64]),
             3000
         );
         assert_eq!(
-            bond.calc_effective_interest_rate(impact_base_value.unwrap_or(0), 15000_u64),
+            bond.calc_effective_interest_rate(0, &[15000_u64]),
             3666
         );
     });
 }
 
+#[test]
+fn bond_with_kpis_interest_rate() {
+    let bond = get_test_bond_kpis();
+    // all KPIs hit the baseline
+    assert_eq!(
+        bond.calc_effective_interest_rate(0, &[20000, 1000, 100]),
+        bond.inner.interest_rate_base_value
+    );
+    // main KPI hits the cap: 0.5 * 1000 + 0.25 * 2000 + 0.25 * 2000
+    assert_eq!(bond.calc_effective_interest_rate(0, &[30000, 1000, 100]), 1500);
+    // first additional KPI hits the floor: 0.5 * 2000 + 0.25 * 4000 + 0.25 * 2000
+    assert_eq!(bond.calc_effective_interest_rate(0, &[20000, 500, 100]), 2500);
+    // both additional KPIs hit the cap: 0.5 * 2000 + 0.25 * 1000 + 0.25 * 1000
+    assert_eq!(bond.calc_effective_interest_rate(0, &[20000, 2000, 200]), 1500);
+    // missing values are considered equal to baseline
+    assert_eq!(bond.calc_effective_interest_rate(0, &[30000]), 1500);
+}
+
 #[test]
 fn bond_period_interest_rate() {
     new_test_ext().execute_with(|| {
@@ -719,48 +1899,48 @@ fn bond_period_interest_rate() {
             //missing report
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 20000_u64,
+                impact_data: vec![20000_u64],
                 signed: true,
             },
             //missing report
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
             // worst result and maximal interest rate value
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 14000_u64,
+                impact_data: vec![14000_u64],
                 signed: true,
             },
             //missing report. it cannot make interest rate worse
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
             // very good result lead to mininal interest rate
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 100000_u64,
+                impact_data: vec![100000_u64],
                 signed: true,
             },
             //first missing report.
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
             //second missing report.
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
         ];
@@ -931,36 +2111,159 @@ fn bond_buy_bond_uc() {
 }
 
 #[test]
-fn bond_try_create_by_nonissuer() {
-    let bond = get_test_bond();
+fn bond_buy_requires_kyc_eligibility() {
+    const MASTER: u64 = 1;
+    const ISSUER: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const KYC_PROVIDER: u64 = 101;
+    const KYC_PROVIDER2: u64 = 102;
+
     let bondid: BondId = "BOND".into();
 
     new_test_ext().execute_with(|| {
-        for acc in iter_accounts().filter(|acc| !Evercity::account_is_issuer(acc)) {
-            assert_noop!(
-                Evercity::bond_add_new(Origin::signed(acc), bondid, bond.inner.clone()),
-                RuntimeError::AccountNotAuthorized
-            );
+        bond_grand_everusd();
+        for acc in [KYC_PROVIDER, KYC_PROVIDER2].iter() {
+            assert_ok!(Evercity::account_add_with_role_and_data(
+                Origin::signed(MASTER),
+                *acc,
+                KYC_PROVIDER_ROLE_MASK,
+                0
+            ));
         }
-    });
-}
 
-#[test]
-fn bond_try_create_incorrect_stable_or_unstable() {
-    let process_test = |payment_period: u32, bond_duration: u32, bond_unit_base_prize: u64| {
-        let stable_bond = get_test_bond_incorrect(payment_period, bond_duration, bond_unit_base_prize, true);
-        let bond = get_test_bond_incorrect(payment_period, bond_duration, bond_unit_base_prize, false);
-        let stable_bondid: BondId = "BOND_S".into();
-        let bondid: BondId = "BOND".into();
-    
-        new_test_ext().execute_with(|| {
-            for acc in iter_accounts().filter(|acc| Evercity::account_is_issuer(acc)) {
-                assert_noop!(
-                    Evercity::bond_add_new(Origin::signed(acc), stable_bondid, stable_bond.inner.clone()),
-                    RuntimeError::BondParamIncorrect
-                );
-    
-                assert_noop!(
+        let mut bond = get_test_bond().inner;
+        // jurisdiction cannot be checked without attestation
+        bond.eligibility.jurisdictions = vec![*b"DE", *b"FR"];
+        assert_noop!(
+            Evercity::bond_add_new(Origin::signed(ISSUER), bondid, bond.clone()),
+            RuntimeError::BondParamIncorrect
+        );
+        bond.eligibility.kyc_required = true;
+        bond.eligibility.min_accreditation = 1;
+        bond_release(bondid, ISSUER, bond);
+        let nonce = Evercity::get_bond(&bondid).nonce;
+
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, nonce, 100),
+            RuntimeError::KycNotValid
+        );
+        assert_noop!(
+            Evercity::account_kyc_set(
+                Origin::signed(MASTER),
+                INVESTOR1,
+                BlakeTwo256::hash(b"passport"),
+                *b"DE",
+                1,
+                100_000
+            ),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_noop!(
+            Evercity::account_kyc_set(
+                Origin::signed(KYC_PROVIDER),
+                INVESTOR1,
+                BlakeTwo256::hash(b"passport"),
+                *b"de",
+                1,
+                100_000
+            ),
+            RuntimeError::KycParamIncorrect
+        );
+        assert_ok!(Evercity::account_kyc_set(
+            Origin::signed(KYC_PROVIDER),
+            INVESTOR1,
+            BlakeTwo256::hash(b"passport"),
+            *b"DE",
+            1,
+            100_000
+        ));
+        assert_ok!(Evercity::bond_unit_package_buy(
+            Origin::signed(INVESTOR1),
+            bondid,
+            nonce,
+            100
+        ));
+
+        // jurisdiction is not permitted
+        assert_ok!(Evercity::account_kyc_set(
+            Origin::signed(KYC_PROVIDER),
+            INVESTOR2,
+            BlakeTwo256::hash(b"passport"),
+            *b"US",
+            2,
+            100_000
+        ));
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR2), bondid, nonce, 100),
+            RuntimeError::KycNotValid
+        );
+        // accreditation is too low
+        assert_ok!(Evercity::account_kyc_set(
+            Origin::signed(KYC_PROVIDER),
+            INVESTOR2,
+            BlakeTwo256::hash(b"passport"),
+            *b"FR",
+            0,
+            100_000
+        ));
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR2), bondid, nonce, 100),
+            RuntimeError::KycNotValid
+        );
+
+        // attestation can be revoked by its provider or master
+        assert_noop!(
+            Evercity::account_kyc_revoke(Origin::signed(KYC_PROVIDER2), INVESTOR1),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_ok!(Evercity::account_kyc_revoke(
+            Origin::signed(MASTER),
+            INVESTOR1
+        ));
+        assert_noop!(
+            Evercity::account_kyc_revoke(Origin::signed(KYC_PROVIDER), INVESTOR1),
+            RuntimeError::KycNotFound
+        );
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, nonce, 100),
+            RuntimeError::KycNotValid
+        );
+        assert_eq!(Evercity::get_bond(&bondid).issued_amount, 100);
+    });
+}
+
+#[test]
+fn bond_try_create_by_nonissuer() {
+    let bond = get_test_bond();
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        for acc in iter_accounts().filter(|acc| !Evercity::account_is_issuer(acc)) {
+            assert_noop!(
+                Evercity::bond_add_new(Origin::signed(acc), bondid, bond.inner.clone()),
+                RuntimeError::AccountNotAuthorized
+            );
+        }
+    });
+}
+
+#[test]
+fn bond_try_create_incorrect_stable_or_unstable() {
+    let process_test = |payment_period: u32, bond_duration: u32, bond_unit_base_prize: u64| {
+        let stable_bond = get_test_bond_incorrect(payment_period, bond_duration, bond_unit_base_prize, true);
+        let bond = get_test_bond_incorrect(payment_period, bond_duration, bond_unit_base_prize, false);
+        let stable_bondid: BondId = "BOND_S".into();
+        let bondid: BondId = "BOND".into();
+    
+        new_test_ext().execute_with(|| {
+            for acc in iter_accounts().filter(|acc| Evercity::account_is_issuer(acc)) {
+                assert_noop!(
+                    Evercity::bond_add_new(Origin::signed(acc), stable_bondid, stable_bond.inner.clone()),
+                    RuntimeError::BondParamIncorrect
+                );
+    
+                assert_noop!(
                     Evercity::bond_add_new(Origin::signed(acc), bondid, bond.inner.clone()),
                     RuntimeError::BondParamIncorrect
                 );
@@ -972,6 +2275,48 @@ fn bond_try_create_incorrect_stable_or_unstable() {
     process_test(crate::bond::MIN_PAYMENT_PERIOD*DEFAULT_DAY_DURATION, 12, 0);
 }
 
+#[test]
+fn bond_try_create_with_too_long_duration() {
+    let mut bond = get_test_bond().inner;
+    bond.bond_duration = crate::MAX_BOND_DURATION;
+    bond.impact_data_baseline = vec![Some(20000_u64); bond.bond_duration as usize];
+    assert!(bond.is_valid(DEFAULT_DAY_DURATION));
+
+    bond.bond_duration += 1;
+    bond.impact_data_baseline.push(Some(20000_u64));
+    assert!(!bond.is_valid(DEFAULT_DAY_DURATION));
+}
+
+#[test]
+fn bond_unit_lot_bid_is_limited() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 1,
+            amount: 3_000_000_000_000,
+        };
+        for _ in 0..crate::WEIGHT_LOTS {
+            assert_ok!(Evercity::bond_unit_lot_bid(
+                Origin::signed(INVESTOR1),
+                bondid,
+                lot.clone()
+            ));
+        }
+        assert_noop!(
+            Evercity::bond_unit_lot_bid(Origin::signed(INVESTOR1), bondid, lot),
+            RuntimeError::LotLimitExceeded
+        );
+    });
+}
+
 #[test]
 fn bond_try_activate_without_release() {
     const MASTER: u64 = 1;
@@ -1124,48 +2469,48 @@ fn bond_stable_calc_bond_interest_rate() {
             //missing report
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
             //missing report
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
             // worst result and maximal interest rate value
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
             //missing report. it cannot make interest rate worse
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
             // very good result lead to mininal interest rate
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
             //first missing report.
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
             //second missing report.
             BondImpactReportStruct {
                 create_period: 0,
-                impact_data: 0,
+                impact_data: vec![0],
                 signed: false,
             },
         ];
@@ -1573,7 +2918,7 @@ fn bond_calc_coupon_yield_basic() {
             bond_yields[0].interest_rate,
             chain_bond_item.inner.interest_rate_start_period_value.unwrap_or(0)
         );
-        assert_eq!(bond_yields[0].total_yield, 29_983_561_643_520);
+        assert_eq!(bond_yields[0].total_yield, 29_983_561_643_834);
 
         assert_eq!(
             bond_yields[1].interest_rate,
@@ -1582,7 +2927,191 @@ fn bond_calc_coupon_yield_basic() {
                     .inner
                     .interest_rate_penalty_for_missed_report.unwrap_or(0)
         );
-        assert_eq!(bond_yields[1].total_yield, 39_057_534_246_240);
+        assert_eq!(bond_yields[1].total_yield, 39_057_534_246_572);
+    });
+}
+
+#[test]
+fn bond_day_count_conventions() {
+    const PRICE: EverUSDBalance = 4_000_000_000_000;
+    // 2% per year, 600 Bond Units
+    let coupon = |days, period_days, convention| {
+        calc_coupon_yield(PRICE, 600, 2000, days, period_days, convention)
+    };
+
+    assert_eq!(coupon(30, 30, BondDayCountConvention::ACT_365), 3_945_205_479_452);
+    assert_eq!(coupon(30, 30, BondDayCountConvention::ACT_360), 4_000_000_000_000);
+    // 31-day period is counted as one 30-day month
+    assert_eq!(coupon(31, 31, BondDayCountConvention::THIRTY_360), 4_000_000_000_000);
+    assert_eq!(coupon(15, 31, BondDayCountConvention::THIRTY_360), 1_935_483_870_967);
+    // quarterly and monthly payment periods
+    assert_eq!(coupon(91, 91, BondDayCountConvention::ACT_ACT), 12_000_000_000_000);
+    assert_eq!(coupon(30, 30, BondDayCountConvention::ACT_ACT), 4_000_000_000_000);
+    assert_eq!(coupon(120, 120, BondDayCountConvention::ACT_ACT), 16_000_000_000_000);
+    // days cannot exceed the period length
+    assert_eq!(
+        coupon(40, 30, BondDayCountConvention::ACT_360),
+        coupon(30, 30, BondDayCountConvention::ACT_360)
+    );
+    assert_eq!(coupon(10, 0, BondDayCountConvention::ACT_ACT), 0);
+}
+
+#[test]
+fn bond_calc_coupon_yield_with_day_count_convention() {
+    const ACCOUNT: u64 = 3;
+    let bondid: BondId = "BOND2".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        let mut bond = get_test_bond().inner;
+        bond.day_count_convention = BondDayCountConvention::ACT_360;
+        assert!(!bond.is_financial_options_eq(&get_test_bond().inner));
+        bond_activate(bondid, ACCOUNT, bond);
+
+        let mut chain_bond_item = Evercity::get_bond(&bondid);
+        let moment: Moment =
+            30000_u64 + (chain_bond_item.inner.start_period.unwrap_or(0)) as u64 * 1000_u64 + 1_u64;
+        assert!(
+            Evercity::calc_and_store_bond_coupon_yield(&bondid, &mut chain_bond_item, moment) > 0
+        );
+        // 1.9 % - (600 + 600) x 4000 usd - 120 days / 360
+        let bond_yields = Evercity::get_coupon_yields(&bondid);
+        assert_eq!(bond_yields[0].total_yield, 30_400_000_000_000);
+    });
+}
+
+#[test]
+fn bond_calc_coupon_yield_on_initialize() {
+    const ACCOUNT: u64 = 3;
+    let bondid: BondId = "BOND2".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        let start_period_end: Moment =
+            30000_u64 + (chain_bond_item.inner.start_period.unwrap_or(0)) as u64 * 1000_u64;
+        let slot = queue_slot(start_period_end, DEFAULT_DAY_DURATION);
+        assert_eq!(
+            Evercity::bond_accrual_queue(slot, 0),
+            Some((start_period_end, bondid))
+        );
+        assert_eq!(
+            Evercity::bond_accrual_moment(&bondid),
+            Some(start_period_end)
+        );
+
+        // period is not passed yet
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(start_period_end - 1);
+        Evercity::on_initialize(1);
+        assert!(Evercity::get_coupon_yields(&bondid).is_empty());
+
+        // pass first (index=0) period
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(start_period_end + 1);
+        Evercity::on_initialize(2);
+
+        let bond_yields = Evercity::get_coupon_yields(&bondid);
+        assert_eq!(bond_yields.len(), 1);
+        assert_eq!(bond_yields[0].total_yield, 29_983_561_643_834);
+        assert_eq!(
+            Evercity::get_bond(&bondid).bond_credit,
+            29_983_561_643_834
+        );
+        // bond waits for the end of next period
+        let next_period_end =
+            start_period_end + chain_bond_item.inner.payment_period as u64 * 1000_u64;
+        assert!(Evercity::bond_accrual_queue(slot, 0).is_none());
+        assert_eq!(
+            Evercity::bond_accrual_queue(queue_slot(next_period_end, DEFAULT_DAY_DURATION), 0),
+            Some((next_period_end, bondid))
+        );
+        assert_eq!(
+            Evercity::bond_accrual_moment(&bondid),
+            Some(next_period_end)
+        );
+    });
+}
+
+#[test]
+fn bond_calc_coupon_yield_on_initialize_respects_weight_limit() {
+    const ACCOUNT: u64 = 3;
+    let bondid: BondId = "BOND2".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        let moment: Moment =
+            30000_u64 + (chain_bond_item.inner.start_period.unwrap_or(0)) as u64 * 1000_u64 + 1;
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(moment);
+
+        // budget is not enough even for single bond
+        Evercity::process_coupon_accruals(0);
+        assert!(Evercity::get_coupon_yields(&bondid).is_empty());
+        assert!(Evercity::bond_accrual_moment(&bondid).is_some());
+    });
+}
+
+#[test]
+fn it_purges_expired_requests_on_initialize() {
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(add_token(INVESTOR2, 10000));
+        assert_ok!(Evercity::token_mint_request_create_everusd(
+            Origin::signed(INVESTOR1),
+            1000,
+            Vec::new()
+        ));
+        assert_ok!(Evercity::token_burn_request_create_everusd(
+            Origin::signed(INVESTOR2),
+            1000,
+            Vec::new(),
+            CUSTODIAN_ID
+        ));
+
+        Evercity::on_initialize(1);
+        assert!(crate::MintRequestEverUSD::<TestRuntime>::contains_key(&INVESTOR1, 1));
+        assert!(crate::BurnRequestEverUSD::<TestRuntime>::contains_key(&INVESTOR2, 0));
+
+        // requests are queued by the day of deadline, including the confirmed one
+        assert_eq!(Evercity::expiry_queue_len(7), 3);
+
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(days2timestamp(10));
+        Evercity::on_initialize(2);
+        assert!(!crate::MintRequestEverUSD::<TestRuntime>::contains_key(&INVESTOR1, 1));
+        assert!(!crate::BurnRequestEverUSD::<TestRuntime>::contains_key(&INVESTOR2, 0));
+        assert!((0..3).all(|index| Evercity::expiry_queue(7, index).is_none()));
+        assert_eq!(Evercity::expiry_queue_len(7), 0);
+        assert_eq!(Evercity::expiry_cursor(), Some((10, 0)));
+    });
+}
+
+#[test]
+fn it_dequeues_expired_items_behind_not_expired_ones() {
+    use crate::{ExpiryCursor, ExpiryQueue, ExpiryQueueLen};
+    let dequeue = |until| {
+        Evercity::dequeue::<ExpiryQueue<TestRuntime>, ExpiryQueueLen, ExpiryCursor, _>(until, 10)
+    };
+
+    new_test_ext().execute_with(|| {
+        Timestamp::set_timestamp(1000);
+        Evercity::schedule_expiry(5000, ExpiryItem::Swap(0));
+        Evercity::schedule_expiry(2000, ExpiryItem::Swap(1));
+
+        let (taken, used) = dequeue(3000);
+        assert_eq!(taken, vec![(2000, ExpiryItem::Swap(1))]);
+        // slot and both items are visited
+        assert_eq!(used, 3);
+        // cursor stays at the item, which is not expired yet
+        assert_eq!(Evercity::expiry_cursor(), Some((0, 0)));
+
+        let (taken, _) = dequeue(6000);
+        assert_eq!(taken, vec![(5000, ExpiryItem::Swap(0))]);
+        assert_eq!(Evercity::expiry_cursor(), Some((0, 2)));
     });
 }
 
@@ -1636,8 +3165,8 @@ fn bond_calc_coupon_yield_advanced() {
         Evercity::set_balance(&INVESTOR1, 0);
         Evercity::set_balance(&INVESTOR2, 0);
 
-        Evercity::set_balance(&ACCOUNT1, 124668493149600 + 4000 * 600 * 2 * UNIT);
-        Evercity::set_balance(&ACCOUNT2, 124668493149600 + 4000 * 600 * 2 * UNIT);
+        Evercity::set_balance(&ACCOUNT1, 124668493150682 + 4000 * 600 * 2 * UNIT);
+        Evercity::set_balance(&ACCOUNT2, 124668493150682 + 4000 * 600 * 2 * UNIT);
 
         let mut chain_bond_item1 = deposit(ACCOUNT1, bondid1, 20000 * UNIT);
         let mut chain_bond_item2 = deposit(ACCOUNT2, bondid2, 20000 * UNIT);
@@ -1668,10 +3197,10 @@ fn bond_calc_coupon_yield_advanced() {
                 .map(|x| x.total_yield)
                 .collect::<Vec<_>>(),
             [
-                29983561643520,
-                37873972602360,
-                45764383561200,
-                53654794520040
+                29983561643834,
+                37873972602738,
+                45764383561642,
+                53654794520546
             ]
         );
 
@@ -1709,7 +3238,7 @@ fn bond_calc_coupon_yield_advanced() {
         let balance1 = Evercity::balances_everusd(INVESTOR1);
         let balance2 = Evercity::balances_everusd(INVESTOR2);
         assert_eq!(balance1, balance2);
-        assert_eq!(balance1, 26827397260020); // 2 * 26827397260020 = 53654794520040
+        assert_eq!(balance1, 26827397260273); // 2 * 26827397260273 = 53654794520546
         println!("balance investor1 {}, investor2 {}", balance1, balance2);
 
         chain_bond_item1 = deposit(ACCOUNT1, bondid1, 20000 * UNIT);
@@ -1726,7 +3255,7 @@ fn bond_calc_coupon_yield_advanced() {
         let balance1 = Evercity::balances_everusd(INVESTOR1);
         let balance2 = Evercity::balances_everusd(INVESTOR2);
         assert_eq!(balance1, balance2);
-        assert_eq!(balance1, 26827397260020);
+        assert_eq!(balance1, 26827397260273);
         println!("balance investor1 {}, investor2 {}", balance1, balance2);
         println!("{:?}", chain_bond_item1);
 
@@ -1751,7 +3280,7 @@ fn bond_calc_coupon_yield_advanced() {
         let balance2 = Evercity::balances_everusd(INVESTOR2);
 
         assert_eq!(balance1, balance2);
-        assert_eq!(balance1, 124668493149600 / 2 + 4000 * 600 * UNIT);
+        assert_eq!(balance1, 124668493150682 / 2 + 4000 * 600 * UNIT);
         // check bond debt after been redeemed
         chain_bond_item1 = Evercity::get_bond(&bondid1);
         chain_bond_item2 = Evercity::get_bond(&bondid2);
@@ -1791,7 +3320,7 @@ fn bond_restore_from_bankrupt() {
         }
         //reset balance
         Evercity::set_balance(&INVESTOR1, 0);
-        Evercity::set_balance(&ACCOUNT1, 124668493149600 + 4000 * 600 * 2 * UNIT);
+        Evercity::set_balance(&ACCOUNT1, 124668493150682 + 4000 * 600 * 2 * UNIT);
 
         let mut investor_balance = 0;
 
@@ -1858,7 +3387,7 @@ fn bond_withdraw_everusd() {
         }
         //reset balance
         Evercity::set_balance(&INVESTOR1, 0);
-        Evercity::set_balance(&ACCOUNT1, 124668493149600 + 4000 * 600 * 2 * UNIT);
+        Evercity::set_balance(&ACCOUNT1, 124668493150682 + 4000 * 600 * 2 * UNIT);
 
         let mut now = start_moment + (130 * DEFAULT_DAY_DURATION) as u64 * 1000;
         <pallet_timestamp::Module<TestRuntime>>::set_timestamp(now);
@@ -2055,8 +3584,8 @@ fn bond_calc_redeemed_yield() {
             125_000_000_000_000
         );
         assert_eq!(yield1, yield2);
-        assert_eq!(yield1, 62_334_246_574_800);
-        assert_eq!(Evercity::balance_everusd(&ACCOUNT), 331_506_850_400);
+        assert_eq!(yield1, 62_334_246_575_341);
+        assert_eq!(Evercity::balance_everusd(&ACCOUNT), 331_506_849_318);
 
         assert_eq!(chain_bond_item.state, BondState::FINISHED);
         // @TODO descrees credit on redemption
@@ -2244,7 +3773,17 @@ fn bond_release(bondid: BondId, acc: u64, mut bond: BondInnerStruct) -> BondStru
     Evercity::get_bond(&bondid)
 }
 
-fn bond_activate(bondid: BondId, acc: u64, mut bond: BondInnerStruct) {
+fn bond_activate(bondid: BondId, acc: u64, bond: BondInnerStruct) {
+    bond_activate_with_auditors(bondid, acc, bond, Vec::new(), 1);
+}
+
+fn bond_activate_with_auditors(
+    bondid: BondId,
+    acc: u64,
+    mut bond: BondInnerStruct,
+    auditors: Vec<u64>,
+    quorum: u32,
+) {
     const MASTER: u64 = 1;
     const AUDITOR: u64 = 5;
     const INVESTOR1: u64 = 4;
@@ -2290,13 +3829,21 @@ fn bond_activate(bondid: BondId, acc: u64, mut bond: BondInnerStruct) {
         bondid,
         AUDITOR
     ));
+    if !auditors.is_empty() {
+        assert_ok!(Evercity::bond_set_auditors(
+            Origin::signed(MASTER),
+            bondid,
+            auditors,
+            quorum
+        ));
+    }
 
     // Activate bond
     <pallet_timestamp::Module<TestRuntime>>::set_timestamp(30000);
     assert_ok!(Evercity::bond_activate(
         Origin::signed(MASTER),
         bondid,
-        chain_bond_item.nonce + 1
+        Evercity::get_bond(&bondid).nonce
     ));
     let chain_bond_item = Evercity::get_bond(&bondid);
 
@@ -2375,57 +3922,195 @@ fn bond_create_release_update() {
 }
 
 #[test]
-fn bond_activate_bond_and_withdraw_bondfund() {
+fn bond_document_add_amend_acknowledge() {
     const ACCOUNT: u64 = 3;
+    const MASTER: u64 = 1;
+    const AUDITOR: u64 = 5;
+    const INVESTOR: u64 = 4;
     let bondid: BondId = "BOND1".into();
+    let hash0 = Blake2_256::hash(b"doc0").into();
+    let hash1 = Blake2_256::hash(b"doc1").into();
+    let hash2 = Blake2_256::hash(b"doc2").into();
 
     new_test_ext().execute_with(|| {
-        bond_grand_everusd();
-        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
-        let chain_bond_item = Evercity::get_bond(&bondid);
-        assert_eq!(chain_bond_item.state, BondState::ACTIVE);
-        assert_eq!(chain_bond_item.active_start_date, 30000);
-        assert_eq!(chain_bond_item.bond_debit, 0);
-        assert_eq!(chain_bond_item.bond_credit, 0);
-
-        assert_eq!(
-            Evercity::balance_everusd(&ACCOUNT),
-            1200 * 4_000_000_000_000
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, get_test_bond().inner));
+        assert_noop!(
+            Evercity::bond_document_add(Origin::signed(INVESTOR), bondid, BondDocumentType::LEGAL, hash0, b"ipfs://doc0".to_vec()),
+            RuntimeError::BondAccessDenied
         );
+        assert_ok!(Evercity::bond_document_add(
+            Origin::signed(ACCOUNT),
+            bondid,
+            BondDocumentType::LEGAL,
+            hash0,
+            b"ipfs://doc0".to_vec()
+        ));
+        // root of single document pack is the document hash
+        assert_eq!(Evercity::get_bond(&bondid).inner.docs_pack_root_hash_legal, hash0);
 
+        assert_ok!(Evercity::bond_document_add(
+            Origin::signed(ACCOUNT),
+            bondid,
+            BondDocumentType::LEGAL,
+            hash1,
+            b"ipfs://doc1".to_vec()
+        ));
         let chain_bond_item = Evercity::get_bond(&bondid);
-        assert_eq!(chain_bond_item.bond_debit, 0);
-        assert_eq!(
-            Evercity::balance_everusd(&ACCOUNT),
-            1200 * 4_000_000_000_000
-        );
-        assert_eq!(Evercity::bond_packages(&bondid).is_empty(), false);
-        let acquired_bond_units: BondUnitAmount = Evercity::bond_packages(&bondid)
-            .iter()
-            .map(|(_, packages)| {
-                packages
-                    .iter()
-                    .map(|package| package.bond_units)
-                    .sum::<BondUnitAmount>()
-            })
-            .sum::<BondUnitAmount>();
-
-        assert_eq!(acquired_bond_units, 1200);
-    });
-}
+        assert_eq!(chain_bond_item.inner.docs_pack_root_hash_legal, BlakeTwo256::hash_of(&(hash0, hash1)));
+        assert_eq!(chain_bond_item.inner.docs_pack_root_hash_main, Default::default());
+        assert_eq!(chain_bond_item.nonce, 2);
 
-#[test]
-fn bond_buy_bond_units_after_activation() {
-    const ACCOUNT: u64 = 3;
-    const INVESTOR1: u64 = 4;
-    let bondid: BondId = "BOND1".into();
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(10_000);
+        assert_ok!(Evercity::bond_set_auditor(Origin::signed(MASTER), bondid, AUDITOR));
+        assert_ok!(Evercity::bond_release(Origin::signed(MASTER), bondid, 3));
 
-    new_test_ext().execute_with(|| {
-        bond_grand_everusd();
-        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
-        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(600_000);
-        assert_ok!(Evercity::bond_unit_package_buy(
-            Origin::signed(INVESTOR1),
+        // after release documents can only be amended
+        assert_noop!(
+            Evercity::bond_document_add(Origin::signed(ACCOUNT), bondid, BondDocumentType::LEGAL, hash2, Vec::new()),
+            RuntimeError::BondStateNotPermitAction
+        );
+        assert_noop!(
+            Evercity::bond_document_amend(Origin::signed(ACCOUNT), bondid, 2, hash2, Vec::new()),
+            RuntimeError::DocumentNotFound
+        );
+        assert_ok!(Evercity::bond_document_amend(
+            Origin::signed(ACCOUNT),
+            bondid,
+            0,
+            hash2,
+            b"ipfs://doc2".to_vec()
+        ));
+        assert_noop!(
+            Evercity::bond_document_amend(Origin::signed(ACCOUNT), bondid, 0, hash2, Vec::new()),
+            RuntimeError::DocumentParamIncorrect
+        );
+
+        let original = Evercity::bond_document(&bondid, 0).unwrap();
+        let amendment = Evercity::bond_document(&bondid, 2).unwrap();
+        assert_eq!(original.content_hash, hash0);
+        assert_eq!(original.amended_by, Some(2));
+        assert_eq!(amendment.amends, Some(0));
+        assert_eq!(amendment.version, 2);
+        assert_eq!(amendment.doc_type, BondDocumentType::LEGAL);
+        assert_eq!(amendment.uploader, ACCOUNT);
+        assert_eq!(amendment.create_date, 10_000);
+        assert_eq!(Evercity::bond_documents(&bondid).len(), 3);
+
+        // amended version is excluded from the pack
+        let root = BlakeTwo256::hash_of(&(hash1, hash2));
+        assert_eq!(Evercity::get_bond(&bondid).inner.docs_pack_root_hash_legal, root);
+
+        // bond update cannot change root hash of registered documents
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_ok!(Evercity::bond_update(
+            Origin::signed(ACCOUNT),
+            bondid,
+            chain_bond_item.nonce,
+            get_test_bond().inner
+        ));
+        assert_eq!(Evercity::get_bond(&bondid).inner.docs_pack_root_hash_legal, root);
+
+        // acknowledgement
+        assert_noop!(
+            Evercity::bond_document_acknowledge(Origin::signed(INVESTOR), bondid, 2),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_noop!(
+            Evercity::bond_document_acknowledge(Origin::signed(AUDITOR), bondid, 3),
+            RuntimeError::DocumentNotFound
+        );
+        assert_ok!(Evercity::bond_document_acknowledge(Origin::signed(AUDITOR), bondid, 2));
+        assert_noop!(
+            Evercity::bond_document_acknowledge(Origin::signed(AUDITOR), bondid, 2),
+            RuntimeError::DocumentParamIncorrect
+        );
+        assert_eq!(Evercity::bond_document(&bondid, 2).unwrap().acknowledged_by, vec![AUDITOR]);
+    });
+}
+
+#[test]
+fn bond_document_removed_on_revoke() {
+    const ACCOUNT: u64 = 3;
+    let bondid: BondId = "BOND1".into();
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, get_test_bond().inner));
+        assert_ok!(Evercity::bond_document_add(
+            Origin::signed(ACCOUNT),
+            bondid,
+            BondDocumentType::TECH,
+            Blake2_256::hash(b"doc0").into(),
+            b"ipfs://doc0".to_vec()
+        ));
+        assert_noop!(
+            Evercity::bond_document_add(
+                Origin::signed(ACCOUNT),
+                bondid,
+                BondDocumentType::TECH,
+                Default::default(),
+                vec![0; crate::document::MAX_DOCUMENT_URI_LEN + 1]
+            ),
+            RuntimeError::DocumentParamIncorrect
+        );
+        assert_ok!(Evercity::bond_revoke(Origin::signed(ACCOUNT), bondid));
+
+        assert!(Evercity::bond_documents(&bondid).is_empty());
+        assert_eq!(Evercity::bond_document_count(&bondid), 0);
+    });
+}
+
+#[test]
+fn bond_activate_bond_and_withdraw_bondfund() {
+    const ACCOUNT: u64 = 3;
+    let bondid: BondId = "BOND1".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.state, BondState::ACTIVE);
+        assert_eq!(chain_bond_item.active_start_date, 30000);
+        assert_eq!(chain_bond_item.bond_debit, 0);
+        assert_eq!(chain_bond_item.bond_credit, 0);
+
+        assert_eq!(
+            Evercity::balance_everusd(&ACCOUNT),
+            1200 * 4_000_000_000_000
+        );
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.bond_debit, 0);
+        assert_eq!(
+            Evercity::balance_everusd(&ACCOUNT),
+            1200 * 4_000_000_000_000
+        );
+        assert_eq!(Evercity::bond_packages(&bondid).is_empty(), false);
+        let acquired_bond_units: BondUnitAmount = Evercity::bond_packages(&bondid)
+            .iter()
+            .map(|(_, packages)| {
+                packages
+                    .iter()
+                    .map(|package| package.bond_units)
+                    .sum::<BondUnitAmount>()
+            })
+            .sum::<BondUnitAmount>();
+
+        assert_eq!(acquired_bond_units, 1200);
+    });
+}
+
+#[test]
+fn bond_buy_bond_units_after_activation() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    let bondid: BondId = "BOND1".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(600_000);
+        assert_ok!(Evercity::bond_unit_package_buy(
+            Origin::signed(INVESTOR1),
             bondid,
             3,
             400
@@ -2895,7 +4580,7 @@ fn bond_interest_rate_rnd() {
         );
         for period in 0..periods {
             let interest_rate = bond_coupon_yields[period + 1].interest_rate;
-            let impact_data = impact_reports[period].impact_data;
+            let impact_data = impact_reports[period].impact_data[0];
             assert_eq!(impact_data, 20000_u64);
             // if impact data is less than baseline value then  interest rate is more than base value
             assert_eq!(
@@ -2998,13 +4683,13 @@ fn bond_impact_report_send_approve() {
                 Origin::signed(ACCOUNT1),
                 bondid,
                 period,
-                bond.impact_data_baseline[period as usize].unwrap_or(0)
+                vec![bond.impact_data_baseline[period as usize].unwrap_or(0)]
             ));
             assert_ok!(Evercity::bond_impact_report_approve(
                 Origin::signed(AUDITOR),
                 bondid,
                 period,
-                bond.impact_data_baseline[period as usize].unwrap_or(0)
+                vec![bond.impact_data_baseline[period as usize].unwrap_or(0)]
             ));
         }
         <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
@@ -3039,137 +4724,446 @@ fn bond_impact_report_send_approve() {
 }
 
 #[test]
-fn bond_impact_report_try_approve_unauthorized() {
+fn bond_impact_report_with_kpis() {
     const ACCOUNT1: u64 = 3;
     const AUDITOR: u64 = 5;
     let bondid: BondId = "BOND1".into();
 
     new_test_ext().execute_with(|| {
         bond_grand_everusd();
-        let bond = get_test_bond().inner;
+        let bond = get_test_bond_kpis().inner;
         bond_activate(bondid, ACCOUNT1, bond.clone());
 
         let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.inner.impact_data_kpis, bond.impact_data_kpis);
         // first period
         <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
             chain_bond_item.active_start_date + 1000_u64 * (bond.start_period.unwrap_or(0) - 1) as u64,
         );
 
-        // send report
+        // report must contain value for every KPI
+        assert_noop!(
+            Evercity::bond_impact_report_send(Origin::signed(ACCOUNT1), bondid, 0, vec![30000]),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_noop!(
+            Evercity::bond_impact_report_send(Origin::signed(ACCOUNT1), bondid, 0, vec![30000, 1000, 100, 1]),
+            RuntimeError::BondParamIncorrect
+        );
         assert_ok!(Evercity::bond_impact_report_send(
             Origin::signed(ACCOUNT1),
             bondid,
             0,
-            1000
+            vec![30000, 1000, 100]
         ));
 
-        for acc in iter_accounts().filter(|acc| *acc != AUDITOR) {
-            assert_noop!(
-                Evercity::bond_impact_report_approve(Origin::signed(acc), bondid, 0, 1000),
-                RuntimeError::AccountNotAuthorized
-            );
-        }
-
-        // make amend
-
+        // auditor must confirm all the values
         assert_ok!(Evercity::bond_impact_report_approve(
             Origin::signed(AUDITOR),
             bondid,
             0,
-            1000
+            vec![30000, 1000, 101]
+        ));
+        assert!(!Evercity::impact_reports(&bondid)[0].signed);
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(AUDITOR),
+            bondid,
+            0,
+            vec![30000, 1000, 100]
+        ));
+
+        let report = &Evercity::impact_reports(&bondid)[0];
+        assert!(report.signed);
+        assert_eq!(report.impact_data, vec![30000, 1000, 100]);
+        assert_eq!(
+            Evercity::calc_bond_interest_rate(&chain_bond_item, &Evercity::impact_reports(&bondid), 1),
+            1500
+        );
+    });
+}
+
+fn add_auditors(auditors: &[u64]) {
+    const MASTER: u64 = 1;
+    for &acc in auditors {
+        assert_ok!(Evercity::account_add_with_role_and_data(
+            Origin::signed(MASTER),
+            acc,
+            AUDITOR_ROLE_MASK,
+            acc * 10
         ));
+    }
+}
+
+#[test]
+fn bond_set_auditors() {
+    const ACCOUNT: u64 = 3;
+    const MASTER: u64 = 1;
+    const AUDITOR: u64 = 5;
+    let bondid: BondId = "BOND1".into();
+
+    new_test_ext().execute_with(|| {
+        add_auditors(&[101, 102]);
+        assert_ok!(Evercity::bond_add_new(Origin::signed(ACCOUNT), bondid, get_test_bond().inner));
+        assert_ok!(Evercity::bond_set_auditor(Origin::signed(MASTER), bondid, AUDITOR));
+
+        assert_noop!(
+            Evercity::bond_set_auditors(Origin::signed(ACCOUNT), bondid, vec![101, 102], 2),
+            RuntimeError::AccountNotAuthorized
+        );
+        // all accounts must have AUDITOR role
+        assert_noop!(
+            Evercity::bond_set_auditors(Origin::signed(MASTER), bondid, vec![101, 4], 2),
+            RuntimeError::AccountRoleParamIncorrect
+        );
+        // auditors must be unique
+        assert_noop!(
+            Evercity::bond_set_auditors(Origin::signed(MASTER), bondid, vec![101, 101], 2),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_noop!(
+            Evercity::bond_set_auditors(Origin::signed(MASTER), bondid, vec![101, AUDITOR], 2),
+            RuntimeError::BondParamIncorrect
+        );
+        // quorum must be reachable
+        assert_noop!(
+            Evercity::bond_set_auditors(Origin::signed(MASTER), bondid, vec![101, 102], 0),
+            RuntimeError::BondParamIncorrect
+        );
+        assert_noop!(
+            Evercity::bond_set_auditors(Origin::signed(MASTER), bondid, vec![101, 102], 4),
+            RuntimeError::BondParamIncorrect
+        );
+
+        assert_ok!(Evercity::bond_set_auditors(Origin::signed(MASTER), bondid, vec![101, 102], 3));
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.auditors, vec![101, 102]);
+        assert_eq!(chain_bond_item.approval_quorum(), 3);
+        assert!(chain_bond_item.is_auditor(&AUDITOR));
+        assert!(chain_bond_item.is_auditor(&102));
+        assert!(!chain_bond_item.is_auditor(&ACCOUNT));
+
+        assert_noop!(
+            Evercity::bond_set_auditor(Origin::signed(MASTER), bondid, 101),
+            RuntimeError::BondParamIncorrect
+        );
     });
 }
 
 #[test]
-fn bond_impact_report_try_approve_unattended() {
+fn bond_impact_report_quorum_approve() {
     const ACCOUNT1: u64 = 3;
     const AUDITOR: u64 = 5;
     let bondid: BondId = "BOND1".into();
 
     new_test_ext().execute_with(|| {
+        System::set_block_number(1);
         bond_grand_everusd();
+        add_auditors(&[101, 102, 103]);
         let bond = get_test_bond().inner;
-        bond_activate(bondid, ACCOUNT1, bond.clone());
+        bond_activate_with_auditors(bondid, ACCOUNT1, bond.clone(), vec![101, 102], 2);
 
         let chain_bond_item = Evercity::get_bond(&bondid);
-        // first period
         <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
             chain_bond_item.active_start_date + 1000_u64 * (bond.start_period.unwrap_or(0) - 1) as u64,
         );
-        // try approve without report
+        assert_ok!(Evercity::bond_impact_report_send(
+            Origin::signed(ACCOUNT1),
+            bondid,
+            0,
+            vec![20000]
+        ));
+
+        // auditor, not assigned to the bond
+        assert_noop!(
+            Evercity::bond_impact_report_approve(Origin::signed(103), bondid, 0, vec![20000]),
+            RuntimeError::BondAccessDenied
+        );
+        // approved data must have value of every KPI
         assert_noop!(
-            Evercity::bond_impact_report_approve(Origin::signed(AUDITOR), bondid, 0, 0),
+            Evercity::bond_impact_report_approve(
+                Origin::signed(AUDITOR),
+                bondid,
+                0,
+                vec![20000, 1]
+            ),
             RuntimeError::BondParamIncorrect
         );
 
-        // make amend
-        assert_ok!(Evercity::bond_impact_report_send(
-            Origin::signed(ACCOUNT1),
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(AUDITOR),
             bondid,
             0,
-            0
+            vec![20000]
         ));
+        assert!(!Evercity::impact_reports(&bondid)[0].signed);
 
+        // conflicting approval does not count
         assert_ok!(Evercity::bond_impact_report_approve(
-            Origin::signed(AUDITOR),
+            Origin::signed(101),
             bondid,
             0,
-            0
+            vec![19000]
+        ));
+        assert!(!Evercity::impact_reports(&bondid)[0].signed);
+        assert_eq!(Evercity::impact_report_approvals((bondid, 0_u32), 101_u64), Some(vec![19000]));
+        assert!(System::events().iter().any(|record| record.event
+            == Event::pallet_evercity(crate::RawEvent::BondImpactReportDisputed(101, bondid, 0, vec![19000]))));
+
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(102),
+            bondid,
+            0,
+            vec![20000]
         ));
+        assert!(Evercity::impact_reports(&bondid)[0].signed);
+        assert_eq!(Evercity::impact_report_approvals((bondid, 0_u32), AUDITOR), Some(vec![20000]));
+        assert_eq!(Evercity::impact_report_approvals((bondid, 0_u32), 102_u64), Some(vec![20000]));
+
+        // signed report cannot be approved again
+        assert_noop!(
+            Evercity::bond_impact_report_approve(Origin::signed(101), bondid, 0, vec![20000]),
+            RuntimeError::BondParamIncorrect
+        );
     });
 }
 
 #[test]
-fn bond_impact_report_outof_order() {
+fn bond_impact_report_ignores_approvals_of_replaced_auditor() {
     const ACCOUNT1: u64 = 3;
     const AUDITOR: u64 = 5;
     let bondid: BondId = "BOND1".into();
 
     new_test_ext().execute_with(|| {
         bond_grand_everusd();
+        add_auditors(&[101, 102, 103]);
         let bond = get_test_bond().inner;
-        bond_activate(bondid, ACCOUNT1, bond.clone());
+        bond_activate_with_auditors(bondid, ACCOUNT1, bond.clone(), vec![101, 102], 2);
 
         let chain_bond_item = Evercity::get_bond(&bondid);
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+            chain_bond_item.active_start_date
+                + 1000_u64 * (bond.start_period.unwrap_or(0) - 1) as u64,
+        );
+        assert_ok!(Evercity::bond_impact_report_send(
+            Origin::signed(ACCOUNT1),
+            bondid,
+            0,
+            vec![20000]
+        ));
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(AUDITOR),
+            bondid,
+            0,
+            vec![20000]
+        ));
 
-        for period in 0..bond.bond_duration {
-            // before start of the report period
-            <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
-                chain_bond_item.active_start_date
-                    + 1000_u64
-                        * (bond.start_period.unwrap_or(0) + period * bond.payment_period
-                            - bond.impact_data_send_period
-                            - 1) as u64,
-            );
-            assert_noop!(
-                Evercity::bond_impact_report_send(
-                    Origin::signed(ACCOUNT1),
-                    bondid,
-                    period,
-                    bond.impact_data_baseline[period as usize].unwrap_or(0)
-                ),
-                RuntimeError::BondOutOfOrder
-            );
-
-            // after current period end
-            <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
-                chain_bond_item.active_start_date
-                    + 1000_u64 * (bond.start_period.unwrap_or(0) + period * bond.payment_period + 1) as u64,
-            );
+        // bondholder meeting replaces the auditor (SetAuditor proposal)
+        crate::BondRegistry::<TestRuntime>::mutate(&bondid, |item| item.auditor = 103);
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(103),
+            bondid,
+            0,
+            vec![20000]
+        ));
+        assert!(!Evercity::impact_reports(&bondid)[0].signed);
 
-            assert_noop!(
-                Evercity::bond_impact_report_send(
-                    Origin::signed(ACCOUNT1),
-                    bondid,
-                    period,
-                    bond.impact_data_baseline[period as usize].unwrap_or(0)
-                ),
-                RuntimeError::BondOutOfOrder
-            );
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(102),
+            bondid,
+            0,
+            vec![20000]
+        ));
+        assert!(Evercity::impact_reports(&bondid)[0].signed);
+    });
+}
 
-            // between report period start and  current period end
-            <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+#[test]
+fn bond_impact_report_resend_discards_approvals() {
+    const ACCOUNT1: u64 = 3;
+    const AUDITOR: u64 = 5;
+    let bondid: BondId = "BOND1".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        add_auditors(&[101, 102]);
+        let bond = get_test_bond().inner;
+        bond_activate_with_auditors(bondid, ACCOUNT1, bond.clone(), vec![101, 102], 2);
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+            chain_bond_item.active_start_date + 1000_u64 * (bond.start_period.unwrap_or(0) - 1) as u64,
+        );
+        assert_ok!(Evercity::bond_impact_report_send(
+            Origin::signed(ACCOUNT1),
+            bondid,
+            0,
+            vec![20000]
+        ));
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(AUDITOR),
+            bondid,
+            0,
+            vec![20000]
+        ));
+
+        // new version of the report requires new approvals
+        assert_ok!(Evercity::bond_impact_report_send(
+            Origin::signed(ACCOUNT1),
+            bondid,
+            0,
+            vec![21000]
+        ));
+        assert_eq!(Evercity::impact_report_approvals((bondid, 0_u32), AUDITOR), None);
+
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(101),
+            bondid,
+            0,
+            vec![21000]
+        ));
+        assert!(!Evercity::impact_reports(&bondid)[0].signed);
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(AUDITOR),
+            bondid,
+            0,
+            vec![21000]
+        ));
+        assert!(Evercity::impact_reports(&bondid)[0].signed);
+    });
+}
+
+#[test]
+fn bond_impact_report_try_approve_unauthorized() {
+    const ACCOUNT1: u64 = 3;
+    const AUDITOR: u64 = 5;
+    let bondid: BondId = "BOND1".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        let bond = get_test_bond().inner;
+        bond_activate(bondid, ACCOUNT1, bond.clone());
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        // first period
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+            chain_bond_item.active_start_date + 1000_u64 * (bond.start_period.unwrap_or(0) - 1) as u64,
+        );
+
+        // send report
+        assert_ok!(Evercity::bond_impact_report_send(
+            Origin::signed(ACCOUNT1),
+            bondid,
+            0,
+            vec![1000]
+        ));
+
+        for acc in iter_accounts().filter(|acc| *acc != AUDITOR) {
+            assert_noop!(
+                Evercity::bond_impact_report_approve(Origin::signed(acc), bondid, 0, vec![1000]),
+                RuntimeError::AccountNotAuthorized
+            );
+        }
+
+        // make amend
+
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(AUDITOR),
+            bondid,
+            0,
+            vec![1000]
+        ));
+    });
+}
+
+#[test]
+fn bond_impact_report_try_approve_unattended() {
+    const ACCOUNT1: u64 = 3;
+    const AUDITOR: u64 = 5;
+    let bondid: BondId = "BOND1".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        let bond = get_test_bond().inner;
+        bond_activate(bondid, ACCOUNT1, bond.clone());
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        // first period
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+            chain_bond_item.active_start_date + 1000_u64 * (bond.start_period.unwrap_or(0) - 1) as u64,
+        );
+        // try approve without report
+        assert_noop!(
+            Evercity::bond_impact_report_approve(Origin::signed(AUDITOR), bondid, 0, vec![0]),
+            RuntimeError::BondParamIncorrect
+        );
+
+        // make amend
+        assert_ok!(Evercity::bond_impact_report_send(
+            Origin::signed(ACCOUNT1),
+            bondid,
+            0,
+            vec![0]
+        ));
+
+        assert_ok!(Evercity::bond_impact_report_approve(
+            Origin::signed(AUDITOR),
+            bondid,
+            0,
+            vec![0]
+        ));
+    });
+}
+
+#[test]
+fn bond_impact_report_outof_order() {
+    const ACCOUNT1: u64 = 3;
+    const AUDITOR: u64 = 5;
+    let bondid: BondId = "BOND1".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        let bond = get_test_bond().inner;
+        bond_activate(bondid, ACCOUNT1, bond.clone());
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+
+        for period in 0..bond.bond_duration {
+            // before start of the report period
+            <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+                chain_bond_item.active_start_date
+                    + 1000_u64
+                        * (bond.start_period.unwrap_or(0) + period * bond.payment_period
+                            - bond.impact_data_send_period
+                            - 1) as u64,
+            );
+            assert_noop!(
+                Evercity::bond_impact_report_send(
+                    Origin::signed(ACCOUNT1),
+                    bondid,
+                    period,
+                    vec![bond.impact_data_baseline[period as usize].unwrap_or(0)]
+                ),
+                RuntimeError::BondOutOfOrder
+            );
+
+            // after current period end
+            <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+                chain_bond_item.active_start_date
+                    + 1000_u64 * (bond.start_period.unwrap_or(0) + period * bond.payment_period + 1) as u64,
+            );
+
+            assert_noop!(
+                Evercity::bond_impact_report_send(
+                    Origin::signed(ACCOUNT1),
+                    bondid,
+                    period,
+                    vec![bond.impact_data_baseline[period as usize].unwrap_or(0)]
+                ),
+                RuntimeError::BondOutOfOrder
+            );
+
+            // between report period start and  current period end
+            <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
                 chain_bond_item.active_start_date
                     + 1000_u64 * (bond.start_period.unwrap_or(0) + period * bond.payment_period - 1000) as u64,
             );
@@ -3178,14 +5172,14 @@ fn bond_impact_report_outof_order() {
                 Origin::signed(ACCOUNT1),
                 bondid,
                 period,
-                bond.impact_data_baseline[period as usize].unwrap_or(0)
+                vec![bond.impact_data_baseline[period as usize].unwrap_or(0)]
             ));
 
             assert_ok!(Evercity::bond_impact_report_approve(
                 Origin::signed(AUDITOR),
                 bondid,
                 period,
-                bond.impact_data_baseline[period as usize].unwrap_or(0)
+                vec![bond.impact_data_baseline[period as usize].unwrap_or(0)]
             ));
         }
     });
@@ -3284,161 +5278,1328 @@ fn bond_acquire_try_after_redemption() {
     });
 }
 
+fn get_test_call_schedule() -> BondCallScheduleStruct {
+    BondCallScheduleStruct {
+        call_periods: vec![2, 6],
+        premium_bps: 200, // 2%
+    }
+}
+
 #[test]
-fn bond_deposit_bond() {
+fn bond_with_call_schedule_validation() {
+    let mut bond = get_test_bond().inner;
+    bond.call_schedule = Some(get_test_call_schedule());
+    assert!(bond.is_valid(DEFAULT_DAY_DURATION));
+    assert!(!bond.is_financial_options_eq(&get_test_bond().inner));
+
+    let mut bond1 = bond.clone();
+    bond1.call_schedule = Some(BondCallScheduleStruct {
+        call_periods: vec![6, 2],
+        premium_bps: 200,
+    });
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+
+    let mut bond1 = bond.clone();
+    bond1.call_schedule = Some(BondCallScheduleStruct {
+        call_periods: vec![0],
+        premium_bps: 200,
+    });
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+
+    // maturity date cannot be a call date
+    let mut bond1 = bond.clone();
+    bond1.call_schedule = Some(BondCallScheduleStruct {
+        call_periods: vec![bond.get_periods()],
+        premium_bps: 200,
+    });
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+
+    let mut bond1 = bond;
+    bond1.call_schedule = Some(BondCallScheduleStruct {
+        call_periods: vec![2],
+        premium_bps: 10_001,
+    });
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+}
+
+#[test]
+fn bond_call_before_maturity() {
     const ACCOUNT: u64 = 3;
     const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
     let bondid: BondId = "BOND".into();
 
     new_test_ext().execute_with(|| {
         bond_grand_everusd();
-        assert!(Evercity::evercity_balance().is_ok());
-
-        let bond = get_test_bond().inner;
+        let mut bond = get_test_bond().inner;
+        bond.call_schedule = Some(get_test_call_schedule());
         bond_activate(bondid, ACCOUNT, bond.clone());
+        assert_ok!(add_token(ACCOUNT, 1_000_000_000_000_000));
         let chain_bond_item = Evercity::get_bond(&bondid);
+        let start_period = bond.start_period.unwrap_or(0);
 
+        // the first period is not a call date
         <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
-            chain_bond_item.active_start_date + 1000_u64 * (bond.start_period.unwrap_or(0) + 1) as u64,
+            chain_bond_item.active_start_date + 1000_u64 * start_period as u64,
         );
-
-        assert_eq!(chain_bond_item.bond_debit, 0);
-        assert_eq!(chain_bond_item.coupon_yield, 0);
-        assert_eq!(
-            Evercity::balance_everusd(&ACCOUNT),
-            1200 * 4_000_000_000_000
+        assert_noop!(
+            Evercity::bond_call(Origin::signed(ACCOUNT), bondid),
+            RuntimeError::BondOutOfOrder
         );
 
-        assert_ok!(Evercity::bond_deposit_everusd(
-            Origin::signed(ACCOUNT),
-            bondid,
-            100_000_000_000_000
-        ));
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+            chain_bond_item.active_start_date + 1000_u64 * (start_period + bond.payment_period) as u64,
+        );
+        assert_noop!(
+            Evercity::bond_call(Origin::signed(INVESTOR1), bondid),
+            RuntimeError::BondAccessDenied
+        );
+        let balance = Evercity::balance_everusd(&ACCOUNT);
+        assert_ok!(Evercity::bond_call(Origin::signed(ACCOUNT), bondid));
 
         let chain_bond_item = Evercity::get_bond(&bondid);
-        assert_eq!(chain_bond_item.bond_debit, 100_000_000_000_000);
-        assert_eq!(chain_bond_item.coupon_yield, 0);
-        assert!(Evercity::evercity_balance().is_ok());
-
+        assert_eq!(chain_bond_item.state, BondState::FINISHED);
+        assert_eq!(chain_bond_item.call_premium_bps, 200);
+        // coupon yield is accrued up to the call date
+        assert_eq!(Evercity::get_coupon_yields(&bondid).len(), 2);
+        let redemption_value = 1200 * 4_080_000_000_000;
+        let coupon_yield = chain_bond_item.bond_credit - redemption_value;
+        assert!(coupon_yield > 0);
         assert_eq!(
             Evercity::balance_everusd(&ACCOUNT),
-            1200 * 4_000_000_000_000 - 100_000_000_000_000
+            balance - redemption_value - coupon_yield
         );
+        assert!(Evercity::evercity_balance().is_ok());
 
-        assert_ok!(Evercity::bond_withdraw_everusd(
-            Origin::signed(INVESTOR1),
-            bondid
-        ));
+        for investor in &[INVESTOR1, INVESTOR2] {
+            let balance = Evercity::balance_everusd(investor);
+            assert_ok!(Evercity::bond_withdraw_everusd(
+                Origin::signed(*investor),
+                bondid
+            ));
+            assert_eq!(
+                Evercity::balance_everusd(investor),
+                balance + 600 * 4_080_000_000_000 + coupon_yield / 2
+            );
+        }
         let chain_bond_item = Evercity::get_bond(&bondid);
-        assert_eq!(chain_bond_item.coupon_yield, 14_991_780_821_760);
-        assert_eq!(chain_bond_item.get_debt(), 0);
-        // 1.9 % - (600 + 600) x 4000 usd - 120 days
-        assert_eq!(chain_bond_item.bond_credit, 29_983_561_643_520);
-        assert_eq!(
-            chain_bond_item.get_free_balance(),
-            100_000_000_000_000 - 29_983_561_643_520
-        );
+        assert_eq!(chain_bond_item.coupon_yield, chain_bond_item.bond_credit);
         assert!(Evercity::evercity_balance().is_ok());
+
+        assert_noop!(
+            Evercity::bond_call(Origin::signed(ACCOUNT), bondid),
+            RuntimeError::BondStateNotPermitAction
+        );
     });
 }
 
+fn get_test_amortization_schedule(bond: &BondInnerStruct) -> Vec<u32> {
+    // 10% of par value is repaid at the end of the first payment period after start period
+    let mut schedule = vec![0; bond.get_periods() as usize];
+    schedule[1] = 1000;
+    schedule
+}
+
 #[test]
-fn bond_deposit_return_after_redemption() {
-    const ACCOUNT: u64 = 3;
+fn bond_with_amortization_schedule_validation() {
+    let mut bond = get_test_bond().inner;
+    bond.amortization_schedule = get_test_amortization_schedule(&bond);
+    assert!(bond.is_valid(DEFAULT_DAY_DURATION));
+    assert!(!bond.is_financial_options_eq(&get_test_bond().inner));
+
+    // schedule should cover every payment period
+    let mut bond1 = bond.clone();
+    bond1.amortization_schedule.pop();
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+
+    // principal installments cannot exceed par value
+    let mut bond1 = bond.clone();
+    bond1.amortization_schedule[2] = 9001;
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+    bond1.amortization_schedule[2] = 9000;
+    assert!(bond1.is_valid(DEFAULT_DAY_DURATION));
+
+    // the first period of bond without start period has zero length
+    let mut bond1 = get_test_bond_stable().inner;
+    bond1.amortization_schedule = vec![0; bond1.get_periods() as usize];
+    assert!(bond1.is_valid(DEFAULT_DAY_DURATION));
+    bond1.amortization_schedule[0] = 1000;
+    assert!(!bond1.is_valid(DEFAULT_DAY_DURATION));
+}
+
+#[test]
+fn bond_amortization_repays_principal() {
+    const ACCOUNT1: u64 = 3;
+    const ACCOUNT2: u64 = 7;
     const INVESTOR1: u64 = 4;
-    let bondid: BondId = "BOND".into();
+    const INVESTOR2: u64 = 6;
+    let bondid1: BondId = "BOND1".into();
+    let bondid2: BondId = "BOND2".into();
 
     new_test_ext().execute_with(|| {
         bond_grand_everusd();
         let bond = get_test_bond().inner;
-        bond_activate(bondid, ACCOUNT, bond.clone());
-        let chain_bond_item = Evercity::get_bond(&bondid);
+        let mut amortizing_bond = bond.clone();
+        amortizing_bond.amortization_schedule = get_test_amortization_schedule(&bond);
+        bond_activate(bondid1, ACCOUNT1, bond.clone());
+        bond_activate(bondid2, ACCOUNT2, amortizing_bond);
+        assert_ok!(add_token(ACCOUNT2, 10_000_000_000_000_000));
+        let mut chain_bond_item1 = Evercity::get_bond(&bondid1);
+        let mut chain_bond_item2 = Evercity::get_bond(&bondid2);
+        let start_period = bond.start_period.unwrap_or(0);
+
+        let now = chain_bond_item1.active_start_date
+            + 1000_u64 * (start_period + 2 * bond.payment_period) as u64;
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(now);
+        Evercity::calc_and_store_bond_coupon_yield(&bondid1, &mut chain_bond_item1, now);
+        Evercity::calc_and_store_bond_coupon_yield(&bondid2, &mut chain_bond_item2, now);
 
-        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
-            chain_bond_item.active_start_date
-                + 1000_u64
-                    * (bond.start_period.unwrap_or(0) + bond.bond_duration * bond.payment_period+ 1) as u64,
+        let yields1: Vec<_> = Evercity::get_coupon_yields(&bondid1)
+            .into_iter()
+            .map(|x| x.total_yield)
+            .collect();
+        let yields2: Vec<_> = Evercity::get_coupon_yields(&bondid2)
+            .into_iter()
+            .map(|x| x.total_yield)
+            .collect();
+        assert_eq!(yields2.len(), 3);
+        assert_eq!(yields1[0], yields2[0]);
+        // principal installment is accrued along with coupon yield
+        assert_eq!(
+            yields2[1] - yields2[0],
+            yields1[1] - yields1[0] + 1200 * 400_000_000_000
         );
-        // add everusd to pay off bond yield
+        // coupon yield is accrued on reduced principal
+        assert!(yields2[2] - yields2[1] < yields1[2] - yields1[1]);
+        assert_eq!(chain_bond_item2.bond_credit, yields2[2]);
+
+        assert_ok!(Evercity::bond_deposit_everusd(
+            Origin::signed(ACCOUNT2),
+            bondid2,
+            yields2[2]
+        ));
+        let balance = Evercity::balance_everusd(&INVESTOR1);
+        assert_ok!(Evercity::bond_withdraw_everusd(
+            Origin::signed(INVESTOR1),
+            bondid2
+        ));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), balance + yields2[2] / 2);
+        assert_eq!(
+            Evercity::get_bond(&bondid2).outstanding_value(600, 3),
+            600 * 3_600_000_000_000
+        );
+        assert!(Evercity::evercity_balance().is_ok());
+
+        // outstanding principal is repaid at maturity date
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+            chain_bond_item2.active_start_date
+                + 1000_u64 * (start_period + bond.bond_duration * bond.payment_period) as u64,
+        );
+        assert_ok!(Evercity::bond_redeem(Origin::signed(ACCOUNT2), bondid2));
+        let chain_bond_item2 = Evercity::get_bond(&bondid2);
+        let total_yield = Evercity::get_coupon_yields(&bondid2)
+            .last()
+            .map(|x| x.total_yield)
+            .unwrap();
+        assert_eq!(
+            chain_bond_item2.bond_credit,
+            total_yield + 1200 * 3_600_000_000_000
+        );
+
+        for investor in &[INVESTOR1, INVESTOR2] {
+            assert_ok!(Evercity::bond_withdraw_everusd(
+                Origin::signed(*investor),
+                bondid2
+            ));
+        }
+        let chain_bond_item2 = Evercity::get_bond(&bondid2);
+        assert_eq!(chain_bond_item2.coupon_yield, chain_bond_item2.bond_credit);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_unit_put_before_maturity() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        let mut bond = get_test_bond().inner;
+        bond.put_schedule = Some(BondPutScheduleStruct {
+            put_periods: vec![2],
+        });
+        bond_activate(bondid, ACCOUNT, bond.clone());
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        let start_period = bond.start_period.unwrap_or(0);
+
+        // the first period is not a put date
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+            chain_bond_item.active_start_date + 1000_u64 * start_period as u64,
+        );
+        assert_noop!(
+            Evercity::bond_unit_put(Origin::signed(INVESTOR1), bondid, 100),
+            RuntimeError::BondOutOfOrder
+        );
+
+        let put_date = chain_bond_item.active_start_date
+            + 1000_u64 * (start_period + bond.payment_period) as u64;
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(put_date);
+        assert_noop!(
+            Evercity::bond_unit_put(Origin::signed(INVESTOR1), bondid, 601),
+            RuntimeError::BalanceOverdraft
+        );
+        // issuer can deposit EverUSD during interest pay period
+        assert_err!(
+            Evercity::bond_unit_put(Origin::signed(INVESTOR1), bondid, 100),
+            RuntimeError::BondFundShortage
+        );
+        assert_ok!(Evercity::bond_deposit_everusd(
+            Origin::signed(ACCOUNT),
+            bondid,
+            1_000_000_000_000_000
+        ));
+
+        let balance = Evercity::balance_everusd(&INVESTOR1);
+        assert_ok!(Evercity::bond_unit_put(Origin::signed(INVESTOR1), bondid, 100));
+        let coupon_yield =
+            Evercity::balance_everusd(&INVESTOR1) - balance - 100 * 4_000_000_000_000;
+        assert!(coupon_yield > 0);
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.state, BondState::ACTIVE);
+        assert_eq!(chain_bond_item.issued_amount, 1100);
+        let bond_units: BondUnitAmount = Evercity::bond_holder_packages(&bondid, &INVESTOR1)
+            .iter()
+            .map(|p| p.bond_units)
+            .sum();
+        assert_eq!(bond_units, 500);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        // bond fund cannot pay off after interest pay period
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+            put_date + 1000_u64 * bond.interest_pay_period.unwrap_or(0) as u64,
+        );
+        assert_ok!(Evercity::bond_unit_put(Origin::signed(INVESTOR2), bondid, 600));
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.state, BondState::BANKRUPT);
+        assert_eq!(chain_bond_item.issued_amount, 1100);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+fn get_test_restructuring_terms() -> BondRestructuringTermsStruct {
+    BondRestructuringTermsStruct {
+        duration_extension: 2,
+        interest_rate_base_value: 1500,
+        interest_rate_margin_cap: Some(3000),
+        interest_rate_margin_floor: Some(1000),
+        haircut_bps: 2000,
+    }
+}
+
+/// Activates the bond and moves it to BANKRUPT state, when Issuer misses coupon payment
+fn bond_bankrupt(bondid: BondId, acc: u64) -> BondStruct {
+    const INVESTOR1: u64 = 4;
+    bond_activate(bondid, acc, get_test_bond().inner);
+    let chain_bond_item = Evercity::get_bond(&bondid);
+    <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+        chain_bond_item.active_start_date + days2timestamp(160),
+    );
+    assert_ok!(Evercity::bond_withdraw_everusd(
+        Origin::signed(INVESTOR1),
+        bondid
+    ));
+    let chain_bond_item = Evercity::get_bond(&bondid);
+    assert_eq!(chain_bond_item.state, BondState::BANKRUPT);
+    chain_bond_item
+}
+
+#[test]
+fn bond_restructuring_approved() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const MANAGER: u64 = 8;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        let chain_bond_item = bond_bankrupt(bondid, ACCOUNT);
+        let deadline = Timestamp::get() + days2timestamp(10);
+
+        assert_noop!(
+            Evercity::bond_restructuring_propose(
+                Origin::signed(INVESTOR1),
+                bondid,
+                get_test_restructuring_terms(),
+                deadline
+            ),
+            RuntimeError::BondAccessDenied
+        );
+        let mut terms = get_test_restructuring_terms();
+        terms.haircut_bps = 10_000;
+        assert_noop!(
+            Evercity::bond_restructuring_propose(Origin::signed(ACCOUNT), bondid, terms, deadline),
+            RuntimeError::RestructuringParamIncorrect
+        );
+        let mut terms = get_test_restructuring_terms();
+        terms.duration_extension = crate::MAX_BOND_DURATION;
+        assert_noop!(
+            Evercity::bond_restructuring_propose(Origin::signed(ACCOUNT), bondid, terms, deadline),
+            RuntimeError::RestructuringParamIncorrect
+        );
+        let mut terms = get_test_restructuring_terms();
+        terms.interest_rate_margin_cap = None;
+        assert_noop!(
+            Evercity::bond_restructuring_propose(Origin::signed(ACCOUNT), bondid, terms, deadline),
+            RuntimeError::RestructuringParamIncorrect
+        );
+        assert_ok!(Evercity::bond_restructuring_propose(
+            Origin::signed(ACCOUNT),
+            bondid,
+            get_test_restructuring_terms(),
+            deadline
+        ));
+        assert_noop!(
+            Evercity::bond_restructuring_propose(
+                Origin::signed(ACCOUNT),
+                bondid,
+                get_test_restructuring_terms(),
+                deadline
+            ),
+            RuntimeError::RestructuringInProgress
+        );
+        // Bond Units cannot change hands while bondholders vote
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR2), bondid, chain_bond_item.nonce, 1),
+            RuntimeError::RestructuringInProgress
+        );
+
+        assert_ok!(Evercity::bond_restructuring_vote(
+            Origin::signed(INVESTOR1),
+            bondid,
+            true
+        ));
+        assert_noop!(
+            Evercity::bond_restructuring_vote(Origin::signed(INVESTOR1), bondid, true),
+            RuntimeError::RestructuringAlreadyVoted
+        );
+        assert_noop!(
+            Evercity::bond_restructuring_vote(Origin::signed(MANAGER), bondid, true),
+            RuntimeError::BondAccessDenied
+        );
+        // 600 of 1200 Bond Units is not a majority
+        assert_eq!(Evercity::get_bond(&bondid).state, BondState::BANKRUPT);
+        assert_eq!(Evercity::bond_restructuring(&bondid).unwrap().votes_for, 600);
+
+        assert_ok!(Evercity::bond_restructuring_vote(
+            Origin::signed(INVESTOR2),
+            bondid,
+            true
+        ));
+        assert!(Evercity::bond_restructuring(&bondid).is_none());
+        assert_eq!(Evercity::bond_restructuring_vote(&bondid, &INVESTOR1), None);
+
+        let restructured_item = Evercity::get_bond(&bondid);
+        assert_eq!(restructured_item.state, BondState::ACTIVE);
+        assert_eq!(restructured_item.nonce, chain_bond_item.nonce + 1);
+        assert_eq!(restructured_item.inner.bond_duration, 14);
+        assert_eq!(restructured_item.inner.interest_rate_base_value, 1500);
+        assert_eq!(restructured_item.inner.bond_units_base_price, 4_000_000_000_000);
+        assert!(restructured_item.inner.is_valid(DEFAULT_DAY_DURATION));
+        assert_eq!(Evercity::impact_reports(&bondid).len(), 14);
+        // coupon yield of passed periods is accrued on previous terms
+        assert_eq!(Evercity::get_coupon_yields(&bondid).len(), 2);
+        // haircut reduces principal from the restructuring period only
+        assert_eq!(restructured_item.principal_haircuts, vec![(2, 2000)]);
+        assert_eq!(restructured_item.unit_principal(1), 4_000_000_000_000);
+        assert_eq!(restructured_item.unit_principal(2), 3_200_000_000_000);
+        assert_eq!(
+            Evercity::bond_accrual_moment(&bondid),
+            restructured_item.coupon_accrual_moment(2)
+        );
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_restructuring_haircut_keeps_accrued_yield() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_bankrupt(bondid, ACCOUNT);
+        let accrued_yields = Evercity::get_coupon_yields(&bondid);
+        assert_eq!(accrued_yields.len(), 2);
+        // INVESTOR1 has received a part of accrued coupon yield before bankruptcy
+        let paid: EverUSDBalance = Evercity::bond_holder_packages(&bondid, &INVESTOR1)
+            .iter()
+            .map(|p| p.coupon_yield)
+            .sum();
+        assert!(paid > 0);
+        let balances = [
+            Evercity::balance_everusd(&INVESTOR1) - paid,
+            Evercity::balance_everusd(&INVESTOR2),
+        ];
+
+        let deadline = Timestamp::get() + days2timestamp(10);
+        assert_ok!(Evercity::bond_restructuring_propose(
+            Origin::signed(ACCOUNT),
+            bondid,
+            get_test_restructuring_terms(),
+            deadline
+        ));
+        for investor in &[INVESTOR1, INVESTOR2] {
+            assert_ok!(Evercity::bond_restructuring_vote(
+                Origin::signed(*investor),
+                bondid,
+                true
+            ));
+        }
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.state, BondState::ACTIVE);
+        assert_eq!(
+            &Evercity::get_coupon_yields(&bondid)[..2],
+            &accrued_yields[..]
+        );
+
+        // redeem the bond at new maturity date
+        let bond = &chain_bond_item.inner;
+        let start_period = bond.start_period.unwrap_or(0);
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+            chain_bond_item.active_start_date
+                + 1000_u64 * (start_period + bond.bond_duration * bond.payment_period) as u64,
+        );
+        assert_ok!(add_token(ACCOUNT, 10_000_000_000_000_000));
+        assert_ok!(Evercity::bond_redeem(Origin::signed(ACCOUNT), bondid));
+        let total_yield = Evercity::get_coupon_yields(&bondid)
+            .last()
+            .map(|x| x.total_yield)
+            .unwrap();
+        assert_eq!(
+            Evercity::get_bond(&bondid).bond_credit,
+            total_yield + 1200 * 3_200_000_000_000
+        );
+
+        // every bondholder receives the whole accrued coupon yield and reduced principal
+        for (investor, balance) in [INVESTOR1, INVESTOR2].iter().zip(balances.iter()) {
+            assert_ok!(Evercity::bond_withdraw_everusd(
+                Origin::signed(*investor),
+                bondid
+            ));
+            assert_eq!(
+                Evercity::balance_everusd(investor),
+                balance + total_yield / 2 + 600 * 3_200_000_000_000
+            );
+        }
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.coupon_yield, chain_bond_item.bond_credit);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_restructuring_rejected_and_cancelled() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_bankrupt(bondid, ACCOUNT);
+        let deadline = Timestamp::get() + days2timestamp(10);
+
+        assert_ok!(Evercity::bond_restructuring_propose(
+            Origin::signed(ACCOUNT),
+            bondid,
+            get_test_restructuring_terms(),
+            deadline
+        ));
+        // half of Bond Units against the proposal
+        assert_ok!(Evercity::bond_restructuring_vote(
+            Origin::signed(INVESTOR1),
+            bondid,
+            false
+        ));
+        assert!(Evercity::bond_restructuring(&bondid).is_none());
+        assert_eq!(Evercity::get_bond(&bondid).state, BondState::BANKRUPT);
+
+        assert_ok!(Evercity::bond_restructuring_propose(
+            Origin::signed(ACCOUNT),
+            bondid,
+            get_test_restructuring_terms(),
+            deadline
+        ));
+        assert_noop!(
+            Evercity::bond_restructuring_cancel(Origin::signed(INVESTOR2), bondid),
+            RuntimeError::BondAccessDenied
+        );
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(deadline + 1);
+        assert_noop!(
+            Evercity::bond_restructuring_vote(Origin::signed(INVESTOR2), bondid, true),
+            RuntimeError::RestructuringObsolete
+        );
+        // anyone can remove expired proposal
+        assert_ok!(Evercity::bond_restructuring_cancel(
+            Origin::signed(INVESTOR2),
+            bondid
+        ));
+        assert!(Evercity::bond_restructuring(&bondid).is_none());
+        assert_noop!(
+            Evercity::bond_restructuring_cancel(Origin::signed(ACCOUNT), bondid),
+            RuntimeError::RestructuringNotFound
+        );
+    });
+}
+
+#[test]
+fn bond_proposal_passed_with_snapshot_votes() {
+    const MASTER: u64 = 1;
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const INVESTOR3: u64 = 7;
+    const MANAGER: u64 = 8;
+    const NEW_AUDITOR: u64 = 9;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        let deadline = Timestamp::get() + days2timestamp(10);
+
+        assert_noop!(
+            Evercity::bond_proposal_create(
+                Origin::signed(MANAGER),
+                bondid,
+                BondProposalAction::SetAuditor(NEW_AUDITOR),
+                deadline
+            ),
+            RuntimeError::BondAccessDenied
+        );
+        assert_noop!(
+            Evercity::bond_proposal_create(
+                Origin::signed(INVESTOR1),
+                bondid,
+                BondProposalAction::SetAuditor(NEW_AUDITOR),
+                deadline
+            ),
+            RuntimeError::AccountRoleParamIncorrect
+        );
+        assert_ok!(Evercity::account_add_with_role_and_data(
+            Origin::signed(MASTER),
+            NEW_AUDITOR,
+            AUDITOR_ROLE_MASK,
+            90
+        ));
+        assert_ok!(Evercity::bond_proposal_create(
+            Origin::signed(INVESTOR1),
+            bondid,
+            BondProposalAction::SetAuditor(NEW_AUDITOR),
+            deadline
+        ));
+        // Issuer can't cancel proposal of bondholder
+        assert_noop!(
+            Evercity::bond_proposal_cancel(Origin::signed(ACCOUNT), bondid, 0),
+            RuntimeError::BondAccessDenied
+        );
+        let proposal = Evercity::bond_proposal(&bondid, 0).unwrap();
+        assert_eq!(proposal.snapshot_units, 1200);
+        assert_eq!(Evercity::bond_proposal_snapshot((bondid, 0), &INVESTOR1), 600);
+
+        // Bond Units, sold during the voting, are voted by the former bondholder
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 600,
+            amount: 600 * 3_000_000_000_000,
+        };
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.clone()
+        ));
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR2),
+            bondid,
+            INVESTOR1,
+            0,
+            lot.bond_units
+        ));
+        assert_ok!(Evercity::bond_proposal_vote(
+            Origin::signed(INVESTOR2),
+            bondid,
+            0,
+            true
+        ));
+        assert_noop!(
+            Evercity::bond_proposal_vote(Origin::signed(INVESTOR2), bondid, 0, true),
+            RuntimeError::ProposalAlreadyVoted
+        );
+        assert_noop!(
+            Evercity::bond_proposal_vote(Origin::signed(MANAGER), bondid, 0, true),
+            RuntimeError::BondAccessDenied
+        );
+        // 600 of 1200 Bond Units is not a majority
+        assert_eq!(Evercity::bond_proposal(&bondid, 0).unwrap().votes_for, 600);
+        assert_eq!(Evercity::get_bond(&bondid).auditor, chain_bond_item.auditor);
+
+        assert_ok!(Evercity::bond_proposal_vote(
+            Origin::signed(INVESTOR1),
+            bondid,
+            0,
+            true
+        ));
+        assert!(Evercity::bond_proposal(&bondid, 0).is_none());
+        assert_eq!(Evercity::bond_proposal_snapshot((bondid, 0), &INVESTOR1), 0);
+        assert_eq!(Evercity::bond_proposal_vote((bondid, 0), &INVESTOR2), None);
+
+        let chain_bond_item_changed = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item_changed.auditor, NEW_AUDITOR);
+        assert_eq!(chain_bond_item_changed.nonce, chain_bond_item.nonce + 1);
+
+        // bondholder with small stake can't open proposals
+        assert_ok!(Evercity::bond_unit_transfer(
+            Origin::signed(INVESTOR2),
+            bondid,
+            INVESTOR3,
+            10
+        ));
+        assert_noop!(
+            Evercity::bond_proposal_create(
+                Origin::signed(INVESTOR3),
+                bondid,
+                BondProposalAction::Resolution(BlakeTwo256::hash(b"waiver")),
+                deadline
+            ),
+            RuntimeError::BondAccessDenied
+        );
+    });
+}
+
+#[test]
+fn bond_proposal_rejected_and_cancelled() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const AUDITOR: u64 = 5;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        let deadline = Timestamp::get() + days2timestamp(10);
+        let resolution = BondProposalAction::Resolution(BlakeTwo256::hash(b"waiver"));
+
+        assert_noop!(
+            Evercity::bond_proposal_create(
+                Origin::signed(ACCOUNT),
+                bondid,
+                resolution.clone(),
+                Timestamp::get()
+            ),
+            RuntimeError::ProposalParamIncorrect
+        );
+        assert_noop!(
+            Evercity::bond_proposal_create(
+                Origin::signed(ACCOUNT),
+                bondid,
+                BondProposalAction::SetImpactReporter(AUDITOR),
+                deadline
+            ),
+            RuntimeError::AccountRoleParamIncorrect
+        );
+        assert_ok!(Evercity::bond_proposal_create(
+            Origin::signed(ACCOUNT),
+            bondid,
+            resolution.clone(),
+            deadline
+        ));
+        // half of Bond Units against the proposal
+        assert_ok!(Evercity::bond_proposal_vote(
+            Origin::signed(INVESTOR1),
+            bondid,
+            0,
+            false
+        ));
+        assert!(Evercity::bond_proposal(&bondid, 0).is_none());
+        assert_eq!(Evercity::get_bond(&bondid).nonce, chain_bond_item.nonce);
+
+        assert_ok!(Evercity::bond_proposal_create(
+            Origin::signed(ACCOUNT),
+            bondid,
+            resolution,
+            deadline
+        ));
+        assert_noop!(
+            Evercity::bond_proposal_cancel(Origin::signed(INVESTOR2), bondid, 1),
+            RuntimeError::BondAccessDenied
+        );
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(deadline + 1);
+        assert_noop!(
+            Evercity::bond_proposal_vote(Origin::signed(INVESTOR2), bondid, 1, true),
+            RuntimeError::ProposalObsolete
+        );
+        // anyone can remove expired proposal
+        assert_ok!(Evercity::bond_proposal_cancel(
+            Origin::signed(INVESTOR2),
+            bondid,
+            1
+        ));
+        assert!(Evercity::bond_proposal(&bondid, 1).is_none());
+        assert_eq!(Evercity::bond_proposal_snapshot((bondid, 1), &INVESTOR2), 0);
+        assert_noop!(
+            Evercity::bond_proposal_cancel(Origin::signed(ACCOUNT), bondid, 1),
+            RuntimeError::ProposalNotFound
+        );
+    });
+}
+
+#[test]
+fn bond_deposit_bond() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        assert!(Evercity::evercity_balance().is_ok());
+
+        let bond = get_test_bond().inner;
+        bond_activate(bondid, ACCOUNT, bond.clone());
+        let chain_bond_item = Evercity::get_bond(&bondid);
+
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+            chain_bond_item.active_start_date + 1000_u64 * (bond.start_period.unwrap_or(0) + 1) as u64,
+        );
+
+        assert_eq!(chain_bond_item.bond_debit, 0);
+        assert_eq!(chain_bond_item.coupon_yield, 0);
+        assert_eq!(
+            Evercity::balance_everusd(&ACCOUNT),
+            1200 * 4_000_000_000_000
+        );
+
+        assert_ok!(Evercity::bond_deposit_everusd(
+            Origin::signed(ACCOUNT),
+            bondid,
+            100_000_000_000_000
+        ));
+
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.bond_debit, 100_000_000_000_000);
+        assert_eq!(chain_bond_item.coupon_yield, 0);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        assert_eq!(
+            Evercity::balance_everusd(&ACCOUNT),
+            1200 * 4_000_000_000_000 - 100_000_000_000_000
+        );
+
+        assert_ok!(Evercity::bond_withdraw_everusd(
+            Origin::signed(INVESTOR1),
+            bondid
+        ));
+        let chain_bond_item = Evercity::get_bond(&bondid);
+        assert_eq!(chain_bond_item.coupon_yield, 14_991_780_821_917);
+        assert_eq!(chain_bond_item.get_debt(), 0);
+        // 1.9 % - (600 + 600) x 4000 usd - 120 days
+        assert_eq!(chain_bond_item.bond_credit, 29_983_561_643_834);
+        assert_eq!(
+            chain_bond_item.get_free_balance(),
+            100_000_000_000_000 - 29_983_561_643_834
+        );
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_deposit_return_after_redemption() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        let bond = get_test_bond().inner;
+        bond_activate(bondid, ACCOUNT, bond.clone());
+        let chain_bond_item = Evercity::get_bond(&bondid);
+
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(
+            chain_bond_item.active_start_date
+                + 1000_u64
+                    * (bond.start_period.unwrap_or(0) + bond.bond_duration * bond.payment_period+ 1) as u64,
+        );
+        // add everusd to pay off bond yield
         assert_ok!(add_token(ACCOUNT, 500_000_000_000_000));
 
         assert_ok!(Evercity::bond_redeem(Origin::signed(ACCOUNT), bondid));
 
         assert_noop!(
-            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, 4, 2),
-            RuntimeError::BondStateNotPermitAction
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, 4, 2),
+            RuntimeError::BondStateNotPermitAction
+        );
+    });
+}
+
+#[test]
+fn bond_deposit_try_foreign() {
+    const ACCOUNT1: u64 = 3;
+    const ACCOUNT2: u64 = 7;
+
+    let bondid1: BondId = "BOND1".into();
+    let bondid2: BondId = "BOND2".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        assert!(Evercity::evercity_balance().is_ok());
+
+        let bond = get_test_bond().inner;
+        bond_activate(bondid1, ACCOUNT1, bond.clone());
+        bond_activate(bondid2, ACCOUNT2, bond);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        assert_noop!(
+            Evercity::bond_deposit_everusd(Origin::signed(ACCOUNT1), bondid2, 100_000_000_000_000),
+            RuntimeError::BondAccessDenied
+        );
+    });
+}
+
+#[test]
+fn bond_lot_bit_n_buy() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        assert!(Evercity::bond_check_invariant(&bondid));
+
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 600,
+            amount: 600 * 3_000_000_000_000,
+        };
+        assert!(Evercity::evercity_balance().is_ok());
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.clone()
+        ));
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR2),
+            bondid,
+            INVESTOR1,
+            0,
+            lot.bond_units
+        ));
+        assert!(Evercity::bond_check_invariant(&bondid));
+        assert!(Evercity::evercity_balance().is_ok());
+        let packages1 = Evercity::bond_holder_packages(&bondid, &INVESTOR1);
+        let bond_units1: BondUnitAmount = packages1.iter().map(|p| p.bond_units).sum();
+        let packages2 = Evercity::bond_holder_packages(&bondid, &INVESTOR2);
+        let bond_units2: BondUnitAmount = packages2.iter().map(|p| p.bond_units).sum();
+
+        assert_eq!(bond_units1, 0);
+        assert_eq!(bond_units2, 1200);
+    });
+}
+
+#[test]
+fn bond_lot_partial_settle() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 300,
+            amount: 1_000_000_000_000_000,
+        };
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.clone()
+        ));
+        // lot id is assigned by the pallet
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot
+        ));
+        let lots = Evercity::bond_unit_lots(&bondid, &INVESTOR1);
+        assert_eq!(lots.iter().map(|lot| lot.id).collect::<Vec<_>>(), vec![0, 1]);
+
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR2), bondid, INVESTOR1, 2, 100),
+            RuntimeError::LotNotFound
+        );
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR2), bondid, INVESTOR1, 1, 301),
+            RuntimeError::LotParamIncorrect
         );
+
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR2),
+            bondid,
+            INVESTOR1,
+            1,
+            100
+        ));
+        // pro-rata price is rounded down
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), balance2 - 333_333_333_333_333);
+        let lots = Evercity::bond_unit_lots(&bondid, &INVESTOR1);
+        assert_eq!(lots[1].bond_units, 200);
+        assert_eq!(lots[1].amount, 666_666_666_666_667);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        // the remainder is sold for the rest of lot amount
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR2),
+            bondid,
+            INVESTOR1,
+            1,
+            200
+        ));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR1), balance1 + 1_000_000_000_000_000);
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), balance2 - 1_000_000_000_000_000);
+        assert_eq!(Evercity::bond_unit_lots(&bondid, &INVESTOR1).len(), 1);
+
+        let bond_units2: BondUnitAmount = Evercity::bond_holder_packages(&bondid, &INVESTOR2)
+            .iter()
+            .map(|p| p.bond_units)
+            .sum();
+        assert_eq!(bond_units2, 900);
+        assert!(Evercity::bond_check_invariant(&bondid));
+        assert!(Evercity::evercity_balance().is_ok());
     });
 }
 
 #[test]
-fn bond_deposit_try_foreign() {
-    const ACCOUNT1: u64 = 3;
-    const ACCOUNT2: u64 = 7;
-
-    let bondid1: BondId = "BOND1".into();
-    let bondid2: BondId = "BOND2".into();
+fn bond_lot_checks_lockups_and_trading_halt() {
+    const ISSUER: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const MANAGER: u64 = 8;
+    let bondid: BondId = "BOND".into();
 
     new_test_ext().execute_with(|| {
+        let mut bond = get_test_bond().inner;
+        bond.lockup_period = 30 * DEFAULT_DAY_DURATION;
         bond_grand_everusd();
-        assert!(Evercity::evercity_balance().is_ok());
+        bond_activate(bondid, ISSUER, bond);
+        crate::BondRegistry::<TestRuntime>::mutate(&bondid, |item| item.manager = MANAGER);
+        let lockup_end = Evercity::get_bond(&bondid).active_start_date
+            + 30 * DEFAULT_DAY_DURATION as Moment * 1000;
 
-        let bond = get_test_bond().inner;
-        bond_activate(bondid1, ACCOUNT1, bond.clone());
-        bond_activate(bondid2, ACCOUNT2, bond);
-        assert!(Evercity::evercity_balance().is_ok());
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: lockup_end + 100_000,
+            new_bondholder: Default::default(),
+            bond_units: 100,
+            amount: 100 * 4_000_000_000_000,
+        };
+        assert_noop!(
+            Evercity::bond_unit_lot_bid(Origin::signed(INVESTOR1), bondid, lot.clone()),
+            RuntimeError::BondUnitsLocked
+        );
 
+        // anchor investor is locked up after lock-up period of the bond
         assert_noop!(
-            Evercity::bond_deposit_everusd(Origin::signed(ACCOUNT1), bondid2, 100_000_000_000_000),
+            Evercity::bond_unit_lockup_set(
+                Origin::signed(INVESTOR2),
+                bondid,
+                INVESTOR1,
+                Some(lockup_end + 10_000)
+            ),
             RuntimeError::BondAccessDenied
         );
+        assert_ok!(Evercity::bond_unit_lockup_set(
+            Origin::signed(ISSUER),
+            bondid,
+            INVESTOR1,
+            Some(lockup_end + 10_000)
+        ));
+        Timestamp::set_timestamp(lockup_end);
+        assert_noop!(
+            Evercity::bond_unit_lot_bid(Origin::signed(INVESTOR1), bondid, lot.clone()),
+            RuntimeError::BondUnitsLocked
+        );
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR2),
+            bondid,
+            lot.clone()
+        ));
+
+        // trading halt
+        assert_noop!(
+            Evercity::bond_trading_halt_set(Origin::signed(ISSUER), bondid, true),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_ok!(Evercity::bond_trading_halt_set(
+            Origin::signed(MANAGER),
+            bondid,
+            true
+        ));
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR1), bondid, INVESTOR2, 0, 100),
+            RuntimeError::BondTradingHalted
+        );
+        assert_noop!(
+            Evercity::bond_order_place(
+                Origin::signed(INVESTOR1),
+                bondid,
+                BondOrderSide::BUY,
+                4_000_000_000_000,
+                10,
+                lockup_end + 100_000
+            ),
+            RuntimeError::BondTradingHalted
+        );
+        assert_ok!(Evercity::bond_trading_halt_set(
+            Origin::signed(MANAGER),
+            bondid,
+            false
+        ));
+        assert_ok!(Evercity::bond_trading_halt_set(
+            Origin::root(),
+            bondid,
+            true
+        ));
+        assert!(Evercity::bond_trading_halt(&bondid));
+        assert_ok!(Evercity::bond_trading_halt_set(
+            Origin::root(),
+            bondid,
+            false
+        ));
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR1),
+            bondid,
+            INVESTOR2,
+            0,
+            100
+        ));
+
+        // lock-up of anchor investor is over
+        Timestamp::set_timestamp(lockup_end + 10_000);
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot
+        ));
+        assert!(Evercity::bond_check_invariant(&bondid));
+        assert!(Evercity::evercity_balance().is_ok());
     });
 }
 
 #[test]
-fn bond_lot_bit_n_buy() {
-    const ACCOUNT: u64 = 3;
+fn bond_lot_settle_checks_eligibility() {
+    const MASTER: u64 = 1;
+    const ISSUER: u64 = 3;
     const INVESTOR1: u64 = 4;
     const INVESTOR2: u64 = 6;
+    const INVESTOR3: u64 = 7;
     let bondid: BondId = "BOND".into();
 
     new_test_ext().execute_with(|| {
-        let bond = get_test_bond().inner;
         bond_grand_everusd();
-        bond_activate(bondid, ACCOUNT, bond);
+        assert_ok!(add_token(INVESTOR3, 50_000_000_000_000_000));
 
-        assert!(Evercity::bond_check_invariant(&bondid));
+        let mut bond = get_test_bond().inner;
+        bond.eligibility.allow_list_only = true;
+        bond.eligibility.min_holding = 100;
+        bond.eligibility.max_holding = Some(800);
+        bond.eligibility.max_holders = Some(2);
+        bond_release(bondid, ISSUER, bond);
+        let nonce = Evercity::get_bond(&bondid).nonce;
+
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, nonce, 600),
+            RuntimeError::BondInvestorNotAllowed
+        );
+        assert_noop!(
+            Evercity::bond_allow_list_set(Origin::signed(INVESTOR1), bondid, INVESTOR1, true),
+            RuntimeError::BondAccessDenied
+        );
+        for acc in [INVESTOR1, INVESTOR2, INVESTOR3].iter() {
+            assert_ok!(Evercity::bond_allow_list_set(
+                Origin::signed(ISSUER),
+                bondid,
+                *acc,
+                true
+            ));
+        }
+
+        // booking
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, nonce, 50),
+            RuntimeError::BondHoldingOutOfLimits
+        );
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR1), bondid, nonce, 900),
+            RuntimeError::BondHoldingOutOfLimits
+        );
+        assert_ok!(Evercity::bond_unit_package_buy(
+            Origin::signed(INVESTOR1),
+            bondid,
+            nonce,
+            600
+        ));
+        assert_ok!(Evercity::bond_unit_package_buy(
+            Origin::signed(INVESTOR2),
+            bondid,
+            nonce,
+            600
+        ));
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR3), bondid, nonce, 100),
+            RuntimeError::BondHoldersLimitExceeded
+        );
+        assert_eq!(Evercity::bond_holder_count(&bondid), 2);
+        assert_ok!(Evercity::bond_activate(
+            Origin::signed(MASTER),
+            bondid,
+            nonce
+        ));
 
+        // secondary market
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: 100000,
             new_bondholder: Default::default(),
             bond_units: 600,
-            amount: 600 * 3_000_000_000_000,
+            amount: 600 * 4_000_000_000_000,
         };
-        assert!(Evercity::evercity_balance().is_ok());
         assert_ok!(Evercity::bond_unit_lot_bid(
             Origin::signed(INVESTOR1),
             bondid,
-            lot.clone()
+            lot
         ));
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR2), bondid, INVESTOR1, 0, 250),
+            RuntimeError::BondHoldingOutOfLimits
+        );
         assert_ok!(Evercity::bond_unit_lot_settle(
             Origin::signed(INVESTOR2),
             bondid,
             INVESTOR1,
-            lot
+            0,
+            100
+        ));
+        // seller cannot keep less than minimal holding
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR3), bondid, INVESTOR1, 0, 450),
+            RuntimeError::BondHoldingOutOfLimits
+        );
+        assert_noop!(
+            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR3), bondid, INVESTOR1, 0, 400),
+            RuntimeError::BondHoldersLimitExceeded
+        );
+        // seller leaves the bond
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR3),
+            bondid,
+            INVESTOR1,
+            0,
+            500
+        ));
+        let bond_units3: BondUnitAmount = Evercity::bond_holder_packages(&bondid, &INVESTOR3)
+            .iter()
+            .map(|p| p.bond_units)
+            .sum();
+        assert_eq!(bond_units3, 500);
+        assert_eq!(Evercity::bond_holder_count(&bondid), 2);
+        assert!(!crate::BondUnitPackageRegistry::<TestRuntime>::contains_key(&bondid, &INVESTOR1));
+
+        // removed investor keeps bond units, but cannot buy more
+        assert_ok!(Evercity::bond_allow_list_set(
+            Origin::signed(ISSUER),
+            bondid,
+            INVESTOR2,
+            false
         ));
+        assert!(!Evercity::bond_allow_list(&bondid, &INVESTOR2));
+        let nonce = Evercity::get_bond(&bondid).nonce;
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR2), bondid, nonce, 10),
+            RuntimeError::BondInvestorNotAllowed
+        );
         assert!(Evercity::bond_check_invariant(&bondid));
         assert!(Evercity::evercity_balance().is_ok());
-        let packages1 = Evercity::bond_holder_packages(&bondid, &INVESTOR1);
-        let bond_units1: BondUnitAmount = packages1.iter().map(|p| p.bond_units).sum();
-        let packages2 = Evercity::bond_holder_packages(&bondid, &INVESTOR2);
-        let bond_units2: BondUnitAmount = packages2.iter().map(|p| p.bond_units).sum();
-
-        assert_eq!(bond_units1, 0);
-        assert_eq!(bond_units2, 1200);
     });
 }
 
+#[test]
+fn bond_holders_are_limited() {
+    const ISSUER: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_release(bondid, ISSUER, get_test_bond().inner);
+        let nonce = Evercity::get_bond(&bondid).nonce;
+        assert_ok!(Evercity::bond_unit_package_buy(
+            Origin::signed(INVESTOR1),
+            bondid,
+            nonce,
+            600
+        ));
+        assert_eq!(Evercity::bond_holder_count(&bondid), 1);
+
+        // platform limit applies to bonds without own limit
+        crate::BondHolderCount::insert(&bondid, MAX_BOND_HOLDERS);
+        assert_noop!(
+            Evercity::bond_unit_package_buy(Origin::signed(INVESTOR2), bondid, nonce, 600),
+            RuntimeError::BondHoldersLimitExceeded
+        );
+        // existing bondholder can buy more
+        assert_ok!(Evercity::bond_unit_package_buy(
+            Origin::signed(INVESTOR1),
+            bondid,
+            nonce,
+            100
+        ));
+        crate::BondHolderCount::insert(&bondid, 1);
+
+        // bondholder, who returned all Bond Units, leaves the bond
+        assert_ok!(Evercity::bond_unit_package_return(
+            Origin::signed(INVESTOR1),
+            bondid,
+            700
+        ));
+        assert_eq!(Evercity::bond_holder_count(&bondid), 0);
+        assert!(Evercity::bond_holder_packages(&bondid, &INVESTOR1).is_empty());
+    });
+}
 #[test]
 fn bond_lot_paid_coupon() {
     const ACCOUNT: u64 = 3;
@@ -3476,6 +6637,7 @@ fn bond_lot_paid_coupon() {
         assert_eq!(period, 1);
 
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: moment + 1,
             new_bondholder: Default::default(),
             bond_units: 400,
@@ -3489,42 +6651,256 @@ fn bond_lot_paid_coupon() {
             100_000_000_000_000
         ));
 
-        let balance1 = Evercity::balance_everusd(&INVESTOR1);
-
-        assert!(Evercity::evercity_balance().is_ok());
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+
+        assert!(Evercity::evercity_balance().is_ok());
+        assert_ok!(Evercity::bond_unit_lot_bid(
+            Origin::signed(INVESTOR1),
+            bondid,
+            lot.clone()
+        ));
+        assert_ok!(Evercity::bond_unit_lot_settle(
+            Origin::signed(INVESTOR2),
+            bondid,
+            INVESTOR1,
+            0,
+            lot.bond_units
+        ));
+        assert!(Evercity::evercity_balance().is_ok());
+
+        let packages1 = Evercity::bond_holder_packages(&bondid, &INVESTOR1);
+        let bond_units1: BondUnitAmount = packages1.iter().map(|p| p.bond_units).sum();
+
+        let packages2 = Evercity::bond_holder_packages(&bondid, &INVESTOR2);
+        let bond_units2: BondUnitAmount = packages2.iter().map(|p| p.bond_units).sum();
+
+        assert_eq!(bond_units1, 500);
+        assert_eq!(bond_units2, 1000);
+
+        let bond_units1: Vec<_> = packages1.iter().map(|p| p.bond_units).collect();
+        let bond_units2: Vec<_> = packages2.iter().map(|p| p.bond_units).collect();
+
+        assert_eq!(bond_units1, vec![500]);
+        assert_eq!(bond_units2, vec![600, 100, 200, 100]);
+        // 1.9% - 120 days - (600 + 200 + 100) units x 4000 usd =22487.671 usd
+        // @TODO calc coupon yield
+        assert_eq!(
+            Evercity::balance_everusd(&INVESTOR1) - balance1,
+            400 * 3_000_000_000_000 + 22_487_671_232_640 // 1200000000000000
+        );
+    });
+}
+
+#[test]
+fn bond_unit_transfer_pays_coupon_yield() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond.clone());
+        let chain_bond_item = Evercity::get_bond(&bondid);
+
+        assert_noop!(
+            Evercity::bond_unit_transfer(Origin::signed(INVESTOR1), bondid, ACCOUNT, 100),
+            RuntimeError::AccountNotAuthorized
+        );
+        assert_noop!(
+            Evercity::bond_unit_transfer(Origin::signed(INVESTOR1), bondid, INVESTOR2, 601),
+            RuntimeError::BalanceOverdraft
+        );
+        // first period
+        let moment = chain_bond_item.active_start_date
+            + 1000_u64 * (bond.start_period.unwrap_or(0) + 1) as u64;
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(moment);
+        assert_ok!(Evercity::bond_deposit_everusd(
+            Origin::signed(ACCOUNT),
+            bondid,
+            100_000_000_000_000
+        ));
+
+        // bond units, offered in sale lot, cannot be transferred
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: moment + 1,
+            new_bondholder: Default::default(),
+            bond_units: 550,
+            amount: 550 * 3_000_000_000_000,
+        };
         assert_ok!(Evercity::bond_unit_lot_bid(
             Origin::signed(INVESTOR1),
             bondid,
-            lot.clone()
+            lot
         ));
-        assert_ok!(Evercity::bond_unit_lot_settle(
-            Origin::signed(INVESTOR2),
+        assert_noop!(
+            Evercity::bond_unit_transfer(Origin::signed(INVESTOR1), bondid, INVESTOR2, 100),
+            RuntimeError::BalanceOverdraft
+        );
+
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_unit_transfer(
+            Origin::signed(INVESTOR1),
             bondid,
-            INVESTOR1,
-            lot
+            INVESTOR2,
+            50
         ));
+        let bond_units1: BondUnitAmount = Evercity::bond_holder_packages(&bondid, &INVESTOR1)
+            .iter()
+            .map(|p| p.bond_units)
+            .sum();
+        let bond_units2: BondUnitAmount = Evercity::bond_holder_packages(&bondid, &INVESTOR2)
+            .iter()
+            .map(|p| p.bond_units)
+            .sum();
+        assert_eq!(bond_units1, 550);
+        assert_eq!(bond_units2, 650);
+
+        // both bondholders received coupon yield of the first period for 600 units
+        let coupon1 = Evercity::balance_everusd(&INVESTOR1) - balance1;
+        let coupon2 = Evercity::balance_everusd(&INVESTOR2) - balance2;
+        assert!(coupon1 > 0);
+        assert_eq!(coupon1, coupon2);
+        assert!(Evercity::bond_check_invariant(&bondid));
         assert!(Evercity::evercity_balance().is_ok());
+    });
+}
 
-        let packages1 = Evercity::bond_holder_packages(&bondid, &INVESTOR1);
-        let bond_units1: BondUnitAmount = packages1.iter().map(|p| p.bond_units).sum();
+#[test]
+fn bond_swap_settles_both_legs() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    let bondid1: BondId = "BOND1".into();
+    let bondid2: BondId = "BOND2".into();
 
-        let packages2 = Evercity::bond_holder_packages(&bondid, &INVESTOR2);
-        let bond_units2: BondUnitAmount = packages2.iter().map(|p| p.bond_units).sum();
+    fn bond_units(bond: &BondId, bondholder: &u64) -> BondUnitAmount {
+        Evercity::bond_holder_packages(bond, bondholder)
+            .iter()
+            .map(|p| p.bond_units)
+            .sum()
+    }
 
-        assert_eq!(bond_units1, 500);
-        assert_eq!(bond_units2, 1000);
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid1, ACCOUNT, get_test_bond().inner);
+        bond_activate(bondid2, ACCOUNT, get_test_bond().inner);
 
-        let bond_units1: Vec<_> = packages1.iter().map(|p| p.bond_units).collect();
-        let bond_units2: Vec<_> = packages2.iter().map(|p| p.bond_units).collect();
+        assert_noop!(
+            Evercity::bond_swap_offer(
+                Origin::signed(INVESTOR1),
+                bondid1,
+                100,
+                0,
+                bondid1,
+                200,
+                INVESTOR2,
+                100_000
+            ),
+            RuntimeError::SwapParamIncorrect
+        );
+        assert_noop!(
+            Evercity::bond_swap_offer(
+                Origin::signed(INVESTOR1),
+                bondid1,
+                601,
+                0,
+                bondid2,
+                200,
+                INVESTOR2,
+                100_000
+            ),
+            RuntimeError::BalanceOverdraft
+        );
 
-        assert_eq!(bond_units1, vec![500]);
-        assert_eq!(bond_units2, vec![600, 100, 200, 100]);
-        // 1.9% - 120 days - (600 + 200 + 100) units x 4000 usd =22487.671 usd
-        // @TODO calc coupon yield
+        let balance1 = Evercity::balance_everusd(&INVESTOR1);
+        let balance2 = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_swap_offer(
+            Origin::signed(INVESTOR1),
+            bondid1,
+            100,
+            1_000_000_000_000,
+            bondid2,
+            200,
+            INVESTOR2,
+            100_000
+        ));
         assert_eq!(
-            Evercity::balance_everusd(&INVESTOR1) - balance1,
-            400 * 3_000_000_000_000 + 22_487_671_232_640 // 1200000000000000
+            Evercity::balance_everusd(&INVESTOR1),
+            balance1 - 1_000_000_000_000
+        );
+        assert!(Evercity::evercity_balance().is_ok());
+
+        // swap is restricted to INVESTOR2
+        assert_noop!(
+            Evercity::bond_swap_accept(Origin::signed(ACCOUNT), 0),
+            RuntimeError::SwapNotFound
+        );
+        // if one leg fails, none of them is settled
+        assert_ok!(Evercity::bond_unit_lockup_set(
+            Origin::signed(ACCOUNT),
+            bondid2,
+            INVESTOR2,
+            Some(50_000)
+        ));
+        assert_noop!(
+            Evercity::bond_swap_accept(Origin::signed(INVESTOR2), 0),
+            RuntimeError::BondUnitsLocked
+        );
+        assert_ok!(Evercity::bond_unit_lockup_set(
+            Origin::signed(ACCOUNT),
+            bondid2,
+            INVESTOR2,
+            None
+        ));
+
+        assert_ok!(Evercity::bond_swap_accept(Origin::signed(INVESTOR2), 0));
+        assert_eq!(Evercity::bond_swap(0), None);
+        assert_eq!(bond_units(&bondid1, &INVESTOR1), 500);
+        assert_eq!(bond_units(&bondid2, &INVESTOR1), 800);
+        assert_eq!(bond_units(&bondid1, &INVESTOR2), 700);
+        assert_eq!(bond_units(&bondid2, &INVESTOR2), 400);
+        assert_eq!(
+            Evercity::balance_everusd(&INVESTOR2),
+            balance2 + 1_000_000_000_000
+        );
+        assert!(Evercity::bond_check_invariant(&bondid1));
+        assert!(Evercity::bond_check_invariant(&bondid2));
+        assert!(Evercity::evercity_balance().is_ok());
+        assert_noop!(
+            Evercity::bond_swap_accept(Origin::signed(INVESTOR2), 0),
+            RuntimeError::SwapNotFound
+        );
+
+        // expired swap can be cancelled by anyone
+        assert_ok!(Evercity::bond_swap_offer(
+            Origin::signed(INVESTOR1),
+            bondid1,
+            100,
+            1_000_000_000_000,
+            bondid2,
+            100,
+            Default::default(),
+            100_000
+        ));
+        assert_noop!(
+            Evercity::bond_swap_cancel(Origin::signed(INVESTOR2), 1),
+            RuntimeError::BondAccessDenied
+        );
+        <pallet_timestamp::Module<TestRuntime>>::set_timestamp(100_001);
+        assert_noop!(
+            Evercity::bond_swap_accept(Origin::signed(INVESTOR2), 1),
+            RuntimeError::SwapObsolete
+        );
+        assert_ok!(Evercity::bond_swap_cancel(Origin::signed(INVESTOR2), 1));
+        assert_eq!(
+            Evercity::balance_everusd(&INVESTOR1),
+            balance1 - 1_000_000_000_000
         );
+        assert!(Evercity::evercity_balance().is_ok());
     });
 }
 
@@ -3541,6 +6917,7 @@ fn bond_lot_try_buy_foreign() {
         bond_activate(bondid, ACCOUNT, bond);
 
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: 100000,
             new_bondholder: 7,
             bond_units: 600,
@@ -3553,7 +6930,7 @@ fn bond_lot_try_buy_foreign() {
             lot.clone()
         ));
         assert_noop!(
-            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR2), bondid, INVESTOR1, lot),
+            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR2), bondid, INVESTOR1, 0, lot.bond_units),
             RuntimeError::LotNotFound
         );
     });
@@ -3571,6 +6948,7 @@ fn bond_lot_try_create_expired() {
         bond_activate(bondid, ACCOUNT, bond);
 
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: 100000,
             new_bondholder: Default::default(),
             bond_units: 600,
@@ -3598,6 +6976,7 @@ fn bond_lot_try_buy_expired() {
         bond_activate(bondid, ACCOUNT, bond);
 
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: 100000,
             new_bondholder: Default::default(),
             bond_units: 600,
@@ -3614,7 +6993,7 @@ fn bond_lot_try_buy_expired() {
         <pallet_timestamp::Module<TestRuntime>>::set_timestamp(1000000 + 1);
 
         assert_noop!(
-            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR2), bondid, INVESTOR1, lot),
+            Evercity::bond_unit_lot_settle(Origin::signed(INVESTOR2), bondid, INVESTOR1, 0, lot.bond_units),
             RuntimeError::LotObsolete
         );
     });
@@ -3632,6 +7011,7 @@ fn bond_lot_try_exceed_portfolio() {
         bond_activate(bondid, ACCOUNT, bond);
 
         let lot = BondUnitSaleLotStruct {
+            id: 0,
             deadline: 100000,
             new_bondholder: Default::default(),
             bond_units: 500,
@@ -3657,4 +7037,289 @@ fn bond_lot_try_exceed_portfolio() {
             lot
         ));
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn bond_order_book_match() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const PRICE: EverUSDBalance = 3_000_000_000_000;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        assert_ok!(Evercity::bond_order_place(
+            Origin::signed(INVESTOR1),
+            bondid,
+            BondOrderSide::SELL,
+            PRICE,
+            100,
+            1_000_000
+        ));
+        assert_ok!(Evercity::bond_order_place(
+            Origin::signed(INVESTOR1),
+            bondid,
+            BondOrderSide::SELL,
+            PRICE - 1_000_000_000_000,
+            100,
+            1_000_000
+        ));
+        // better price goes first
+        let asks = Evercity::bond_order_book(&bondid, BondOrderSide::SELL);
+        assert_eq!(asks.iter().map(|order| order.id).collect::<Vec<_>>(), vec![1, 0]);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        let balance = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_order_place(
+            Origin::signed(INVESTOR2),
+            bondid,
+            BondOrderSide::BUY,
+            PRICE,
+            150,
+            1_000_000
+        ));
+        // deals are made at the price of resting orders
+        assert_eq!(
+            Evercity::balance_everusd(&INVESTOR2),
+            balance - 100 * (PRICE - 1_000_000_000_000) - 50 * PRICE
+        );
+        assert!(Evercity::bond_order_book(&bondid, BondOrderSide::BUY).is_empty());
+        let asks = Evercity::bond_order_book(&bondid, BondOrderSide::SELL);
+        assert_eq!(asks.len(), 1);
+        assert_eq!(asks[0].id, 0);
+        assert_eq!(asks[0].bond_units, 50);
+
+        let bond_units = |acc: &u64| -> BondUnitAmount {
+            Evercity::bond_holder_packages(&bondid, acc)
+                .iter()
+                .map(|p| p.bond_units)
+                .sum()
+        };
+        assert_eq!(bond_units(&INVESTOR1), 450);
+        assert_eq!(bond_units(&INVESTOR2), 750);
+        assert!(Evercity::bond_check_invariant(&bondid));
+        assert!(Evercity::evercity_balance().is_ok());
+
+        // own orders are not matched
+        assert_ok!(Evercity::bond_order_place(
+            Origin::signed(INVESTOR1),
+            bondid,
+            BondOrderSide::BUY,
+            PRICE,
+            10,
+            1_000_000
+        ));
+        assert_eq!(Evercity::bond_order_book(&bondid, BondOrderSide::BUY).len(), 1);
+        assert_eq!(bond_units(&INVESTOR1), 450);
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_order_book_drops_unsettleable_orders() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const PRICE: EverUSDBalance = 3_000_000_000_000;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+
+        let balance = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_order_place(
+            Origin::signed(INVESTOR2),
+            bondid,
+            BondOrderSide::BUY,
+            PRICE,
+            100,
+            1_000_000
+        ));
+        // buyer of resting order is not eligible anymore
+        crate::BondRegistry::<TestRuntime>::mutate(&bondid, |item| {
+            item.inner.eligibility.allow_list_only = true
+        });
+
+        assert_ok!(Evercity::bond_order_place(
+            Origin::signed(INVESTOR1),
+            bondid,
+            BondOrderSide::SELL,
+            PRICE,
+            100,
+            1_000_000
+        ));
+        assert!(Evercity::bond_order_book(&bondid, BondOrderSide::BUY).is_empty());
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), balance);
+        let asks = Evercity::bond_order_book(&bondid, BondOrderSide::SELL);
+        assert_eq!(asks.len(), 1);
+        assert_eq!(asks[0].bond_units, 100);
+        assert_eq!(Evercity::bond_units_total(&bondid, &INVESTOR1), 600);
+        assert!(Evercity::bond_check_invariant(&bondid));
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_order_book_keeps_orders_when_taker_is_not_eligible() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR2: u64 = 6;
+    const INVESTOR3: u64 = 7;
+    const PRICE: EverUSDBalance = 3_000_000_000_000;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        bond_grand_everusd();
+        assert_ok!(add_token(INVESTOR3, 50_000_000_000_000_000));
+        bond_activate(bondid, ACCOUNT, get_test_bond().inner);
+        crate::BondRegistry::<TestRuntime>::mutate(&bondid, |item| {
+            item.inner.eligibility.min_holding = 50
+        });
+
+        for _ in 0..3 {
+            assert_ok!(Evercity::bond_order_place(
+                Origin::signed(INVESTOR2),
+                bondid,
+                BondOrderSide::SELL,
+                PRICE,
+                10,
+                1_000_000
+            ));
+        }
+        // every fill would leave the buyer below minimal holding
+        assert_ok!(Evercity::bond_order_place(
+            Origin::signed(INVESTOR3),
+            bondid,
+            BondOrderSide::BUY,
+            PRICE,
+            100,
+            1_000_000
+        ));
+        let asks = Evercity::bond_order_book(&bondid, BondOrderSide::SELL);
+        assert_eq!(asks.len(), 3);
+        assert!(asks.iter().all(|order| order.bond_units == 10));
+        let bids = Evercity::bond_order_book(&bondid, BondOrderSide::BUY);
+        assert_eq!(bids.len(), 1);
+        assert_eq!(bids[0].bond_units, 100);
+        assert_eq!(Evercity::bond_units_total(&bondid, &INVESTOR3), 0);
+        assert!(Evercity::bond_check_invariant(&bondid));
+        assert!(Evercity::evercity_balance().is_ok());
+    });
+}
+
+#[test]
+fn bond_order_book_cancel_and_expire() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    const INVESTOR2: u64 = 6;
+    const PRICE: EverUSDBalance = 3_000_000_000_000;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        let balance = Evercity::balance_everusd(&INVESTOR2);
+        assert_ok!(Evercity::bond_order_place(
+            Origin::signed(INVESTOR2),
+            bondid,
+            BondOrderSide::BUY,
+            PRICE,
+            100,
+            1_000_000
+        ));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), balance - 100 * PRICE);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        assert_noop!(
+            Evercity::bond_order_cancel(Origin::signed(INVESTOR1), bondid, 0),
+            RuntimeError::BondAccessDenied
+        );
+        assert_ok!(Evercity::bond_order_cancel(
+            Origin::signed(INVESTOR2),
+            bondid,
+            0
+        ));
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), balance);
+        assert_noop!(
+            Evercity::bond_order_cancel(Origin::signed(INVESTOR2), bondid, 0),
+            RuntimeError::OrderNotFound
+        );
+
+        assert_ok!(Evercity::bond_order_place(
+            Origin::signed(INVESTOR2),
+            bondid,
+            BondOrderSide::BUY,
+            PRICE,
+            100,
+            1_000_000
+        ));
+        Timestamp::set_timestamp(1_000_001);
+        Evercity::on_initialize(1);
+        assert!(Evercity::bond_order_book(&bondid, BondOrderSide::BUY).is_empty());
+        assert_eq!(Evercity::balance_everusd(&INVESTOR2), balance);
+        assert!(Evercity::evercity_balance().is_ok());
+
+        assert_noop!(
+            Evercity::bond_order_place(
+                Origin::signed(INVESTOR2),
+                bondid,
+                BondOrderSide::BUY,
+                PRICE,
+                100,
+                1_000_000
+            ),
+            RuntimeError::OrderParamIncorrect
+        );
+    });
+}
+
+#[test]
+fn bond_order_try_exceed_portfolio() {
+    const ACCOUNT: u64 = 3;
+    const INVESTOR1: u64 = 4;
+    let bondid: BondId = "BOND".into();
+
+    new_test_ext().execute_with(|| {
+        let bond = get_test_bond().inner;
+        bond_grand_everusd();
+        bond_activate(bondid, ACCOUNT, bond);
+
+        assert_ok!(Evercity::bond_order_place(
+            Origin::signed(INVESTOR1),
+            bondid,
+            BondOrderSide::SELL,
+            3_000_000_000_000,
+            500,
+            1_000_000
+        ));
+        assert_noop!(
+            Evercity::bond_order_place(
+                Origin::signed(INVESTOR1),
+                bondid,
+                BondOrderSide::SELL,
+                3_000_000_000_000,
+                101,
+                1_000_000
+            ),
+            RuntimeError::BalanceOverdraft
+        );
+        // units, locked by sell orders, cannot be sold with lots
+        let lot = BondUnitSaleLotStruct {
+            id: 0,
+            deadline: 100000,
+            new_bondholder: Default::default(),
+            bond_units: 101,
+            amount: 101 * 3_000_000_000_000,
+        };
+        assert_noop!(
+            Evercity::bond_unit_lot_bid(Origin::signed(INVESTOR1), bondid, lot),
+            RuntimeError::BalanceOverdraft
+        );
+    });
+}
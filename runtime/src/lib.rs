@@ -262,6 +262,8 @@ parameter_types! {
     pub const MintRequestTtl: u32 = DEFAULT_DAY_DURATION as u32 * 7 * 1000;
     pub const MaxMintAmount: pallet_evercity::EverUSDBalance = 60_000_000_000_000_000;
     pub const TimeStep: pallet_evercity::BondPeriod = DEFAULT_DAY_DURATION;
+    /// Coupon accruals may use up to 10% of block weight.
+    pub MaxAccrualWeight: Weight = Perbill::from_percent(10) * BlockWeights::get().max_block;
 }
 
 impl pallet_evercity::Config for Runtime {
@@ -270,7 +272,9 @@ impl pallet_evercity::Config for Runtime {
     type MintRequestTtl = MintRequestTtl;
     type MaxMintAmount = MaxMintAmount;
     type TimeStep = TimeStep;
-    type WeightInfo = ();
+    type MasterOrigin = frame_system::EnsureRoot<AccountId>;
+    type MaxAccrualWeight = MaxAccrualWeight;
+    type WeightInfo = pallet_evercity::weights::SubstrateWeight<Runtime>;
     type OnAddAccount = ();
     type OnAddBond = ();
 }
@@ -456,6 +460,35 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_evercity::runtime_api::BondApi<Block, AccountId, Moment, Hash> for Runtime {
+        fn get_impact_reports(bond: pallet_evercity::BondId) -> Vec<pallet_evercity::PeriodDataStruct> {
+            Evercity::get_impact_reports(bond)
+        }
+
+        fn get_bond(bond: pallet_evercity::BondId) -> pallet_evercity::BondStruct<AccountId, Moment, Hash> {
+            Evercity::get_bond(&bond)
+        }
+
+        fn get_bond_yields(bond: pallet_evercity::BondId) -> Vec<pallet_evercity::PeriodYield> {
+            Evercity::get_coupon_yields(&bond)
+        }
+
+        fn get_bondholder_packages(
+            bond: pallet_evercity::BondId,
+            bondholder: AccountId,
+        ) -> Vec<pallet_evercity::BondUnitPackage> {
+            Evercity::bond_holder_packages(&bond, &bondholder)
+        }
+
+        fn get_everusd_balance(account: AccountId) -> pallet_evercity::EverUSDBalance {
+            Evercity::balance_everusd(&account)
+        }
+
+        fn get_account(account: AccountId) -> pallet_evercity::account::EvercityAccountStructT<Moment> {
+            Evercity::account_registry(account)
+        }
+    }
+
     impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
         fn query_info(
             uxt: <Block as BlockT>::Extrinsic,
@@ -470,4 +503,40 @@ impl_runtime_apis! {
             TransactionPayment::query_fee_details(uxt, len)
         }
     }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    impl frame_benchmarking::Benchmark<Block> for Runtime {
+        fn dispatch_benchmark(
+            config: frame_benchmarking::BenchmarkConfig
+        ) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+            use frame_benchmarking::{Benchmarking, BenchmarkBatch, add_benchmark, TrackedStorageKey};
+
+            use frame_system_benchmarking::Module as SystemBench;
+            impl frame_system_benchmarking::Config for Runtime {}
+
+            let whitelist: Vec<TrackedStorageKey> = vec![
+                // Block Number
+                hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b2ef2935ab1d9a85c1ab5e0e2f").to_vec().into(),
+                // Total Issuance
+                hex_literal::hex!("c2261276cc9d1f8598ea4b6a74b15c2f57c875e4cff74148e4628f264b974c80").to_vec().into(),
+                // Execution Phase
+                hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef7ff553b5a9862a516939d82b3d3d8661a").to_vec().into(),
+                // Event Count
+                hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef70a98fdbe9ce6c55837576c60c7af3850").to_vec().into(),
+                // System Events
+                hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef780d41e5e16056765bc8461851072c9d7").to_vec().into(),
+            ];
+
+            let mut batches = Vec::<BenchmarkBatch>::new();
+            let params = (&config, &whitelist);
+
+            add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
+            add_benchmark!(params, batches, pallet_balances, Balances);
+            add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+            add_benchmark!(params, batches, pallet_evercity, Evercity);
+
+            if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
+            Ok(batches)
+        }
+    }
 }